@@ -0,0 +1,44 @@
+//! Compares interning present 10 KB keys with and without a precomputed hash.
+
+use std::{collections::hash_map::RandomState, hash::BuildHasher, hint::black_box};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use xgx_intern::Interner;
+
+/// The number of keys, all sharing a 10 KB prefix.
+const KEYS: usize = 64;
+
+fn large_keys(c: &mut Criterion) {
+    let hasher = RandomState::new();
+    let prefix = "k".repeat(10_000);
+    let keys: Vec<String> = (0..KEYS).map(|i| format!("{prefix}{i}")).collect();
+    let hashes: Vec<u64> = keys.iter().map(|k| hasher.hash_one(k.as_str())).collect();
+
+    let mut interner = Interner::<String, RandomState>::new(hasher);
+    for key in &keys {
+        interner.intern_ref(key.as_str()).unwrap();
+    }
+
+    let mut group = c.benchmark_group("large_keys_present");
+    group.bench_function("intern_ref", |b| {
+        b.iter(|| {
+            for key in &keys {
+                black_box(interner.intern_ref(black_box(key.as_str())).unwrap());
+            }
+        });
+    });
+    group.bench_function("intern_ref_prehashed", |b| {
+        b.iter(|| {
+            for (key, &hash) in keys.iter().zip(&hashes) {
+                let h = interner
+                    .intern_ref_prehashed(black_box(hash), black_box(key.as_str()), || key.clone())
+                    .unwrap();
+                black_box(h);
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, large_keys);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+//! Measures interning distinct long keys, where every call is a miss.
+//!
+//! `intern_ref` hashes each key once for both the probe and the insert. The
+//! `lookup_then_intern` baseline probes with `lookup_handle` first, as a
+//! separate lookup and insert would, and so hashes every key twice.
+
+use std::{
+    cell::Cell,
+    collections::hash_map::{DefaultHasher, RandomState},
+    hash::BuildHasher,
+    hint::black_box,
+    rc::Rc,
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use xgx_intern::Interner;
+
+/// The number of distinct keys interned per iteration.
+const KEYS: usize = 1_000;
+
+/// A `BuildHasher` counting how many hashers it has built, i.e. how many
+/// keys have been hashed.
+#[derive(Clone, Default)]
+struct CountingState {
+    inner: RandomState,
+    hashes: Rc<Cell<usize>>,
+}
+
+impl BuildHasher for CountingState {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        self.hashes.set(self.hashes.get() + 1);
+        self.inner.build_hasher()
+    }
+}
+
+fn intern_all(keys: &[String], state: &CountingState) -> usize {
+    let mut interner = Interner::<String, CountingState>::with_capacity(state.clone(), keys.len());
+    for key in keys {
+        black_box(interner.intern_ref(black_box(key.as_str())).unwrap());
+    }
+    interner.len()
+}
+
+fn lookup_then_intern_all(keys: &[String], state: &CountingState) -> usize {
+    let mut interner = Interner::<String, CountingState>::with_capacity(state.clone(), keys.len());
+    for key in keys {
+        if interner.lookup_handle(key.as_str()).unwrap().is_none() {
+            black_box(interner.intern_ref(black_box(key.as_str())).unwrap());
+        }
+    }
+    interner.len()
+}
+
+fn miss_heavy(c: &mut Criterion) {
+    let prefix = "m".repeat(1_000);
+    let keys: Vec<String> = (0..KEYS).map(|i| format!("{prefix}{i}")).collect();
+
+    // Report the hashes per key once. The interners are sized up front, so
+    // the index never grows and rehashes stored keys.
+    for (name, run) in [
+        (
+            "intern_ref",
+            intern_all as fn(&[String], &CountingState) -> usize,
+        ),
+        ("lookup_then_intern", lookup_then_intern_all),
+    ] {
+        let state = CountingState::default();
+        run(&keys, &state);
+        println!("{name}: {} hashes for {KEYS} keys", state.hashes.get());
+    }
+
+    let state = CountingState::default();
+    let mut group = c.benchmark_group("miss_heavy");
+    group.bench_function("intern_ref", |b| b.iter(|| intern_all(&keys, &state)));
+    group.bench_function("lookup_then_intern", |b| {
+        b.iter(|| lookup_then_intern_all(&keys, &state));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, miss_heavy);
+criterion_main!(benches);
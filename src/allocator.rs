@@ -0,0 +1,468 @@
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    str,
+};
+
+use allocator_api2::{
+    alloc::{Allocator, Global},
+    vec::Vec,
+};
+use hashbrown::HashTable;
+
+use crate::{FromRef, InternerError};
+
+/// An interner whose storage is allocated with a custom allocator `A`.
+///
+/// Both the values and the hash index live in collections generic over an
+/// [`allocator_api2`] allocator, so every allocation the interner makes goes
+/// through `A`: a bump arena, a pool, or an instrumented allocator. Values
+/// that own heap memory themselves (e.g. `String`) still allocate it
+/// wherever they do; for strings, [`ArenaStrInterner`] keeps the bytes in
+/// `A` as well.
+///
+/// This is a lean counterpart of [`Interner`](crate::Interner) covering
+/// interning, lookups, and resolving. Handles are assigned in insertion
+/// order, as in an `Interner`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use allocator_api2::alloc::Global;
+/// use xgx_intern::AllocInterner;
+///
+/// let mut interner = AllocInterner::<u64, RandomState>::with_capacity_in(16, RandomState::new(), Global);
+/// let h = interner.intern_owned(42).unwrap();
+/// assert_eq!(interner.intern_owned(42).unwrap(), h);
+/// assert_eq!(interner.resolve(h), Some(&42));
+/// ```
+pub struct AllocInterner<T, S, H = u32, A = Global>
+where
+    A: Allocator,
+{
+    values: Vec<T, A>,
+    /// Maps each value's hash to its index in `values`.
+    table: HashTable<usize, A>,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<T, S, H, A> fmt::Debug for AllocInterner<T, S, H, A>
+where
+    T: fmt::Debug,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllocInterner")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, S, H, A> AllocInterner<T, S, H, A>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    A: Allocator + Clone,
+{
+    /// Creates a new, empty interner allocating from `alloc`. This does not
+    /// allocate.
+    #[must_use]
+    pub fn new_in(hasher: S, alloc: A) -> Self {
+        Self {
+            values: Vec::new_in(alloc.clone()),
+            table: HashTable::new_in(alloc),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Creates a new, empty interner allocating from `alloc`, with room for
+    /// at least `capacity` items.
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, hasher: S, alloc: A) -> Self {
+        Self {
+            values: Vec::with_capacity_in(capacity, alloc.clone()),
+            table: HashTable::with_capacity_in(capacity, alloc),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Returns the allocator the interner allocates from.
+    #[must_use]
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.values.allocator()
+    }
+
+    /// Interns an owned value, taking ownership.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new item is inserted and the
+    /// handle capacity is exhausted.
+    pub fn intern_owned(&mut self, item: T) -> Result<H, InternerError> {
+        match self.find(&item) {
+            Ok(idx) => Self::idx_to_handle(idx),
+            Err(hash) => self.insert(hash, item),
+        }
+    }
+
+    /// Interns a value by reference, cloning it only if it is not yet present.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new item is inserted and the
+    /// handle capacity is exhausted.
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.find(item) {
+            Ok(idx) => Self::idx_to_handle(idx),
+            Err(hash) => self.insert(hash, T::from_ref(item)),
+        }
+    }
+
+    /// Returns the handle for `item` if it was interned, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented
+    /// by `H`.
+    pub fn lookup_handle<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(item).ok().map(Self::idx_to_handle).transpose()
+    }
+
+    /// Resolves a handle to a reference to its value.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        self.values.get(usize::try_from(handle).ok()?)
+    }
+
+    /// Returns the number of unique items.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the interner contains no items.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over all unique items in insertion order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.values.iter()
+    }
+
+    /// Returns the index of `item`, or its hash if it is absent.
+    fn find<Q>(&self, item: &Q) -> Result<usize, u64>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(item);
+        self.table
+            .find(hash, |&idx| self.values[idx].borrow() == item)
+            .copied()
+            .ok_or(hash)
+    }
+
+    /// Appends a new item with the given hash.
+    fn insert(&mut self, hash: u64, item: T) -> Result<H, InternerError> {
+        let idx = self.values.len();
+        let handle = Self::idx_to_handle(idx)?;
+        let Self {
+            values,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.insert_unique(hash, idx, |&i| hasher.hash_one(&values[i]));
+        values.push(item);
+        Ok(handle)
+    }
+
+    fn idx_to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+/// A string interner that keeps all string bytes, and its index, in a custom
+/// allocator `A`.
+///
+/// Strings are appended back to back to a single byte arena instead of
+/// getting an allocation each, so interning allocates only when the arena,
+/// the offset list, or the hash index grows, and all of that comes from `A`.
+///
+/// Resolving checks that the stored bytes are UTF-8, which takes time
+/// linear in the length of the string, as the crate does not use unsafe
+/// code to skip the check.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use allocator_api2::alloc::Global;
+/// use xgx_intern::ArenaStrInterner;
+///
+/// let mut interner = ArenaStrInterner::<RandomState>::new_in(RandomState::new(), Global);
+/// let a = interner.intern("alpha").unwrap();
+/// let b = interner.intern("beta").unwrap();
+/// assert_eq!(interner.intern("alpha").unwrap(), a);
+/// assert_eq!(interner.resolve(b), Some("beta"));
+/// assert_eq!(interner.arena_len(), 9);
+/// ```
+pub struct ArenaStrInterner<S, H = u32, A = Global>
+where
+    A: Allocator,
+{
+    /// The bytes of all strings, in insertion order.
+    bytes: Vec<u8, A>,
+    /// The end offset of each string in `bytes`; each starts where the
+    /// previous one ends.
+    ends: Vec<usize, A>,
+    /// Maps each string's hash to its index in `ends`.
+    table: HashTable<usize, A>,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<S, H, A> fmt::Debug for ArenaStrInterner<S, H, A>
+where
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaStrInterner")
+            .field("len", &self.ends.len())
+            .field("arena_len", &self.bytes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, H, A> ArenaStrInterner<S, H, A>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    A: Allocator + Clone,
+{
+    /// Creates a new, empty interner allocating from `alloc`. This does not
+    /// allocate.
+    #[must_use]
+    pub fn new_in(hasher: S, alloc: A) -> Self {
+        Self {
+            bytes: Vec::new_in(alloc.clone()),
+            ends: Vec::new_in(alloc.clone()),
+            table: HashTable::new_in(alloc),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Creates a new, empty interner allocating from `alloc`, with room for
+    /// at least `capacity` strings totalling `arena_capacity` bytes.
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, arena_capacity: usize, hasher: S, alloc: A) -> Self {
+        Self {
+            bytes: Vec::with_capacity_in(arena_capacity, alloc.clone()),
+            ends: Vec::with_capacity_in(capacity, alloc.clone()),
+            table: HashTable::with_capacity_in(capacity, alloc),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Returns the allocator the interner allocates from.
+    #[must_use]
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.bytes.allocator()
+    }
+
+    /// Interns a string, copying it into the arena if it is new.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new string is inserted and the
+    /// handle capacity is exhausted.
+    pub fn intern(&mut self, s: &str) -> Result<H, InternerError> {
+        let hash = self.hasher.hash_one(s);
+        if let Some(&idx) = self.table.find(hash, |&i| self.get(i) == s.as_bytes()) {
+            return Self::idx_to_handle(idx);
+        }
+        let idx = self.ends.len();
+        let handle = Self::idx_to_handle(idx)?;
+        self.bytes.extend_from_slice(s.as_bytes());
+        self.ends.push(self.bytes.len());
+        let Self {
+            bytes,
+            ends,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.insert_unique(hash, idx, |&i| {
+            let start = i.checked_sub(1).map_or(0, |prev| ends[prev]);
+            hasher.hash_one(str::from_utf8(&bytes[start..ends[i]]).unwrap_or_default())
+        });
+        Ok(handle)
+    }
+
+    /// Returns the handle for `s` if it was interned, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented
+    /// by `H`.
+    pub fn lookup_handle(&self, s: &str) -> Result<Option<H>, InternerError> {
+        let hash = self.hasher.hash_one(s);
+        self.table
+            .find(hash, |&i| self.get(i) == s.as_bytes())
+            .map(|&idx| Self::idx_to_handle(idx))
+            .transpose()
+    }
+
+    /// Resolves a handle to its string.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<&str> {
+        let idx = usize::try_from(handle).ok()?;
+        if idx >= self.ends.len() {
+            return None;
+        }
+        str::from_utf8(self.get(idx)).ok()
+    }
+
+    /// Returns the number of unique strings.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if the interner contains no strings.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the total length in bytes of all unique strings.
+    #[must_use]
+    #[inline]
+    pub fn arena_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns the bytes of the `idx`-th string.
+    fn get(&self, idx: usize) -> &[u8] {
+        let start = idx.checked_sub(1).map_or(0, |prev| self.ends[prev]);
+        &self.bytes[start..self.ends[idx]]
+    }
+
+    fn idx_to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+    use allocator_api2::alloc::Global;
+    use bumpalo::Bump;
+
+    use super::{AllocInterner, ArenaStrInterner};
+
+    /// Returns `true` if `addr` lies in memory handed out by `bump`.
+    fn in_bump(bump: &mut Bump, addr: usize) -> bool {
+        bump.iter_allocated_chunks().any(|chunk| {
+            let start = chunk.as_ptr() as usize;
+            (start..start + chunk.len()).contains(&addr)
+        })
+    }
+
+    #[test]
+    fn test_alloc_interner_allocates_in_allocator() {
+        let mut bump = Bump::new();
+        let mut interner =
+            AllocInterner::<u64, RandomState, u32, _>::new_in(RandomState::new(), &bump);
+        for i in 0..1000 {
+            assert_eq!(interner.intern_owned(i * 7).unwrap(), i as u32);
+        }
+        assert_eq!(interner.intern_ref(&7).unwrap(), 1);
+        assert_eq!(interner.lookup_handle(&14).unwrap(), Some(2));
+        assert_eq!(interner.lookup_handle(&15).unwrap(), None);
+        assert_eq!(interner.len(), 1000);
+        assert!(interner.iter().copied().eq((0..1000).map(|i| i * 7)));
+        let value = interner.resolve(999).unwrap() as *const u64 as usize;
+        drop(interner);
+
+        // The values and the index grew in the bump, not the global allocator.
+        assert!(in_bump(&mut bump, value));
+        let used: usize = bump.iter_allocated_chunks().map(<[_]>::len).sum();
+        assert!(used >= 1000 * (size_of::<u64>() + size_of::<usize>()));
+
+        let sized = AllocInterner::<u64, RandomState, u32, _>::with_capacity_in(
+            64,
+            RandomState::new(),
+            Global,
+        );
+        assert!(sized.is_empty());
+    }
+
+    #[test]
+    fn test_arena_str_interner_allocates_in_allocator() {
+        let mut bump = Bump::new();
+        let mut interner = ArenaStrInterner::<RandomState, u16, _>::with_capacity_in(
+            4,
+            16,
+            RandomState::new(),
+            &bump,
+        );
+        let words: Vec<String> = (0..500).map(|i| i.to_string()).collect();
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(interner.intern(word).unwrap(), i as u16);
+        }
+        assert_eq!(interner.intern("").unwrap(), 500);
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(interner.intern(word).unwrap(), i as u16);
+            assert_eq!(interner.resolve(i as u16), Some(word.as_str()));
+        }
+        assert_eq!(interner.resolve(500), Some(""));
+        assert_eq!(interner.resolve(501), None);
+        assert_eq!(interner.lookup_handle("absent").unwrap(), None);
+        assert_eq!(interner.len(), 501);
+        assert_eq!(
+            interner.arena_len(),
+            words.iter().map(String::len).sum::<usize>()
+        );
+        let string = interner.resolve(42).unwrap().as_ptr() as usize;
+        drop(interner);
+
+        // The string bytes live in the bump as well.
+        assert!(in_bump(&mut bump, string));
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::boxed::Box;
+use core::{fmt, mem::size_of};
+
+use crate::{DeterministicState, Interner};
+
+/// The estimated bytes an interner spends per distinct string: its stored
+/// `Box<str>` plus an index entry and a control byte in the hash index.
+const ENTRY_OVERHEAD: usize = size_of::<Box<str>>() + size_of::<u32>() + 1;
+
+/// An estimate of what interning a sample of strings would save, from
+/// [`analyze`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DedupReport {
+    /// The number of strings in the sample.
+    pub total_items: usize,
+    /// The number of distinct strings.
+    pub distinct_items: usize,
+    /// The total length of all strings.
+    pub total_bytes: usize,
+    /// The total length of the distinct strings, i.e. what an interner
+    /// stores.
+    pub distinct_bytes: usize,
+    /// The estimated bytes an interner adds with `u16` handles, or `None` if
+    /// there are too many distinct strings for them.
+    pub overhead_u16: Option<usize>,
+    /// The estimated bytes an interner adds with `u32` handles, or `None` if
+    /// there are too many distinct strings for them.
+    pub overhead_u32: Option<usize>,
+}
+
+impl DedupReport {
+    /// Returns the fraction of strings that repeat an earlier one, `0.0` for
+    /// an empty sample.
+    #[must_use]
+    pub fn duplication_ratio(&self) -> f64 {
+        if self.total_items == 0 {
+            0.0
+        } else {
+            (self.total_items - self.distinct_items) as f64 / self.total_items as f64
+        }
+    }
+
+    /// Returns the string bytes interning would not store,
+    /// `total_bytes - distinct_bytes`.
+    #[must_use]
+    #[inline]
+    pub const fn saved_bytes(&self) -> usize {
+        self.total_bytes - self.distinct_bytes
+    }
+
+    /// Returns the estimated overhead with handles of `handle_size` bytes,
+    /// if `distinct_items` fit in them.
+    fn overhead(total_items: usize, distinct_items: usize, handle_size: usize) -> Option<usize> {
+        let max_items = 1usize.checked_shl(u32::try_from(handle_size * 8).ok()?);
+        if max_items.is_some_and(|max| distinct_items > max) {
+            return None;
+        }
+        Some(distinct_items * ENTRY_OVERHEAD + total_items * handle_size)
+    }
+}
+
+impl fmt::Display for DedupReport {
+    /// Writes a summary such as `8 items, 4 distinct (50.0% duplicates);
+    /// 10 bytes, 6 distinct (4 saved); overhead: 100 bytes with u16 handles,
+    /// 116 bytes with u32 handles`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} items, {} distinct ({:.1}% duplicates); {} bytes, {} distinct ({} saved); overhead: ",
+            self.total_items,
+            self.distinct_items,
+            self.duplication_ratio() * 100.0,
+            self.total_bytes,
+            self.distinct_bytes,
+            self.saved_bytes(),
+        )?;
+        for (i, (overhead, handle)) in [(self.overhead_u16, "u16"), (self.overhead_u32, "u32")]
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match overhead {
+                Some(bytes) => write!(f, "{bytes} bytes with {handle} handles")?,
+                None => write!(f, "too many items for {handle} handles")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Estimates the effect of interning a sample of strings.
+///
+/// The sample is streamed through a temporary interner, which only ever
+/// holds the distinct strings; the input itself is not collected. The
+/// overhead estimates count a `Box<str>` slot and a hash index entry per
+/// distinct string plus one handle per item, and leave out spare capacity
+/// from growth.
+///
+/// # Examples
+///
+/// ```
+/// let report = xgx_intern::analyze(["get", "put", "get", "get"]);
+/// assert_eq!(report.total_items, 4);
+/// assert_eq!(report.distinct_items, 2);
+/// assert_eq!(report.saved_bytes(), 6);
+/// println!("{report}");
+/// ```
+pub fn analyze<I>(iter: I) -> DedupReport
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut interner =
+        Interner::<Box<str>, DeterministicState, usize>::new(DeterministicState::default());
+    let (mut total_items, mut total_bytes, mut distinct_bytes) = (0, 0, 0);
+    for item in iter {
+        let item = item.as_ref();
+        let len = interner.len();
+        // `usize` handles cannot overflow before memory runs out.
+        let _ = interner.intern_ref(item);
+        if interner.len() > len {
+            distinct_bytes += item.len();
+        }
+        total_items += 1;
+        total_bytes += item.len();
+    }
+    let distinct_items = interner.len();
+    DedupReport {
+        total_items,
+        distinct_items,
+        total_bytes,
+        distinct_bytes,
+        overhead_u16: DedupReport::overhead(total_items, distinct_items, size_of::<u16>()),
+        overhead_u32: DedupReport::overhead(total_items, distinct_items, size_of::<u32>()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec::Vec};
+
+    use super::{DedupReport, ENTRY_OVERHEAD, analyze};
+
+    #[test]
+    fn test_exact_report() {
+        let report = analyze(["a", "bb", "", "a", "", "bb", "ccc", "a"]);
+        assert_eq!(
+            report,
+            DedupReport {
+                total_items: 8,
+                distinct_items: 4,
+                total_bytes: 10,
+                distinct_bytes: 6,
+                overhead_u16: Some(4 * ENTRY_OVERHEAD + 8 * 2),
+                overhead_u32: Some(4 * ENTRY_OVERHEAD + 8 * 4),
+            }
+        );
+        assert!((report.duplication_ratio() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(report.saved_bytes(), 4);
+        assert!(
+            report.to_string().starts_with(
+                "8 items, 4 distinct (50.0% duplicates); 10 bytes, 6 distinct (4 saved)"
+            )
+        );
+
+        let empty = analyze(Vec::<&str>::new());
+        assert_eq!(empty.total_items, 0);
+        assert_eq!(empty.duplication_ratio(), 0.0);
+        assert_eq!(empty.overhead_u16, Some(0));
+    }
+
+    #[test]
+    fn test_too_many_for_u16() {
+        let report = analyze((0..70_000).map(|i| i.to_string()));
+        assert_eq!(report.distinct_items, 70_000);
+        assert_eq!(report.overhead_u16, None);
+        assert!(report.overhead_u32.is_some());
+        assert!(
+            report
+                .to_string()
+                .contains("too many items for u16 handles")
+        );
+    }
+}
@@ -0,0 +1,274 @@
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::{
+    any::{Any, TypeId, type_name},
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::{Interner, InternerError};
+
+/// A single type-erased interner stored inside an [`AnyInterner`].
+struct Slot {
+    /// The name of the interned type, for diagnostics.
+    type_name: &'static str,
+    /// The boxed `Interner<T, S, H>`.
+    interner: Box<dyn Any>,
+    /// Returns the number of items in `interner` without knowing its concrete type.
+    len: fn(&dyn Any) -> usize,
+}
+
+impl Slot {
+    fn new<T, S, H>(hasher: S) -> Self
+    where
+        T: Eq + Hash + 'static,
+        S: BuildHasher + 'static,
+        H: Copy + TryFrom<usize> + 'static,
+        usize: TryFrom<H>,
+    {
+        Self {
+            type_name: type_name::<T>(),
+            interner: Box::new(Interner::<T, S, H>::new(hasher)),
+            len: erased_len::<T, S, H>,
+        }
+    }
+}
+
+fn erased_len<T, S, H>(interner: &dyn Any) -> usize
+where
+    T: Eq + Hash + 'static,
+    S: BuildHasher + 'static,
+    H: Copy + TryFrom<usize> + 'static,
+    usize: TryFrom<H>,
+{
+    interner
+        .downcast_ref::<Interner<T, S, H>>()
+        .map_or(0, Interner::len)
+}
+
+/// A heterogeneous registry holding one [`Interner`] per interned type.
+///
+/// Instead of threading a separate interner for strings, paths, floats, etc.
+/// through every context struct, an `AnyInterner` keeps them all in a single
+/// map keyed by [`TypeId`]. Interners are created lazily, the first time a
+/// type is used, and each one is constructed from a clone of the registry's
+/// `BuildHasher`.
+///
+/// Every type has its own, independent handle space: the first `String` and
+/// the first `u64` interned both receive handle `0`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::AnyInterner;
+///
+/// let mut registry = AnyInterner::<RandomState>::new(RandomState::new());
+///
+/// let name = registry.intern("hello".to_string()).unwrap();
+/// let id = registry.intern(42u64).unwrap();
+///
+/// assert_eq!(registry.resolve::<String>(name), Some(&"hello".to_string()));
+/// assert_eq!(registry.resolve::<u64>(id), Some(&42));
+///
+/// // No `i8` interner was ever created, so this is a clean `None`.
+/// assert_eq!(registry.resolve::<i8>(0), None);
+/// ```
+pub struct AnyInterner<S, H = u32>
+where
+    S: BuildHasher + Clone + 'static,
+    H: Copy + TryFrom<usize> + 'static,
+    usize: TryFrom<H>,
+{
+    interners: BTreeMap<TypeId, Slot>,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<S, H> Default for AnyInterner<S, H>
+where
+    S: BuildHasher + Clone + Default + 'static,
+    H: Copy + TryFrom<usize> + 'static,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S, H> fmt::Debug for AnyInterner<S, H>
+where
+    S: BuildHasher + Clone + 'static,
+    H: Copy + TryFrom<usize> + 'static,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.registered()).finish()
+    }
+}
+
+impl<S, H> AnyInterner<S, H>
+where
+    S: BuildHasher + Clone + 'static,
+    H: Copy + TryFrom<usize> + 'static,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty registry.
+    ///
+    /// Each interner created by the registry receives a clone of `hasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            interners: BTreeMap::new(),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Returns the interner for `T`, if one has been created.
+    #[must_use]
+    pub fn get<T>(&self) -> Option<&Interner<T, S, H>>
+    where
+        T: Eq + Hash + 'static,
+    {
+        self.interners
+            .get(&TypeId::of::<T>())
+            .and_then(|slot| slot.interner.downcast_ref())
+    }
+
+    /// Returns the interner for `T`, creating an empty one if needed.
+    pub fn get_or_create<T>(&mut self) -> &mut Interner<T, S, H>
+    where
+        T: Eq + Hash + 'static,
+    {
+        let hasher = &self.hasher;
+        self.interners
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Slot::new::<T, S, H>(hasher.clone()))
+            .interner
+            .downcast_mut()
+            .unwrap_or_else(|| {
+                unreachable!(
+                    "a slot keyed by `TypeId::of::<T>()` always holds an `Interner<T, S, H>`"
+                )
+            })
+    }
+
+    /// Interns an owned value in the interner for `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle space of the interner
+    /// for `T` is exhausted.
+    pub fn intern<T>(&mut self, value: T) -> Result<H, InternerError>
+    where
+        T: Eq + Hash + 'static,
+    {
+        self.get_or_create::<T>().intern_owned(value)
+    }
+
+    /// Resolves a handle in the interner for `T`.
+    ///
+    /// Returns `None` if no interner for `T` exists or the handle is invalid.
+    #[must_use]
+    pub fn resolve<T>(&self, handle: H) -> Option<&T>
+    where
+        T: Eq + Hash + 'static,
+    {
+        self.get::<T>()?.resolve(handle)
+    }
+
+    /// Returns `true` if an interner for `T` has been created.
+    #[must_use]
+    #[inline]
+    pub fn contains_type<T: 'static>(&self) -> bool {
+        self.interners.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Iterates over the registered types, yielding each type's name and the
+    /// number of items in its interner.
+    ///
+    /// The iteration order is unspecified. Intended for diagnostics.
+    pub fn registered(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.interners
+            .values()
+            .map(|slot| (slot.type_name, (slot.len)(&*slot.interner)))
+    }
+
+    /// Returns the number of registered types.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interners.len()
+    }
+
+    /// Returns `true` if no interner has been created yet.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interners.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+
+    use super::AnyInterner;
+
+    #[test]
+    fn test_independent_handle_spaces() {
+        let mut registry = AnyInterner::<RandomState>::new(RandomState::new());
+
+        let s0 = registry.intern("a".to_string()).unwrap();
+        let s1 = registry.intern("b".to_string()).unwrap();
+        let n0 = registry.intern(7u64).unwrap();
+        let s2 = registry.intern("a".to_string()).unwrap();
+
+        // Each type starts counting from zero.
+        assert_eq!((s0, s1, n0), (0, 1, 0));
+        assert_eq!(s0, s2);
+
+        assert_eq!(registry.resolve::<String>(s1), Some(&"b".to_string()));
+        assert_eq!(registry.resolve::<u64>(n0), Some(&7));
+        assert_eq!(registry.get::<String>().unwrap().len(), 2);
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_wrong_type_resolves_to_none() {
+        let mut registry = AnyInterner::<RandomState>::default();
+        let h = registry.intern("a".to_string()).unwrap();
+
+        assert!(registry.contains_type::<String>());
+        assert!(!registry.contains_type::<u8>());
+        assert_eq!(registry.resolve::<u8>(h), None);
+        assert!(registry.get::<u8>().is_none());
+    }
+
+    #[test]
+    fn test_registered_types() {
+        let mut registry = AnyInterner::<RandomState, u16>::new(RandomState::new());
+        assert!(registry.is_empty());
+
+        registry.get_or_create::<u32>();
+        registry.intern(1i64).unwrap();
+        registry.intern(2i64).unwrap();
+
+        let mut registered: Vec<_> = registry.registered().collect();
+        registered.sort_unstable();
+        assert_eq!(registered, alloc::vec![("i64", 2), ("u32", 0)]);
+
+        let debug = alloc::format!("{registry:?}");
+        assert!(debug.contains("\"i64\": 2"));
+    }
+}
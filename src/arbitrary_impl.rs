@@ -0,0 +1,70 @@
+use core::hash::{BuildHasher, Hash};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Interner, InternerError};
+
+/// Builds a valid interner by interning an arbitrary sequence of `T`.
+///
+/// Duplicates in the generated sequence are deduplicated as usual, so every
+/// handle in `0..len` resolves. Generation stops early, rather than failing,
+/// once the handle space of `H` is exhausted.
+impl<'a, T, S, H> Arbitrary<'a> for Interner<T, S, H>
+where
+    T: Arbitrary<'a> + Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut interner = Self::default();
+        for item in u.arbitrary_iter::<T>()? {
+            if let Err(InternerError::Overflow) = interner.intern_owned(item?) {
+                break;
+            }
+        }
+        Ok(interner)
+    }
+
+    fn arbitrary_take_rest(mut u: Unstructured<'a>) -> Result<Self> {
+        Self::arbitrary(&mut u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+    use arbitrary::{Arbitrary as _, Unstructured};
+
+    use crate::Interner;
+
+    #[test]
+    fn test_arbitrary_interner_handles_resolve() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&data);
+
+        let interner = Interner::<String, RandomState>::arbitrary(&mut u).unwrap();
+
+        for idx in 0..interner.len() {
+            let handle = u32::try_from(idx).unwrap();
+            let item = interner.resolve(handle).unwrap();
+            assert_eq!(interner.lookup_handle(item.as_str()).unwrap(), Some(handle));
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_interner_stops_at_overflow() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(8192).collect();
+
+        let interner =
+            Interner::<u16, RandomState, u8>::arbitrary_take_rest(Unstructured::new(&data))
+                .unwrap();
+
+        assert!(interner.len() <= 256);
+        for handle in 0..interner.len() {
+            assert!(interner.resolve(u8::try_from(handle).unwrap()).is_some());
+        }
+    }
+}
@@ -0,0 +1,95 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::hash::{BuildHasher, Hash};
+
+use crate::Interner;
+
+/// An [`Interner`] storing its values behind `Arc`s.
+///
+/// [`resolve_arc`](Interner::resolve_arc) hands out a cheap clone of the
+/// `Arc`, which the caller owns. Unlike the `&T` returned by
+/// [`resolve`](Interner::resolve), it does not borrow the interner, so it can
+/// be held across further interning, moved to another thread, or kept across
+/// an `.await`.
+///
+/// Lookups still work by `&T` (or `&str` for `ArcInterner<str, _>`) without
+/// allocating, since `Arc<T>` borrows as `T`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::ArcInterner;
+///
+/// let mut interner = ArcInterner::<str, RandomState>::new(RandomState::new());
+/// let h = interner.intern_ref("hello").unwrap();
+///
+/// let hello = interner.resolve_arc(h).unwrap();
+/// interner.intern_ref("world").unwrap(); // needs `&mut interner`
+///
+/// assert_eq!(&*hello, "hello");
+/// assert_eq!(interner.lookup_handle("hello").unwrap(), Some(h));
+/// ```
+pub type ArcInterner<T, S, H = u32> = Interner<Arc<T>, S, H>;
+
+impl<T, S, H> Interner<Arc<T>, S, H>
+where
+    T: Eq + Hash + ?Sized,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Resolves a handle to a new `Arc` pointing at the interned value.
+    ///
+    /// The returned `Arc` is independent of the interner's borrow and keeps
+    /// the value alive even after the interner is dropped.
+    ///
+    /// Returns `None` if the handle is invalid.
+    #[must_use]
+    #[inline]
+    pub fn resolve_arc(&self, handle: H) -> Option<Arc<T>> {
+        self.resolve(handle).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use ahash::RandomState;
+
+    use super::ArcInterner;
+
+    #[test]
+    fn test_resolved_arc_outlives_interning() {
+        let mut interner = ArcInterner::<str, RandomState>::new(RandomState::new());
+        let h = interner.intern_ref("hello").unwrap();
+
+        let hello = interner.resolve_arc(h).unwrap();
+        assert_eq!(Arc::strong_count(&hello), 2);
+
+        // Interning more items mutably borrows the interner while `hello` is held.
+        for s in ["a", "b", "c", "d"] {
+            interner.intern_ref(s).unwrap();
+        }
+        assert_eq!(&*hello, "hello");
+        assert_eq!(interner.intern_ref("hello").unwrap(), h);
+        assert_eq!(Arc::strong_count(&hello), 2);
+
+        drop(interner);
+        assert_eq!(Arc::strong_count(&hello), 1);
+        assert_eq!(&*hello, "hello");
+    }
+
+    #[test]
+    fn test_sized_values_lookup_by_ref() {
+        let mut interner = ArcInterner::<u64, RandomState>::new(RandomState::new());
+        let h = interner.intern_ref(&42).unwrap();
+        assert_eq!(interner.intern_owned(Arc::new(42)).unwrap(), h);
+        assert_eq!(interner.lookup_handle(&42).unwrap(), Some(h));
+        assert_eq!(interner.resolve_arc(h).as_deref(), Some(&42));
+        assert_eq!(interner.resolve_arc(1), None);
+    }
+}
@@ -0,0 +1,403 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::{FromRef, Interner, InternerError};
+
+/// The error returned when an arena and its offsets, as written by
+/// [`Interner::export_arena`], do not describe a valid arena.
+///
+/// Indices refer to positions in the offsets table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ArenaImportError {
+    /// The offsets table is empty; even an empty arena has the offset `0`.
+    #[error("arena offsets are empty")]
+    MissingOffsets,
+    /// An offset cannot be converted to `usize`.
+    #[error("arena offset at index {index} does not fit in usize")]
+    Unrepresentable {
+        /// The index of the offset.
+        index: usize,
+    },
+    /// The first offset is not `0`.
+    #[error("arena offsets start at {offset} instead of 0")]
+    NonZeroStart {
+        /// The first offset.
+        offset: usize,
+    },
+    /// An offset is smaller than the one before it.
+    #[error("arena offset {offset} at index {index} is below the previous offset {previous}")]
+    Decreasing {
+        /// The index of the offset.
+        index: usize,
+        /// The offset.
+        offset: usize,
+        /// The offset before it.
+        previous: usize,
+    },
+    /// An offset lies past the end of the arena.
+    #[error("arena offset {offset} at index {index} exceeds the arena length {len}")]
+    OutOfBounds {
+        /// The index of the offset.
+        index: usize,
+        /// The offset.
+        offset: usize,
+        /// The length of the arena in bytes.
+        len: usize,
+    },
+    /// An offset splits a UTF-8 character.
+    #[error("arena offset {offset} at index {index} is not on a character boundary")]
+    NotCharBoundary {
+        /// The index of the offset.
+        index: usize,
+        /// The offset.
+        offset: usize,
+    },
+    /// The last offset is not the arena length, leaving trailing bytes that
+    /// belong to no string.
+    #[error("arena offsets end at {end}, but the arena is {len} bytes long")]
+    LengthMismatch {
+        /// The last offset.
+        end: usize,
+        /// The length of the arena in bytes.
+        len: usize,
+    },
+    /// Two handles map to the same string, so the handles of an interner
+    /// cannot be reproduced.
+    #[error("arena strings {first} and {second} are equal")]
+    Duplicate {
+        /// The handle of the first occurrence.
+        first: usize,
+        /// The handle of the repeated string.
+        second: usize,
+    },
+    /// Interning a string failed, e.g. because the handle space is exhausted.
+    #[error(transparent)]
+    Interner(#[from] InternerError),
+}
+
+/// Checks that `offsets` fit in `usize`, start at `0`, never decrease, end at
+/// `arena.len()`, and fall on character boundaries.
+fn validate<O>(arena: &str, offsets: &[O]) -> Result<(), ArenaImportError>
+where
+    O: Copy,
+    usize: TryFrom<O>,
+{
+    if offsets.is_empty() {
+        return Err(ArenaImportError::MissingOffsets);
+    }
+    let mut previous = 0;
+    for (index, &offset) in offsets.iter().enumerate() {
+        let offset =
+            usize::try_from(offset).map_err(|_| ArenaImportError::Unrepresentable { index })?;
+        if index == 0 && offset != 0 {
+            return Err(ArenaImportError::NonZeroStart { offset });
+        }
+        if offset < previous {
+            return Err(ArenaImportError::Decreasing {
+                index,
+                offset,
+                previous,
+            });
+        }
+        if offset > arena.len() {
+            return Err(ArenaImportError::OutOfBounds {
+                index,
+                offset,
+                len: arena.len(),
+            });
+        }
+        if !arena.is_char_boundary(offset) {
+            return Err(ArenaImportError::NotCharBoundary { index, offset });
+        }
+        previous = offset;
+    }
+    if previous != arena.len() {
+        return Err(ArenaImportError::LengthMismatch {
+            end: previous,
+            len: arena.len(),
+        });
+    }
+    Ok(())
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + Borrow<str> + FromRef<str>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Rebuilds an interner from an arena and its offsets, such as those of
+    /// [`export_arena`](Self::export_arena),
+    /// [`export_arena_with_offsets`](Self::export_arena_with_offsets), or
+    /// [`write_arena`](Self::write_arena), validating them strictly.
+    ///
+    /// The string for handle `h` is `arena[offsets[h]..offsets[h + 1]]`, so
+    /// the rebuilt interner reproduces the original handles. The offsets may
+    /// be of any integer type convertible to `usize`; they must start at `0`
+    /// and end at `arena.len()`. Errors report what is wrong and where, and
+    /// corrupt input never panics. [`from_arena`](Self::from_arena) is this
+    /// with the errors collapsed into an `InternerError`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArenaImportError`] describing the first invalid offset,
+    /// a repeated string, or a failure to intern, e.g. when the strings
+    /// exceed the handle space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::{ArenaImportError, Interner};
+    ///
+    /// let interner =
+    ///     Interner::<String, RandomState>::import_arena(RandomState::new(), "foobar", &[0, 3, 6])
+    ///         .unwrap();
+    /// assert_eq!(interner.lookup_handle("bar").unwrap(), Some(1));
+    ///
+    /// let err = Interner::<String, RandomState>::import_arena(RandomState::new(), "foobar", &[0, 3])
+    ///     .unwrap_err();
+    /// assert_eq!(err, ArenaImportError::LengthMismatch { end: 3, len: 6 });
+    /// ```
+    pub fn import_arena<O>(hasher: S, arena: &str, offsets: &[O]) -> Result<Self, ArenaImportError>
+    where
+        O: Copy,
+        usize: TryFrom<O>,
+    {
+        let view = ArenaView::<usize, O>::new(arena, offsets)?;
+        let mut interner = Self::with_capacity(hasher, view.len());
+        for (second, item) in view.iter().enumerate() {
+            let len = interner.len();
+            let h = interner.intern_ref(item)?;
+            if interner.len() == len {
+                let first = usize::try_from(h).map_err(|_| InternerError::Overflow)?;
+                return Err(ArenaImportError::Duplicate { first, second });
+            }
+        }
+        Ok(interner)
+    }
+}
+
+/// A read-only resolver over a borrowed arena and its offsets, without
+/// copying any string.
+///
+/// This is the zero-copy sibling of
+/// [`Interner::import_arena`]: the offsets are validated the same way, but
+/// instead of building an interner, handles resolve straight into `arena`.
+/// There is no hash index, so it cannot look up strings, and repeated
+/// strings are allowed.
+///
+/// The offsets may be of any integer type `O` convertible to `usize`, such
+/// as the handle-typed offsets of [`Interner::export_arena`].
+///
+/// # Examples
+///
+/// ```
+/// use xgx_intern::ArenaView;
+///
+/// let view = ArenaView::<u32>::new("foobar", &[0, 3, 6]).unwrap();
+/// assert_eq!(view.resolve(1), Some("bar"));
+/// assert_eq!(view.resolve(2), None);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ArenaView<'a, H = u32, O = usize> {
+    arena: &'a str,
+    offsets: &'a [O],
+    _handle: PhantomData<H>,
+}
+
+impl<'a, H, O> ArenaView<'a, H, O>
+where
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    O: Copy,
+    usize: TryFrom<O>,
+{
+    /// Validates `offsets` against `arena` and wraps both.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArenaImportError`] describing the first invalid offset.
+    pub fn new(arena: &'a str, offsets: &'a [O]) -> Result<Self, ArenaImportError> {
+        validate(arena, offsets)?;
+        Ok(Self {
+            arena,
+            offsets,
+            _handle: PhantomData,
+        })
+    }
+
+    /// Resolves a handle to its string in the arena.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<&'a str> {
+        let idx = usize::try_from(handle).ok()?;
+        let end = Self::offset(*self.offsets.get(idx.checked_add(1)?)?);
+        self.arena.get(Self::offset(self.offsets[idx])..end)
+    }
+
+    /// Returns the number of strings.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns `true` if the arena holds no strings.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the strings in handle order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'a str> + use<'a, H, O> {
+        let arena = self.arena;
+        self.offsets.windows(2).map(move |w| {
+            arena
+                .get(Self::offset(w[0])..Self::offset(w[1]))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the arena.
+    #[must_use]
+    #[inline]
+    pub const fn arena(&self) -> &'a str {
+        self.arena
+    }
+
+    /// Internal helper converting an offset, which `new` has validated.
+    #[inline]
+    fn offset(offset: O) -> usize {
+        usize::try_from(offset).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        boxed::Box,
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+
+    use super::{ArenaImportError, ArenaView};
+    use crate::{Interner, InternerError};
+
+    fn import(
+        arena: &str,
+        offsets: &[usize],
+    ) -> Result<Interner<String, RandomState>, ArenaImportError> {
+        Interner::import_arena(RandomState::new(), arena, offsets)
+    }
+
+    #[test]
+    fn test_round_trip_export() {
+        let mut interner = Interner::<Box<str>, RandomState>::new(RandomState::new());
+        let words = ["alpha", "", "βeta", "gamma", "🦀"];
+        for w in words {
+            interner.intern_ref(w).unwrap();
+        }
+        let (arena, offsets) = interner.export_arena().unwrap();
+
+        let imported =
+            Interner::<String, RandomState>::import_arena(RandomState::new(), &arena, &offsets)
+                .unwrap();
+        let view = ArenaView::<u32, u32>::new(&arena, &offsets).unwrap();
+        assert_eq!(imported.len(), words.len());
+        assert_eq!(view.len(), words.len());
+        for (h, w) in (0u32..).zip(words) {
+            assert_eq!(imported.resolve(h).map(String::as_str), Some(w));
+            assert_eq!(imported.lookup_handle(w).unwrap(), Some(h));
+            assert_eq!(view.resolve(h), Some(w));
+        }
+        assert!(view.iter().eq(words));
+        assert_eq!(view.resolve(5), None);
+
+        assert!(import("", &[0]).unwrap().is_empty());
+        assert!(ArenaView::<u32>::new("", &[0]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validation_failures() {
+        assert_eq!(
+            import("ab", &[]).unwrap_err(),
+            ArenaImportError::MissingOffsets
+        );
+        assert_eq!(
+            ArenaView::<u32, i32>::new("ab", &[0, -1]).unwrap_err(),
+            ArenaImportError::Unrepresentable { index: 1 }
+        );
+        assert_eq!(
+            import("ab", &[1, 2]).unwrap_err(),
+            ArenaImportError::NonZeroStart { offset: 1 }
+        );
+        assert_eq!(
+            import("abc", &[0, 2, 1, 3]).unwrap_err(),
+            ArenaImportError::Decreasing {
+                index: 2,
+                offset: 1,
+                previous: 2
+            }
+        );
+        assert_eq!(
+            import("ab", &[0, 3]).unwrap_err(),
+            ArenaImportError::OutOfBounds {
+                index: 1,
+                offset: 3,
+                len: 2
+            }
+        );
+        // 'é' occupies bytes 0..2, so offset 1 splits it.
+        assert_eq!(
+            import("é", &[0, 1, 2]).unwrap_err(),
+            ArenaImportError::NotCharBoundary {
+                index: 1,
+                offset: 1
+            }
+        );
+        assert_eq!(
+            import("abc", &[0, 2]).unwrap_err(),
+            ArenaImportError::LengthMismatch { end: 2, len: 3 }
+        );
+        assert_eq!(
+            import("xyxy", &[0, 1, 2, 3, 4]).unwrap_err(),
+            ArenaImportError::Duplicate {
+                first: 0,
+                second: 2
+            }
+        );
+
+        // 300 distinct strings overflow `u8` handles.
+        let mut arena = String::new();
+        let mut offsets = Vec::from([0]);
+        for i in 0..300 {
+            arena.push_str(&i.to_string());
+            offsets.push(arena.len());
+        }
+        assert_eq!(
+            Interner::<String, RandomState, u8>::import_arena(RandomState::new(), &arena, &offsets)
+                .unwrap_err(),
+            ArenaImportError::Interner(InternerError::Overflow)
+        );
+
+        // The view validates the same way, but allows repeats.
+        assert_eq!(
+            ArenaView::<u32>::new("abc", &[0, 2]).unwrap_err(),
+            ArenaImportError::LengthMismatch { end: 2, len: 3 }
+        );
+        assert_eq!(
+            ArenaView::<u32>::new("xyxy", &[0, 1, 2, 3, 4])
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+}
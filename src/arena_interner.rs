@@ -0,0 +1,254 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, hash::BuildHasher, marker::PhantomData};
+
+use hashbrown::HashTable;
+
+use crate::{InternerError, ResolvedArena};
+
+/// A string interner that stores all strings back to back in a single
+/// `String` arena.
+///
+/// An [`Interner<String, _>`](crate::Interner) spends a `String` (24 bytes)
+/// and a heap allocation on every distinct string.
+/// An `ArenaInterner` spends one `usize` offset per string instead; the
+/// bytes are appended to the arena, which grows like a `String`. It is the
+/// in-memory layout of [`export_arena`](crate::Interner::export_arena),
+/// kept live and interning.
+///
+/// Strings can never be removed, and resolving borrows from the arena, so
+/// handles stay valid for the life of the interner.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::ArenaInterner;
+///
+/// let mut interner = ArenaInterner::<RandomState>::new(RandomState::new());
+/// let a = interner.intern_ref("alpha").unwrap();
+/// let b = interner.intern_ref("beta").unwrap();
+/// assert_eq!(interner.intern_ref("alpha").unwrap(), a);
+///
+/// assert_eq!(interner.resolve(b), Some("beta"));
+/// assert_eq!(interner.arena(), "alphabeta");
+/// ```
+#[derive(Clone)]
+pub struct ArenaInterner<S, H = u32> {
+    arena: String,
+    /// The end offset of each string in `arena`; each starts where the
+    /// previous one ends.
+    ends: Vec<usize>,
+    /// Maps each string's hash to its index in `ends`.
+    table: HashTable<usize>,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<S, H> fmt::Debug for ArenaInterner<S, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaInterner")
+            .field("len", &self.ends.len())
+            .field("arena_len", &self.arena.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, H> ArenaInterner<S, H>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner. This does not allocate.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            arena: String::new(),
+            ends: Vec::new(),
+            table: HashTable::new(),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Creates a new, empty interner with room for at least `capacity`
+    /// strings totalling `arena_capacity` bytes.
+    #[must_use]
+    pub fn with_capacity(hasher: S, capacity: usize, arena_capacity: usize) -> Self {
+        Self {
+            arena: String::with_capacity(arena_capacity),
+            ends: Vec::with_capacity(capacity),
+            table: HashTable::with_capacity(capacity),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Interns a string, appending it to the arena if it is new.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new string is inserted and the
+    /// handle capacity is exhausted.
+    pub fn intern_ref(&mut self, s: &str) -> Result<H, InternerError> {
+        let hash = self.hasher.hash_one(s);
+        if let Some(&idx) = self.table.find(hash, |&i| self.get(i) == s) {
+            return Self::idx_to_handle(idx);
+        }
+        let idx = self.ends.len();
+        let handle = Self::idx_to_handle(idx)?;
+        self.arena.push_str(s);
+        self.ends.push(self.arena.len());
+        let Self {
+            arena,
+            ends,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.insert_unique(hash, idx, |&i| hasher.hash_one(slice(arena, ends, i)));
+        Ok(handle)
+    }
+
+    /// Returns the handle for `s` if it was interned, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented
+    /// by `H`.
+    pub fn lookup_handle(&self, s: &str) -> Result<Option<H>, InternerError> {
+        let hash = self.hasher.hash_one(s);
+        self.table
+            .find(hash, |&i| self.get(i) == s)
+            .map(|&idx| Self::idx_to_handle(idx))
+            .transpose()
+    }
+
+    /// Resolves a handle to its string in the arena.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<&str> {
+        let idx = usize::try_from(handle).ok()?;
+        (idx < self.ends.len()).then(|| self.get(idx))
+    }
+
+    /// Returns the number of unique strings.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if the interner contains no strings.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Iterates over all unique strings in insertion order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> {
+        (0..self.ends.len()).map(|i| self.get(i))
+    }
+
+    /// Returns the arena: every unique string, concatenated in insertion
+    /// order.
+    #[must_use]
+    #[inline]
+    pub fn arena(&self) -> &str {
+        &self.arena
+    }
+
+    /// Converts the interner into a [`ResolvedArena`], dropping the hash
+    /// index and keeping the arena as is.
+    #[must_use]
+    pub fn into_resolved(self) -> ResolvedArena<H> {
+        let mut offsets = Vec::with_capacity(self.ends.len() + 1);
+        offsets.push(0);
+        offsets.extend_from_slice(&self.ends);
+        ResolvedArena::<H>::from_parts(self.arena, offsets)
+    }
+
+    /// Returns the `idx`-th string, which must exist.
+    fn get(&self, idx: usize) -> &str {
+        slice(&self.arena, &self.ends, idx)
+    }
+
+    fn idx_to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+/// Returns the `idx`-th string of an arena with the given end offsets.
+fn slice<'a>(arena: &'a str, ends: &[usize], idx: usize) -> &'a str {
+    let start = idx.checked_sub(1).map_or(0, |prev| ends[prev]);
+    &arena[start..ends[idx]]
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+
+    use super::ArenaInterner;
+    use crate::{Interner, InternerError};
+
+    #[test]
+    fn test_interns_into_arena() {
+        let mut arena = ArenaInterner::<RandomState>::new(RandomState::new());
+        let mut reference = Interner::<String, RandomState>::new(RandomState::new());
+        let words: Vec<String> = (0..2_000).map(|i| (i % 700).to_string()).collect();
+        for w in words.iter().map(String::as_str).chain(["", "βeta", ""]) {
+            assert_eq!(
+                arena.intern_ref(w).unwrap(),
+                reference.intern_ref(w).unwrap()
+            );
+        }
+
+        assert_eq!(arena.len(), 702);
+        for h in 0..702 {
+            assert_eq!(arena.resolve(h), reference.resolve(h).map(String::as_str));
+            let s = arena.resolve(h).unwrap();
+            assert_eq!(arena.lookup_handle(s).unwrap(), Some(h));
+        }
+        assert_eq!(arena.resolve(702), None);
+        assert_eq!(arena.lookup_handle("absent").unwrap(), None);
+        assert!(arena.iter().eq(reference.iter().map(String::as_str)));
+
+        // The arena holds each distinct string exactly once.
+        let expected: String = reference.iter().map(String::as_str).collect();
+        assert_eq!(arena.arena(), expected);
+
+        let resolved = arena.into_resolved();
+        let (text, offsets) = reference.clone().export_arena().unwrap();
+        assert_eq!(resolved.as_parts().0, text);
+        assert!(
+            resolved
+                .as_parts()
+                .1
+                .iter()
+                .copied()
+                .eq(offsets.iter().map(|&o| o as usize))
+        );
+    }
+
+    #[test]
+    fn test_handle_overflow() {
+        let mut arena =
+            ArenaInterner::<RandomState, u8>::with_capacity(RandomState::new(), 256, 1024);
+        for i in 0..256 {
+            arena.intern_ref(&i.to_string()).unwrap();
+        }
+        assert_eq!(arena.intern_ref("0").unwrap(), 0);
+        assert!(matches!(
+            arena.intern_ref("256"),
+            Err(InternerError::Overflow)
+        ));
+        assert_eq!(arena.len(), 256);
+        assert_eq!(arena.lookup_handle("256").unwrap(), None);
+    }
+}
@@ -0,0 +1,234 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use tokio::sync::RwLock;
+
+use crate::{FromRef, Interner, InternerError};
+
+/// An interner for async code, guarded by a `tokio::sync::RwLock`.
+///
+/// Waiting for the lock yields to the executor instead of blocking a worker
+/// thread. All methods take `&self` and never hold the lock across an
+/// `.await` point of the caller; resolving returns an owned clone for the
+/// same reason.
+///
+/// Cloning is cheap and shares the same underlying interner, so it can be
+/// stored directly in application state.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::AsyncInterner;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let interner = AsyncInterner::<String, RandomState>::new(RandomState::new());
+/// let state = interner.clone();
+///
+/// let h = state.intern_ref("hello").await.unwrap();
+/// assert_eq!(interner.resolve_owned(h).await.as_deref(), Some("hello"));
+/// assert_eq!(interner.get("hello").await.unwrap(), Some(h));
+/// # });
+/// ```
+pub struct AsyncInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    inner: Arc<RwLock<Interner<T, S, H>>>,
+}
+
+impl<T, S, H> Clone for AsyncInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T, S, H> Default for AsyncInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for AsyncInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("AsyncInterner");
+        // Never wait for the lock while formatting.
+        if let Ok(interner) = self.inner.try_read() {
+            debug.field("len", &interner.len());
+        }
+        debug.finish_non_exhaustive()
+    }
+}
+
+impl<T, S, H> From<Interner<T, S, H>> for AsyncInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn from(interner: Interner<T, S, H>) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(interner)),
+        }
+    }
+}
+
+impl<T, S, H> AsyncInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner with the given `BuildHasher`.
+    #[must_use]
+    pub fn new(hasher: S) -> Self {
+        Self::from(Interner::new(hasher))
+    }
+
+    /// Interns an owned value.
+    ///
+    /// Takes the read lock to look the value up, and the write lock only if it
+    /// must be inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub async fn intern(&self, item: T) -> Result<H, InternerError> {
+        if let Some(handle) = self.inner.read().await.lookup_handle(&item)? {
+            return Ok(handle);
+        }
+        self.inner.write().await.intern_owned(item)
+    }
+
+    /// Interns a borrowed value, cloning it only if it must be inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub async fn intern_ref<Q>(&self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(handle) = self.inner.read().await.lookup_handle(item)? {
+            return Ok(handle);
+        }
+        self.inner.write().await.intern_ref(item)
+    }
+
+    /// Returns the handle for `item` if present, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub async fn get<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.read().await.lookup_handle(item)
+    }
+
+    /// Resolves `handle` and returns a clone of the value.
+    ///
+    /// Returns `None` if the handle is invalid.
+    pub async fn resolve_owned(&self, handle: H) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.inner.read().await.resolve(handle).cloned()
+    }
+
+    /// Returns the number of unique items.
+    pub async fn len(&self) -> usize {
+        self.inner.read().await.len()
+    }
+
+    /// Returns `true` if the interner contains no items.
+    pub async fn is_empty(&self) -> bool {
+        self.inner.read().await.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        collections::BTreeSet,
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+
+    use super::AsyncInterner;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_tasks_with_overlap() {
+        let interner = AsyncInterner::<String, RandomState>::new(RandomState::new());
+
+        // Task `t` interns `t * 10 .. t * 10 + 50`, so neighbouring tasks overlap.
+        let tasks: Vec<_> = (0..32u32)
+            .map(|t| {
+                let interner = interner.clone();
+                tokio::spawn(async move {
+                    let mut handles = Vec::new();
+                    for v in t * 10..t * 10 + 50 {
+                        let s = v.to_string();
+                        handles.push((s.clone(), interner.intern(s).await.unwrap()));
+                        tokio::task::yield_now().await;
+                    }
+                    handles
+                })
+            })
+            .collect();
+
+        let mut seen = BTreeSet::new();
+        for task in tasks {
+            for (s, h) in task.await.unwrap() {
+                assert_eq!(interner.resolve_owned(h).await, Some(s.clone()));
+                assert_eq!(interner.get(s.as_str()).await.unwrap(), Some(h));
+                seen.insert(s);
+            }
+        }
+
+        // Distinct inputs are 0 .. 31 * 10 + 50.
+        assert_eq!(seen.len(), 360);
+        assert_eq!(interner.len().await, 360);
+        assert!(!interner.is_empty().await);
+    }
+}
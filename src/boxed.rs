@@ -0,0 +1,137 @@
+extern crate alloc;
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::Interner;
+
+impl<S, H> Interner<String, S, H>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Converts every `String` into a `Box<str>`, dropping unused capacity and
+    /// the capacity field of each value.
+    ///
+    /// Insertion order, and therefore every handle, is preserved. Since a
+    /// `Box<str>` hashes like the `String` it came from, the index is kept
+    /// as-is and nothing is rehashed. The hasher, item limit, and growth
+    /// policy carry over.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let h = interner.intern_ref("hello").unwrap();
+    ///
+    /// let boxed = interner.into_boxed();
+    /// assert_eq!(boxed.resolve(h).map(|s| &**s), Some("hello"));
+    /// assert_eq!(boxed.lookup_handle("hello").unwrap(), Some(h));
+    /// ```
+    #[must_use]
+    pub fn into_boxed(self) -> Interner<Box<str>, S, H> {
+        Interner {
+            items: self.items.map_values(String::into_boxed_str),
+            max_items: self.max_items,
+            growth: self.growth,
+            checked: self.checked,
+            #[cfg(feature = "stats")]
+            stats: self.stats.map_items(|item| item.len()),
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            overflow_location: self.overflow_location,
+            overflow_handle: self.overflow_handle,
+            _handle: PhantomData,
+        }
+    }
+}
+
+impl<T, S, H> Interner<Vec<T>, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Converts every `Vec<T>` into a `Box<[T]>`, dropping unused capacity
+    /// and the capacity field of each value.
+    ///
+    /// Like the `String` version, this preserves every handle and does not
+    /// rehash, since a `Box<[T]>` hashes like the `Vec<T>` it came from.
+    #[must_use]
+    pub fn into_boxed(self) -> Interner<Box<[T]>, S, H> {
+        Interner {
+            items: self.items.map_values(Vec::into_boxed_slice),
+            max_items: self.max_items,
+            growth: self.growth,
+            checked: self.checked,
+            #[cfg(feature = "stats")]
+            stats: self.stats.map_items(|item| item.len()),
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            overflow_location: self.overflow_location,
+            overflow_handle: self.overflow_handle,
+            _handle: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        boxed::Box,
+        string::{String, ToString as _},
+        vec,
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+
+    use crate::Interner;
+
+    #[test]
+    fn test_strings_keep_handles() {
+        let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+        let words: Vec<String> = (0..1000).map(|i| i.to_string()).collect();
+        let handles: Vec<u32> = words
+            .iter()
+            .map(|w| {
+                let mut s = String::with_capacity(64);
+                s.push_str(w);
+                interner.intern_owned(s).unwrap()
+            })
+            .collect();
+
+        let mut boxed = interner.into_boxed();
+        assert_eq!(boxed.len(), 1000);
+        for (word, &h) in words.iter().zip(&handles) {
+            assert_eq!(boxed.resolve(h).map(|s| &**s), Some(word.as_str()));
+            assert_eq!(boxed.lookup_handle(word.as_str()).unwrap(), Some(h));
+        }
+
+        // The converted interner keeps interning.
+        assert_eq!(boxed.intern_ref("42").unwrap(), handles[42]);
+        assert_eq!(boxed.intern_ref("new").unwrap(), 1000);
+        assert_eq!(boxed.lookup_handle("new").unwrap(), Some(1000));
+    }
+
+    #[test]
+    fn test_vecs_keep_handles() {
+        let mut interner = Interner::<Vec<u8>, RandomState>::new(RandomState::new());
+        let a = interner.intern_owned(vec![1, 2, 3]).unwrap();
+        let b = interner.intern_owned(Vec::new()).unwrap();
+
+        let mut boxed = interner.into_boxed();
+        assert_eq!(boxed.resolve(a).map(|v| &**v), Some(&[1, 2, 3][..]));
+        assert_eq!(boxed.lookup_handle(&[][..]).unwrap(), Some(b));
+        assert_eq!(boxed.intern_owned(Box::from(&[1, 2, 3][..])).unwrap(), a);
+    }
+}
@@ -0,0 +1,163 @@
+use core::{
+    cell::Cell,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::Interner;
+
+/// A resolver that remembers the last resolved handle and its value.
+///
+/// Created with [`Interner::caching_resolver`]. Resolving the same handle
+/// several times in a row returns the cached reference without touching the
+/// interner's storage, which helps when the same handle is resolved in bursts.
+///
+/// # Invalidation
+///
+/// The resolver holds a shared borrow of the interner, so the interner cannot
+/// be mutated while the resolver is alive. To intern new items, drop the
+/// resolver first; a new resolver starts with an empty cache. This is what
+/// keeps the cached reference valid: items are never moved or removed while it
+/// exists, and the cache never outlives the borrow.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::Interner;
+///
+/// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+/// let h = interner.intern_ref("hello").unwrap();
+///
+/// let resolver = interner.caching_resolver();
+/// assert_eq!(resolver.resolve(h).unwrap(), "hello");
+/// assert_eq!(resolver.cached_handle(), Some(h));
+/// assert_eq!(resolver.resolve(h).unwrap(), "hello"); // served from the cache
+/// ```
+pub struct CachingResolver<'a, T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    interner: &'a Interner<T, S, H>,
+    last: Cell<Option<(H, &'a T)>>,
+}
+
+impl<T, S, H> fmt::Debug for CachingResolver<'_, T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize> + fmt::Debug,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachingResolver")
+            .field("cached_handle", &self.last.get().map(|(handle, _)| handle))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Returns a resolver caching the last resolved handle.
+    ///
+    /// See [`CachingResolver`] for the invalidation rules.
+    #[must_use]
+    #[inline]
+    pub const fn caching_resolver(&self) -> CachingResolver<'_, T, S, H> {
+        CachingResolver {
+            interner: self,
+            last: Cell::new(None),
+        }
+    }
+}
+
+impl<'a, T, S, H> CachingResolver<'a, T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize> + PartialEq,
+    usize: TryFrom<H>,
+{
+    /// Resolves a handle, returning the cached value if `handle` is the
+    /// handle resolved last.
+    ///
+    /// Returns `None` if the handle is invalid, in which case the cache is
+    /// left unchanged.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&'a T> {
+        if let Some((cached, value)) = self.last.get()
+            && cached == handle
+        {
+            return Some(value);
+        }
+        let value = self.interner.resolve(handle)?;
+        self.last.set(Some((handle, value)));
+        Some(value)
+    }
+
+    /// Returns the handle currently held in the cache, if any.
+    #[must_use]
+    #[inline]
+    pub fn cached_handle(&self) -> Option<H> {
+        self.last.get().map(|(handle, _)| handle)
+    }
+
+    /// Returns the interner this resolver reads from.
+    #[must_use]
+    #[inline]
+    pub const fn interner(&self) -> &'a Interner<T, S, H> {
+        self.interner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use core::ptr;
+
+    use ahash::RandomState;
+
+    use crate::Interner;
+
+    #[test]
+    fn test_cache_hit_miss_and_invalidation() {
+        let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+        let a = interner.intern_ref("a").unwrap();
+        let b = interner.intern_ref("b").unwrap();
+
+        {
+            let resolver = interner.caching_resolver();
+            assert_eq!(resolver.cached_handle(), None);
+
+            // Miss, then hit returning the very same reference.
+            let first = resolver.resolve(a).unwrap();
+            assert_eq!(resolver.cached_handle(), Some(a));
+            assert!(ptr::eq(resolver.resolve(a).unwrap(), first));
+
+            // A different handle replaces the cached entry.
+            assert_eq!(resolver.resolve(b).map(String::as_str), Some("b"));
+            assert_eq!(resolver.cached_handle(), Some(b));
+
+            // Invalid handles miss without evicting.
+            assert_eq!(resolver.resolve(7), None);
+            assert_eq!(resolver.cached_handle(), Some(b));
+        }
+
+        // Interning requires the resolver to be gone; a new one starts empty.
+        let c = interner.intern_ref("c").unwrap();
+        let resolver = interner.caching_resolver();
+        assert_eq!(resolver.cached_handle(), None);
+        assert_eq!(resolver.resolve(c).map(String::as_str), Some("c"));
+        assert_eq!(resolver.interner().len(), 3);
+    }
+}
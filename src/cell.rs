@@ -0,0 +1,240 @@
+use core::{
+    borrow::Borrow,
+    cell::RefCell,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{FromRef, Interner, InternerError};
+
+/// A single-threaded interner that can be mutated through a shared reference.
+///
+/// This wraps an [`Interner`] in a `RefCell`, for code that only holds `&self`
+/// (e.g. a visitor) but still needs to intern.
+///
+/// # Resolving Clones
+///
+/// Each method borrows the `RefCell` only for the duration of the call, so a
+/// reference to a stored value cannot outlive it. [`resolve`](Self::resolve)
+/// therefore returns a clone and requires `T: Clone`; for cheap clones, intern
+/// `Arc<str>` or [`ArenaString`](crate::ArenaString), or use
+/// [`resolve_with`](Self::resolve_with) to borrow the value inside a closure.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::CellInterner;
+///
+/// struct Visitor<'a> {
+///     names: &'a CellInterner<String, RandomState>,
+/// }
+///
+/// impl Visitor<'_> {
+///     fn visit(&self, name: &str) -> u32 {
+///         self.names.intern_ref(name).unwrap()
+///     }
+/// }
+///
+/// let names = CellInterner::new(RandomState::new());
+/// let visitor = Visitor { names: &names };
+/// let h = visitor.visit("x");
+/// assert_eq!(visitor.visit("x"), h);
+/// assert_eq!(names.resolve(h).as_deref(), Some("x"));
+/// ```
+pub struct CellInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    inner: RefCell<Interner<T, S, H>>,
+}
+
+impl<T, S, H> Default for CellInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for CellInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellInterner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> From<Interner<T, S, H>> for CellInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn from(interner: Interner<T, S, H>) -> Self {
+        Self {
+            inner: RefCell::new(interner),
+        }
+    }
+}
+
+impl<T, S, H> CellInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner with the given `BuildHasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            inner: RefCell::new(Interner::new(hasher)),
+        }
+    }
+
+    /// Consumes the wrapper and returns the inner [`Interner`].
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> Interner<T, S, H> {
+        self.inner.into_inner()
+    }
+
+    /// Interns an owned value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern(&self, item: T) -> Result<H, InternerError> {
+        self.inner.borrow_mut().intern_owned(item)
+    }
+
+    /// Interns a borrowed value, cloning it only if it must be inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern_ref<Q>(&self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.borrow_mut().intern_ref(item)
+    }
+
+    /// Returns the handle for `item` if present, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub fn get<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.borrow().lookup_handle(item)
+    }
+
+    /// Resolves `handle` and returns a clone of the value.
+    ///
+    /// Returns `None` if the handle is invalid.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.inner.borrow().resolve(handle).cloned()
+    }
+
+    /// Resolves `handle` and passes the value to `f` while the interner is
+    /// borrowed.
+    ///
+    /// Returns `None` if the handle is invalid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` interns into the same interner.
+    pub fn resolve_with<R, F>(&self, handle: H, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.inner.borrow().resolve(handle).map(f)
+    }
+
+    /// Returns the number of unique items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    /// Returns `true` if the interner contains no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::CellInterner;
+
+    /// Interns names while holding only a shared reference to the interner.
+    struct Namer<'a> {
+        interner: &'a CellInterner<String, RandomState>,
+    }
+
+    impl Namer<'_> {
+        fn name_all(&self, names: &[&str]) -> Vec<u32> {
+            names
+                .iter()
+                .map(|name| self.interner.intern_ref(*name).unwrap())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_intern_through_shared_references() {
+        let interner = CellInterner::new(RandomState::new());
+        let first = Namer {
+            interner: &interner,
+        };
+        let second = Namer {
+            interner: &interner,
+        };
+
+        let a = first.name_all(&["a", "b", "a"]);
+        let b = second.name_all(&["b", "c"]);
+        assert_eq!(a, [0, 1, 0]);
+        assert_eq!(b, [1, 2]);
+
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.get("c").unwrap(), Some(2));
+        assert_eq!(interner.resolve(1).as_deref(), Some("b"));
+        assert_eq!(interner.resolve_with(2, String::len), Some(1));
+        assert_eq!(interner.resolve(3), None);
+
+        let inner = interner.into_inner();
+        assert_eq!(inner.resolve(0).map(String::as_str), Some("a"));
+    }
+}
@@ -0,0 +1,268 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, hash::BuildHasher, marker::PhantomData};
+
+use crate::{Digest, Interner, InternerError};
+
+/// A stored value: its LZ4-compressed bytes and its uncompressed length.
+#[derive(Clone)]
+struct Block {
+    data: Box<[u8]>,
+    len: usize,
+}
+
+/// An interner that keeps large values LZ4-compressed in memory, for values
+/// that are interned often but resolved rarely.
+///
+/// Values are byte-like: `T: AsRef<[u8]>` to intern and `T: From<Vec<u8>>`
+/// to resolve, such as `Vec<u8>` or `String`-backed wrappers. Each distinct
+/// value is stored once, compressed, next to its BLAKE3 [`Digest`].
+/// Interning and lookups compare digests of the uncompressed input, so they
+/// never decompress stored values; only resolving does.
+///
+/// # ⚠️ Equality by Digest
+///
+/// Like [`ContentAddressedInterner`](crate::ContentAddressedInterner), two
+/// values are considered equal if and only if their digests are. Stored
+/// values are never compared byte by byte.
+///
+/// # Resolving
+///
+/// [`resolve`](Self::resolve) decompresses into a new `T`.
+/// [`resolve_into`](Self::resolve_into) decompresses into a caller-provided
+/// buffer, which can be reused across calls, and
+/// [`resolve_with`](Self::resolve_with) lends the bytes to a closure.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::CompressedInterner;
+///
+/// let mut docs = CompressedInterner::<Vec<u8>, RandomState>::new(RandomState::new());
+/// let doc = br#"{"items": [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]}"#.to_vec();
+///
+/// let h = docs.intern(&doc).unwrap();
+/// assert_eq!(docs.intern_bytes(&doc.clone()).unwrap(), h);
+/// assert_eq!(docs.resolve(h), Some(doc));
+/// ```
+#[derive(Clone)]
+pub struct CompressedInterner<T, S, H = u32>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    digests: Interner<Digest, S, H>,
+    /// The compressed `i`-th value.
+    blocks: Vec<Block>,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, S, H> fmt::Debug for CompressedInterner<T, S, H>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressedInterner")
+            .field("len", &self.len())
+            .field("raw_bytes", &self.raw_bytes())
+            .field("compressed_bytes", &self.compressed_bytes())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, S, H> CompressedInterner<T, S, H>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            digests: Interner::new(hasher),
+            blocks: Vec::new(),
+            _value: PhantomData,
+        }
+    }
+
+    /// Interns a value by reference, compressing it only if it is new.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new value is inserted and the
+    /// interner's handle capacity is exhausted.
+    pub fn intern(&mut self, value: &T) -> Result<H, InternerError>
+    where
+        T: AsRef<[u8]>,
+    {
+        self.intern_bytes(value.as_ref())
+    }
+
+    /// Interns the raw bytes of a value, compressing them only if they are
+    /// new.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`intern`](Self::intern).
+    pub fn intern_bytes(&mut self, bytes: &[u8]) -> Result<H, InternerError> {
+        let len = self.digests.len();
+        let handle = self.digests.intern_owned(Digest::of(bytes))?;
+        if self.digests.len() > len {
+            self.blocks.push(Block {
+                data: lz4_flex::compress(bytes).into_boxed_slice(),
+                len: bytes.len(),
+            });
+        }
+        Ok(handle)
+    }
+
+    /// Returns the handle of a value with the same bytes as `bytes`, if one
+    /// was interned. Nothing is decompressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the index of the value cannot be
+    /// represented by the handle type `H`.
+    pub fn lookup_bytes(&self, bytes: &[u8]) -> Result<Option<H>, InternerError> {
+        self.digests.lookup_handle(&Digest::of(bytes))
+    }
+
+    /// Decompresses a value into a new `T`.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<T>
+    where
+        T: From<Vec<u8>>,
+    {
+        let mut buf = Vec::new();
+        self.resolve_into(handle, &mut buf)?;
+        Some(T::from(buf))
+    }
+
+    /// Decompresses a value into `buf`, replacing its contents, and returns
+    /// the bytes.
+    ///
+    /// `buf` keeps its allocation, so reusing it across calls avoids
+    /// allocating once it has grown to the largest value. Returns `None`,
+    /// leaving `buf` empty, if the handle is invalid.
+    pub fn resolve_into<'b>(&self, handle: H, buf: &'b mut Vec<u8>) -> Option<&'b [u8]> {
+        buf.clear();
+        let block = self.blocks.get(usize::try_from(handle).ok()?)?;
+        buf.resize(block.len, 0);
+        // Blocks are only ever produced by `lz4_flex::compress`.
+        let written = lz4_flex::decompress_into(&block.data, buf).ok()?;
+        buf.truncate(written);
+        Some(buf)
+    }
+
+    /// Decompresses a value into a temporary buffer and passes its bytes to
+    /// `f`.
+    pub fn resolve_with<R>(&self, handle: H, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        let mut buf = Vec::new();
+        self.resolve_into(handle, &mut buf).map(f)
+    }
+
+    /// Returns the digest of a value.
+    #[must_use]
+    #[inline]
+    pub fn digest(&self, handle: H) -> Option<&Digest> {
+        self.digests.resolve(handle)
+    }
+
+    /// Returns the number of distinct values.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    /// Returns `true` if no value has been interned.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    /// Returns the total uncompressed length of the distinct values.
+    #[must_use]
+    pub fn raw_bytes(&self) -> usize {
+        self.blocks.iter().map(|block| block.len).sum()
+    }
+
+    /// Returns the total compressed length of the distinct values, i.e. the
+    /// bytes the interner stores for them.
+    #[must_use]
+    pub fn compressed_bytes(&self) -> usize {
+        self.blocks.iter().map(|block| block.data.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::CompressedInterner;
+    use crate::Digest;
+
+    /// A JSON-like document with plenty of repetition.
+    fn document(id: usize) -> Vec<u8> {
+        let mut doc = format!(r#"{{"id": {id}, "rows": ["#);
+        for row in 0..200 {
+            doc.push_str(&format!(
+                r#"{{"row": {row}, "status": "active", "tags": ["a", "b"]}},"#
+            ));
+        }
+        doc.push_str("]}");
+        doc.into_bytes()
+    }
+
+    #[test]
+    fn test_round_trip_and_dedup() {
+        let mut docs = CompressedInterner::<Vec<u8>, RandomState>::new(RandomState::new());
+        let inputs: Vec<Vec<u8>> = (0..10).map(document).collect();
+        let handles: Vec<u32> = inputs.iter().map(|d| docs.intern(d).unwrap()).collect();
+        assert_eq!(handles, (0..10).collect::<Vec<_>>());
+
+        // Equal inputs share a handle, whichever way they are passed in.
+        for (doc, &h) in inputs.iter().zip(&handles) {
+            assert_eq!(docs.intern_bytes(&doc.clone()).unwrap(), h);
+            assert_eq!(docs.lookup_bytes(doc).unwrap(), Some(h));
+            assert_eq!(docs.digest(h), Some(&Digest::of(doc)));
+        }
+        assert_eq!(docs.len(), 10);
+        assert_eq!(docs.lookup_bytes(b"absent").unwrap(), None);
+
+        let mut buf = Vec::new();
+        for (doc, &h) in inputs.iter().zip(&handles) {
+            assert_eq!(docs.resolve(h).as_ref(), Some(doc));
+            assert_eq!(docs.resolve_into(h, &mut buf), Some(doc.as_slice()));
+            assert_eq!(docs.resolve_with(h, <[u8]>::len), Some(doc.len()));
+        }
+        assert_eq!(docs.resolve_into(10, &mut buf), None);
+        assert!(buf.is_empty());
+
+        let empty = docs.intern_bytes(b"").unwrap();
+        assert_eq!(docs.resolve(empty), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_storage_smaller_than_input() {
+        let mut docs = CompressedInterner::<Vec<u8>, RandomState, u16>::new(RandomState::new());
+        let inputs: Vec<Vec<u8>> = (0..20).map(document).collect();
+        for doc in &inputs {
+            docs.intern(doc).unwrap();
+        }
+        let raw: usize = inputs.iter().map(Vec::len).sum();
+        assert_eq!(docs.raw_bytes(), raw);
+        assert!(docs.compressed_bytes() * 4 < raw, "{docs:?}");
+
+        let text = String::from_utf8(docs.resolve(7).unwrap()).unwrap();
+        assert!(text.starts_with(r#"{"id": 7, "rows": ["#));
+    }
+}
@@ -0,0 +1,365 @@
+extern crate std;
+
+use alloc::boxed::Box;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::sync::OnceLock;
+#[cfg(not(feature = "parking_lot"))]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use hashbrown::HashTable;
+#[cfg(feature = "parking_lot")]
+use parking_lot::{Mutex, MutexGuard};
+
+use crate::{FromRef, InternerError};
+
+/// Number of buckets; bucket `b` holds `2^b` slots, covering every `usize` index.
+const BUCKETS: usize = usize::BITS as usize;
+
+/// A fixed-size block of write-once slots.
+type Bucket<T> = Box<[OnceLock<T>]>;
+
+/// A concurrent interner whose resolve path takes no lock.
+///
+/// Values are stored in append-only storage made of buckets of doubling size
+/// that are allocated once and never moved, each slot being written exactly
+/// once. Resolving a handle is wait-free: it only performs atomic loads, and
+/// returns a plain `&T` borrowed from the interner.
+///
+/// Interning and lookups by value take a mutex around the hash index, so this
+/// type suits workloads where resolving vastly outnumbers interning. For
+/// write-heavy workloads, see [`ShardedInterner`](crate::ShardedInterner).
+///
+/// The mutex is `std::sync::Mutex` by default, or `parking_lot::Mutex` when
+/// the `parking_lot` feature is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use std::{collections::hash_map::RandomState, sync::Arc, thread};
+///
+/// use xgx_intern::ConcurrentInterner;
+///
+/// let interner = Arc::new(ConcurrentInterner::<String, RandomState>::new(RandomState::new()));
+/// let hello = interner.intern_ref("hello").unwrap();
+///
+/// let shared = Arc::clone(&interner);
+/// thread::spawn(move || assert_eq!(shared.resolve(hello).unwrap(), "hello"))
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(interner.intern_ref("hello").unwrap(), hello);
+/// ```
+pub struct ConcurrentInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    buckets: [OnceLock<Bucket<T>>; BUCKETS],
+    index: Mutex<HashTable<usize>>,
+    len: AtomicUsize,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<T, S, H> Default for ConcurrentInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for ConcurrentInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrentInterner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> ConcurrentInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner with the given `BuildHasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            buckets: [const { OnceLock::new() }; BUCKETS],
+            index: Mutex::new(HashTable::new()),
+            len: AtomicUsize::new(0),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Interns an owned value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern(&self, item: T) -> Result<H, InternerError> {
+        let hash = self.hasher.hash_one(&item);
+        let mut index = self.lock();
+        if let Some(idx) = self.find(&index, hash, &item) {
+            return Self::idx_to_handle(idx);
+        }
+        self.insert(&mut index, hash, item)
+    }
+
+    /// Interns a borrowed value, cloning it only if it must be inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern_ref<Q>(&self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(item);
+        let mut index = self.lock();
+        if let Some(idx) = self.find(&index, hash, item) {
+            return Self::idx_to_handle(idx);
+        }
+        self.insert(&mut index, hash, T::from_ref(item))
+    }
+
+    /// Returns the handle for `item` if present, without inserting.
+    ///
+    /// This takes the index mutex; only [`resolve`](Self::resolve) is lock-free.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub fn get<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(item);
+        let index = self.lock();
+        self.find(&index, hash, item)
+            .map(Self::idx_to_handle)
+            .transpose()
+    }
+
+    /// Resolves a handle back to a reference to the interned value, without
+    /// taking any lock.
+    ///
+    /// Returns `None` if the handle is invalid. A handle returned by `intern`
+    /// on any thread always resolves, on every thread.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        self.slot(usize::try_from(handle).ok()?)
+    }
+
+    /// Returns the number of unique items.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the interner contains no items.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the items interned so far, in handle order.
+    ///
+    /// Items interned while iterating may or may not be yielded.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.len()).filter_map(|idx| self.slot(idx))
+    }
+
+    /// Returns the bucket and the offset within it holding index `idx`.
+    ///
+    /// Returns `None` for `usize::MAX`, the only index no bucket covers.
+    #[inline]
+    const fn locate(idx: usize) -> Option<(usize, usize)> {
+        // Bucket `b` holds indices `2^b - 1 .. 2^(b + 1) - 1`.
+        let Some(pos) = idx.checked_add(1) else {
+            return None;
+        };
+        let bucket = (usize::BITS - 1 - pos.leading_zeros()) as usize;
+        Some((bucket, pos - (1 << bucket)))
+    }
+
+    /// Returns the value at `idx`, if it has been written.
+    #[inline]
+    fn slot(&self, idx: usize) -> Option<&T> {
+        let (bucket, offset) = Self::locate(idx)?;
+        self.buckets[bucket].get()?.get(offset)?.get()
+    }
+
+    /// Looks up the index of `item` in the hash index.
+    fn find<Q>(&self, index: &HashTable<usize>, hash: u64, item: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        index
+            .find(hash, |&idx| {
+                self.slot(idx).is_some_and(|value| value.borrow() == item)
+            })
+            .copied()
+    }
+
+    /// Appends a new item and publishes it in the hash index.
+    ///
+    /// Must be called with the index mutex held, which serializes writers.
+    fn insert(&self, index: &mut HashTable<usize>, hash: u64, item: T) -> Result<H, InternerError> {
+        // Only writers change `len`, and they hold the mutex.
+        let idx = self.len.load(Ordering::Relaxed);
+        let handle = Self::idx_to_handle(idx)?;
+        let (bucket, offset) = Self::locate(idx).ok_or(InternerError::Overflow)?;
+        let slots = self.buckets[bucket]
+            .get_or_init(|| (0..1usize << bucket).map(|_| OnceLock::new()).collect());
+        // The slot at `len` has never been written, so this cannot fail.
+        let _ = slots[offset].set(item);
+
+        index.insert_unique(hash, idx, |&i| {
+            self.slot(i).map_or(0, |value| self.hasher.hash_one(value))
+        });
+        self.len.store(idx + 1, Ordering::Release);
+        Ok(handle)
+    }
+
+    /// Acquires the index mutex, ignoring poisoning.
+    ///
+    /// Slots are written before the index is updated, so a panic while the
+    /// lock was held can at worst leave an unreachable value behind.
+    fn lock(&self) -> MutexGuard<'_, HashTable<usize>> {
+        #[cfg(feature = "parking_lot")]
+        {
+            self.index.lock()
+        }
+        #[cfg(not(feature = "parking_lot"))]
+        {
+            self.index.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    /// Internal helper to safely convert a `usize` index to a handle `H`.
+    #[inline]
+    fn idx_to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::{
+        string::{String, ToString as _},
+        sync::Arc,
+        vec::Vec,
+    };
+    use core::sync::atomic::{AtomicU32, Ordering};
+    use std::thread;
+
+    use ahash::RandomState;
+
+    use super::ConcurrentInterner;
+    use crate::InternerError;
+
+    #[test]
+    fn test_intern_and_resolve() {
+        let interner = ConcurrentInterner::<String, RandomState>::new(RandomState::new());
+        assert!(interner.is_empty());
+
+        let handles: Vec<_> = (0..100)
+            .map(|i| interner.intern(i.to_string()).unwrap())
+            .collect();
+        assert_eq!(handles, (0..100).collect::<Vec<u32>>());
+        assert_eq!(interner.intern_ref("42").unwrap(), 42);
+        assert_eq!(interner.len(), 100);
+
+        assert_eq!(interner.resolve(7).map(String::as_str), Some("7"));
+        assert_eq!(interner.resolve(100), None);
+        assert_eq!(interner.resolve(u32::MAX), None);
+        assert_eq!(interner.get("99").unwrap(), Some(99));
+        assert_eq!(interner.get("100").unwrap(), None);
+        assert_eq!(interner.iter().nth(3).map(String::as_str), Some("3"));
+    }
+
+    #[test]
+    fn test_handle_overflow() {
+        let interner = ConcurrentInterner::<u32, RandomState, u8>::new(RandomState::new());
+        for i in 0..256 {
+            interner.intern(i).unwrap();
+        }
+        assert!(matches!(interner.intern(256), Err(InternerError::Overflow)));
+        assert_eq!(interner.intern(255).unwrap(), 255);
+        assert_eq!(interner.len(), 256);
+    }
+
+    #[test]
+    fn test_readers_concurrent_with_writer() {
+        const COUNT: u32 = 20_000;
+
+        let interner = Arc::new(ConcurrentInterner::<u64, RandomState>::new(
+            RandomState::new(),
+        ));
+        // Number of handles issued so far; readers only resolve those.
+        let issued = Arc::new(AtomicU32::new(0));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let interner = Arc::clone(&interner);
+                let issued = Arc::clone(&issued);
+                thread::spawn(move || {
+                    let mut checked = 0u64;
+                    loop {
+                        let upto = issued.load(Ordering::Acquire);
+                        for h in upto.saturating_sub(64)..upto {
+                            assert_eq!(interner.resolve(h), Some(&(u64::from(h) * 3)));
+                            checked += 1;
+                        }
+                        if upto == COUNT {
+                            return checked;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for i in 0..COUNT {
+            let h = interner.intern(u64::from(i) * 3).unwrap();
+            assert_eq!(h, i);
+            issued.store(i + 1, Ordering::Release);
+        }
+
+        for reader in readers {
+            assert!(reader.join().unwrap() > 0);
+        }
+        assert_eq!(interner.len(), COUNT as usize);
+    }
+}
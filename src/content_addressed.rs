@@ -0,0 +1,307 @@
+use alloc::vec::Vec;
+use core::{fmt, hash::BuildHasher};
+
+use crate::{HeapSize, Interner, InternerError, MemoryUsage};
+
+/// A 256-bit BLAKE3 digest identifying a blob's content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Digest([u8; 32]);
+
+impl Digest {
+    /// Computes the digest of `bytes`.
+    #[must_use]
+    #[inline]
+    pub fn of(bytes: &[u8]) -> Self {
+        Self(*blake3::hash(bytes).as_bytes())
+    }
+
+    /// Returns the raw digest bytes.
+    #[must_use]
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for Digest {
+    #[inline]
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<blake3::Hash> for Digest {
+    #[inline]
+    fn from(hash: blake3::Hash) -> Self {
+        Self(*hash.as_bytes())
+    }
+}
+
+impl HeapSize for Digest {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+/// An interner for large blobs that stores only their content digests.
+///
+/// Answering "seen before?" for multi-megabyte payloads does not require
+/// keeping them: a `ContentAddressedInterner` hashes each blob with BLAKE3
+/// and interns the 32-byte [`Digest`]. Memory use is the same for a 1 KB and
+/// a 1 GB blob. Each entry can carry a small value `V` of metadata, such as a
+/// storage location.
+///
+/// # ⚠️ Equality by Digest
+///
+/// Two blobs are considered equal if and only if their digests are. BLAKE3
+/// makes accidental collisions astronomically unlikely (about 2^-128 for any
+/// pair), and no way to construct one is known, but the payload is never
+/// compared, so a collision would silently deduplicate two different blobs.
+///
+/// # Streaming
+///
+/// [`intern_chunks`](Self::intern_chunks) hashes a blob arriving in pieces
+/// without assembling it. For other sources, hash with a `blake3::Hasher`
+/// and pass the result to [`intern_digest`](Self::intern_digest).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::ContentAddressedInterner;
+///
+/// let mut blobs = ContentAddressedInterner::<RandomState>::new(RandomState::new());
+/// let (h, new) = blobs.intern_bytes(&[7; 4096]).unwrap();
+/// assert!(new);
+///
+/// let copy = vec![7; 4096];
+/// assert_eq!(blobs.intern_bytes(&copy).unwrap(), (h, false));
+/// ```
+#[derive(Clone)]
+pub struct ContentAddressedInterner<S, H = u32, V = ()>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    digests: Interner<Digest, S, H>,
+    /// The metadata of the `i`-th digest.
+    metadata: Vec<V>,
+}
+
+impl<S, H, V> fmt::Debug for ContentAddressedInterner<S, H, V>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentAddressedInterner")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, H, V> ContentAddressedInterner<S, H, V>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            digests: Interner::new(hasher),
+            metadata: Vec::new(),
+        }
+    }
+
+    /// Interns a blob by its digest, with default metadata if it is new.
+    ///
+    /// Returns the handle and whether the blob was new. The payload is only
+    /// hashed, never stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new blob is inserted and the
+    /// interner's handle capacity is exhausted.
+    pub fn intern_bytes(&mut self, bytes: &[u8]) -> Result<(H, bool), InternerError>
+    where
+        V: Default,
+    {
+        self.intern_digest_with(Digest::of(bytes), V::default)
+    }
+
+    /// Interns a blob given as a sequence of chunks, hashing them in order
+    /// without concatenating them.
+    ///
+    /// The result is the same as interning the concatenated chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`intern_bytes`](Self::intern_bytes).
+    pub fn intern_chunks<'a, I>(&mut self, chunks: I) -> Result<(H, bool), InternerError>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+        V: Default,
+    {
+        let mut hasher = blake3::Hasher::new();
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+        self.intern_digest_with(hasher.finalize().into(), V::default)
+    }
+
+    /// Interns a precomputed digest, with default metadata if it is new.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`intern_bytes`](Self::intern_bytes).
+    pub fn intern_digest(&mut self, digest: Digest) -> Result<(H, bool), InternerError>
+    where
+        V: Default,
+    {
+        self.intern_digest_with(digest, V::default)
+    }
+
+    /// Interns a precomputed digest, creating its metadata with `make` only
+    /// if it is new.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`intern_bytes`](Self::intern_bytes).
+    pub fn intern_digest_with(
+        &mut self,
+        digest: Digest,
+        make: impl FnOnce() -> V,
+    ) -> Result<(H, bool), InternerError> {
+        let len = self.digests.len();
+        let handle = self.digests.intern_owned(digest)?;
+        let new = self.digests.len() > len;
+        if new {
+            self.metadata.push(make());
+        }
+        Ok((handle, new))
+    }
+
+    /// Returns the handle of the blob with `digest`, if it was interned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the index of the digest cannot
+    /// be represented by the handle type `H`.
+    pub fn lookup_digest(&self, digest: &Digest) -> Result<Option<H>, InternerError> {
+        self.digests.lookup_handle(digest)
+    }
+
+    /// Returns the handle of a blob with the same content as `bytes`, if one
+    /// was interned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`lookup_digest`](Self::lookup_digest).
+    pub fn lookup_bytes(&self, bytes: &[u8]) -> Result<Option<H>, InternerError> {
+        self.lookup_digest(&Digest::of(bytes))
+    }
+
+    /// Resolves a handle to the digest of its blob.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&Digest> {
+        self.digests.resolve(handle)
+    }
+
+    /// Returns the metadata of a blob.
+    #[must_use]
+    pub fn metadata(&self, handle: H) -> Option<&V> {
+        self.metadata.get(usize::try_from(handle).ok()?)
+    }
+
+    /// Returns the metadata of a blob mutably.
+    pub fn metadata_mut(&mut self, handle: H) -> Option<&mut V> {
+        self.metadata.get_mut(usize::try_from(handle).ok()?)
+    }
+
+    /// Returns the number of distinct blobs.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    /// Returns `true` if no blob has been interned.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    /// Estimates the heap memory used by the digests and their metadata.
+    ///
+    /// It only depends on the number of blobs and the metadata, never on
+    /// the size of the blobs.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage
+    where
+        V: HeapSize,
+    {
+        let mut usage = self.digests.memory_usage();
+        usage.table_bytes += self.metadata.capacity() * core::mem::size_of::<V>();
+        usage.value_bytes += self.metadata.iter().map(V::heap_size).sum::<usize>();
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::{ContentAddressedInterner, Digest};
+
+    #[test]
+    fn test_dedups_equal_content() {
+        let mut blobs =
+            ContentAddressedInterner::<RandomState, u32, String>::new(RandomState::new());
+        let first: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let copy = first.clone();
+        let other = vec![1u8; 100_000];
+
+        let (a, new) = blobs
+            .intern_digest_with(Digest::of(&first), || String::from("shard-1"))
+            .unwrap();
+        assert!(new);
+        assert_eq!(blobs.intern_bytes(&copy).unwrap(), (a, false));
+        let (b, new) = blobs.intern_bytes(&other).unwrap();
+        assert!(new);
+        assert_ne!(a, b);
+
+        // Chunked input hashes like the concatenation.
+        let chunks = first.chunks(7777);
+        assert_eq!(blobs.intern_chunks(chunks).unwrap(), (a, false));
+
+        assert_eq!(blobs.lookup_bytes(&copy).unwrap(), Some(a));
+        assert_eq!(blobs.lookup_bytes(b"absent").unwrap(), None);
+        assert_eq!(blobs.resolve(a), Some(&Digest::of(&first)));
+        assert_eq!(blobs.metadata(a).map(String::as_str), Some("shard-1"));
+        blobs.metadata_mut(b).unwrap().push_str("shard-2");
+        assert_eq!(blobs.metadata(b).map(String::as_str), Some("shard-2"));
+        assert_eq!(blobs.len(), 2);
+    }
+
+    #[test]
+    fn test_memory_independent_of_blob_size() {
+        let mut small = ContentAddressedInterner::<RandomState>::new(RandomState::new());
+        let mut large = ContentAddressedInterner::<RandomState>::new(RandomState::new());
+        for i in 0..16u8 {
+            small.intern_bytes(&[i; 16]).unwrap();
+            large.intern_bytes(&vec![i; 1 << 20]).unwrap();
+        }
+        assert_eq!(small.memory_usage(), large.memory_usage());
+        assert_eq!(large.memory_usage().value_bytes, 0);
+    }
+}
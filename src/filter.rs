@@ -0,0 +1,114 @@
+use alloc::{vec, vec::Vec};
+
+/// A Bloom filter over the hashes of the stored values, answering most
+/// lookups of absent values without probing the hash index.
+///
+/// The filter only ever reports false positives: `may_contain` is `true` for
+/// every inserted hash, and usually `false` for others. Removing values does
+/// not clear their bits, so the owner rebuilds the filter after removals and
+/// once more values are stored than it was sized for.
+#[derive(Clone, Debug)]
+pub(crate) struct NegativeFilter {
+    words: Vec<u64>,
+    bits_per_entry: usize,
+    /// How many bits each hash sets.
+    probes: u32,
+    /// How many values the filter was sized for.
+    capacity: usize,
+}
+
+impl NegativeFilter {
+    /// The fewest values a filter is sized for, so tiny interners do not
+    /// rebuild it on every few insertions.
+    const MIN_CAPACITY: usize = 64;
+
+    /// Creates an empty filter with `bits_per_entry` bits for each of
+    /// `capacity` values.
+    pub(crate) fn new(bits_per_entry: usize, capacity: usize) -> Self {
+        let bits_per_entry = bits_per_entry.max(1);
+        let capacity = capacity.max(Self::MIN_CAPACITY);
+        let bits = capacity.saturating_mul(bits_per_entry);
+        // The optimal number of probes is `bits_per_entry * ln 2`; `11 / 16`
+        // is close enough to `ln 2` without floating point.
+        let probes = u32::try_from(bits_per_entry * 11 / 16).map_or(16, |k| k.clamp(1, 16));
+        Self {
+            words: vec![0; bits.div_ceil(64)],
+            bits_per_entry,
+            probes,
+            capacity,
+        }
+    }
+
+    /// Returns the number of bits reserved per value.
+    #[inline]
+    pub(crate) const fn bits_per_entry(&self) -> usize {
+        self.bits_per_entry
+    }
+
+    /// Returns how many values the filter was sized for.
+    #[inline]
+    pub(crate) const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the bytes allocated for the bits.
+    #[inline]
+    pub(crate) fn allocation_size(&self) -> usize {
+        self.words.capacity() * core::mem::size_of::<u64>()
+    }
+
+    /// Records a value's hash.
+    pub(crate) fn insert(&mut self, hash: u64) {
+        let words = &mut self.words;
+        for bit in Self::bits(hash, self.probes, words.len() * 64) {
+            words[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns `false` if no value with `hash` was recorded.
+    pub(crate) fn may_contain(&self, hash: u64) -> bool {
+        Self::bits(hash, self.probes, self.words.len() * 64)
+            .all(|bit| self.words[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Forgets every recorded hash.
+    pub(crate) fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns the bit positions of `hash`, derived from its two halves by
+    /// double hashing.
+    fn bits(hash: u64, probes: u32, len: usize) -> impl Iterator<Item = usize> {
+        let step = hash.rotate_left(32) | 1;
+        // `len` is a positive multiple of 64 within `usize`, so the modulus
+        // fits back into `usize`.
+        let len = len as u64;
+        (0..u64::from(probes))
+            .map(move |i| (hash.wrapping_add(i.wrapping_mul(step)) % len) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NegativeFilter;
+
+    #[test]
+    fn test_no_false_negatives() {
+        let mut filter = NegativeFilter::new(10, 1000);
+        let hashes = (0..1000u64).map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        for hash in hashes.clone() {
+            filter.insert(hash);
+        }
+        assert!(hashes.clone().all(|hash| filter.may_contain(hash)));
+
+        // With 10 bits per entry, roughly 1% of absent hashes pass.
+        let false_positives = (1000..11_000u64)
+            .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .filter(|&hash| filter.may_contain(hash))
+            .count();
+        assert!(false_positives < 500, "{false_positives} false positives");
+
+        filter.clear();
+        assert!(!hashes.into_iter().any(|hash| filter.may_contain(hash)));
+    }
+}
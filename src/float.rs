@@ -1,11 +1,16 @@
 use core::{
+    cmp::Ordering,
     fmt,
     hash::{Hash, Hasher},
+    num::ParseFloatError,
     ops::Deref,
+    str::FromStr,
 };
 
 /// A wrapper around f64 that implements Eq and Hash based on bit patterns.
-#[derive(Clone, Copy, Debug, PartialOrd)]
+///
+/// The default is positive zero, `0.0`.
+#[derive(Clone, Copy, Debug, Default, PartialOrd)]
 pub struct HashableF64(pub f64);
 
 impl PartialEq for HashableF64 {
@@ -16,6 +21,23 @@ impl PartialEq for HashableF64 {
     }
 }
 
+/// Compares with a raw `f64` by bit pattern, consistent with `Eq`.
+///
+/// Unlike native float `==`, `HashableF64(f64::NAN) == f64::NAN` is `true`
+/// (for the same NaN bits), and `HashableF64(0.0) == -0.0` is `false`.
+impl PartialEq<f64> for HashableF64 {
+    fn eq(&self, other: &f64) -> bool {
+        self.0.to_bits() == other.to_bits()
+    }
+}
+
+/// Compares a raw `f64` by bit pattern; see `PartialEq<f64> for HashableF64`.
+impl PartialEq<HashableF64> for f64 {
+    fn eq(&self, other: &HashableF64) -> bool {
+        self.to_bits() == other.0.to_bits()
+    }
+}
+
 // Since we've defined a total equality relation, we can implement Eq.
 impl Eq for HashableF64 {}
 
@@ -44,6 +66,23 @@ impl From<f64> for HashableF64 {
     }
 }
 
+impl FromStr for HashableF64 {
+    type Err = ParseFloatError;
+
+    /// Parses the string with `f64::from_str` and wraps the result.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        f64::from_str(s).map(Self)
+    }
+}
+
+impl TryFrom<&str> for HashableF64 {
+    type Error = ParseFloatError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 impl HashableF64 {
     /// Creates a new `HashableF64` from an f64 value.
     #[must_use]
@@ -73,7 +112,9 @@ impl Deref for HashableF64 {
 }
 
 /// A wrapper around f32 that implements Eq and Hash based on bit patterns.
-#[derive(Clone, Copy, Debug, PartialOrd)]
+///
+/// The default is positive zero, `0.0`.
+#[derive(Clone, Copy, Debug, Default, PartialOrd)]
 pub struct HashableF32(pub f32);
 
 impl PartialEq for HashableF32 {
@@ -112,6 +153,23 @@ impl From<f32> for HashableF32 {
     }
 }
 
+impl FromStr for HashableF32 {
+    type Err = ParseFloatError;
+
+    /// Parses the string with `f32::from_str` and wraps the result.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        f32::from_str(s).map(Self)
+    }
+}
+
+impl TryFrom<&str> for HashableF32 {
+    type Error = ParseFloatError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 impl HashableF32 {
     /// Creates a new `HashableF32` from an f32 value.
     #[must_use]
@@ -140,12 +198,273 @@ impl Deref for HashableF32 {
     }
 }
 
+/// A wrapper around f64 that implements Eq and Hash based on bit patterns,
+/// and Ord based on the IEEE 754 total order.
+///
+/// Unlike [`HashableF64`], whose `PartialOrd` follows the usual float
+/// comparison, this type can be a `BTreeSet` or `BTreeMap` key as well as a
+/// hash key. The order is [`f64::total_cmp`], under which two values compare
+/// as equal if and only if their bit patterns are identical, which keeps it
+/// consistent with `Eq`. Negative NaNs sort first and positive NaNs last, and
+/// `-0.0` sorts before `0.0`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HashableOrderedF64(pub f64);
+
+impl PartialEq for HashableOrderedF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for HashableOrderedF64 {}
+
+impl Hash for HashableOrderedF64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl PartialOrd for HashableOrderedF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HashableOrderedF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `total_cmp` orders the bit patterns, so it is `Equal` exactly when
+        // `eq` holds.
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for HashableOrderedF64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<HashableOrderedF64> for f64 {
+    fn from(value: HashableOrderedF64) -> Self {
+        value.0
+    }
+}
+
+impl From<f64> for HashableOrderedF64 {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<HashableF64> for HashableOrderedF64 {
+    fn from(value: HashableF64) -> Self {
+        Self(value.0)
+    }
+}
+
+impl From<HashableOrderedF64> for HashableF64 {
+    fn from(value: HashableOrderedF64) -> Self {
+        Self(value.0)
+    }
+}
+
+impl FromStr for HashableOrderedF64 {
+    type Err = ParseFloatError;
+
+    /// Parses the string with `f64::from_str` and wraps the result.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        f64::from_str(s).map(Self)
+    }
+}
+
+impl HashableOrderedF64 {
+    /// Creates a new `HashableOrderedF64` from an f64 value.
+    #[must_use]
+    #[inline]
+    pub const fn new(value: f64) -> Self {
+        Self(value)
+    }
+    /// Consumes the `HashableOrderedF64` and returns the inner f64 value.
+    #[must_use]
+    #[inline]
+    pub const fn into_inner(self) -> f64 {
+        self.0
+    }
+    /// Returns a reference to the inner f64 value.
+    #[must_use]
+    #[inline]
+    pub const fn as_inner(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl Deref for HashableOrderedF64 {
+    type Target = f64;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A wrapper around f32 that implements Eq and Hash based on bit patterns,
+/// and Ord based on the IEEE 754 total order.
+///
+/// Unlike [`HashableF32`], whose `PartialOrd` follows the usual float
+/// comparison, this type can be a `BTreeSet` or `BTreeMap` key as well as a
+/// hash key. The order is [`f32::total_cmp`], under which two values compare
+/// as equal if and only if their bit patterns are identical, which keeps it
+/// consistent with `Eq`. Negative NaNs sort first and positive NaNs last, and
+/// `-0.0` sorts before `0.0`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HashableOrderedF32(pub f32);
+
+impl PartialEq for HashableOrderedF32 {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for HashableOrderedF32 {}
+
+impl Hash for HashableOrderedF32 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl PartialOrd for HashableOrderedF32 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HashableOrderedF32 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `total_cmp` orders the bit patterns, so it is `Equal` exactly when
+        // `eq` holds.
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for HashableOrderedF32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<HashableOrderedF32> for f32 {
+    fn from(value: HashableOrderedF32) -> Self {
+        value.0
+    }
+}
+
+impl From<f32> for HashableOrderedF32 {
+    fn from(value: f32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<HashableF32> for HashableOrderedF32 {
+    fn from(value: HashableF32) -> Self {
+        Self(value.0)
+    }
+}
+
+impl From<HashableOrderedF32> for HashableF32 {
+    fn from(value: HashableOrderedF32) -> Self {
+        Self(value.0)
+    }
+}
+
+impl FromStr for HashableOrderedF32 {
+    type Err = ParseFloatError;
+
+    /// Parses the string with `f32::from_str` and wraps the result.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        f32::from_str(s).map(Self)
+    }
+}
+
+impl HashableOrderedF32 {
+    /// Creates a new `HashableOrderedF32` from an f32 value.
+    #[must_use]
+    #[inline]
+    pub const fn new(value: f32) -> Self {
+        Self(value)
+    }
+    /// Consumes the `HashableOrderedF32` and returns the inner f32 value.
+    #[must_use]
+    #[inline]
+    pub const fn into_inner(self) -> f32 {
+        self.0
+    }
+    /// Returns a reference to the inner f32 value.
+    #[must_use]
+    #[inline]
+    pub const fn as_inner(&self) -> &f32 {
+        &self.0
+    }
+}
+
+impl Deref for HashableOrderedF32 {
+    type Target = f32;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use alloc::format;
+    use alloc::{collections::BTreeSet, format, vec::Vec};
     use core::hash::{Hash as _, Hasher as _};
 
-    use super::{HashableF32, HashableF64};
+    use super::{HashableF32, HashableF64, HashableOrderedF32, HashableOrderedF64};
+
+    #[test]
+    fn hashable_f64_eq_raw_f64() {
+        assert_eq!(HashableF64(1.5), 1.5);
+        assert_eq!(1.5, HashableF64(1.5));
+        assert_ne!(HashableF64(1.5), 2.5);
+
+        // NaN equals itself by bits, unlike native `==`.
+        let nan = f64::NAN;
+        assert!(HashableF64(nan) == nan);
+        assert!(nan == HashableF64(nan));
+        assert!(HashableF64(nan) != -nan);
+
+        // Zeros of different sign have different bits.
+        assert!(HashableF64(0.0) != -0.0);
+        assert!(-0.0 != HashableF64(0.0));
+        assert!(HashableF64(-0.0) == -0.0);
+    }
+
+    #[test]
+    fn hashable_floats_default_to_positive_zero() {
+        assert_eq!(HashableF64::default().0, 0.0);
+        assert_eq!(HashableF64::default().0.to_bits(), 0);
+        assert_eq!(HashableF32::default().0.to_bits(), 0);
+        assert_eq!(HashableOrderedF64::default().0.to_bits(), 0);
+        assert_eq!(HashableOrderedF32::default().0.to_bits(), 0);
+
+        #[derive(Default, PartialEq, Eq, Hash)]
+        struct Point {
+            x: HashableF64,
+            y: HashableF64,
+        }
+        assert!(
+            Point::default()
+                == Point {
+                    x: HashableF64(0.0),
+                    y: HashableF64(0.0)
+                }
+        );
+        assert!(
+            Point::default()
+                != Point {
+                    x: HashableF64(-0.0),
+                    y: HashableF64(0.0)
+                }
+        );
+    }
 
     #[test]
     fn hashable_f32_nan_equality_and_hash() {
@@ -284,6 +603,25 @@ mod tests {
         assert!(big64 >= small64);
     }
 
+    #[test]
+    fn test_parse_from_str() {
+        assert_eq!("1.5".parse::<HashableF64>().unwrap(), HashableF64::new(1.5));
+        assert!("nan".parse::<HashableF64>().unwrap().is_nan());
+        assert_eq!(
+            HashableF64::try_from("-0.0").unwrap(),
+            HashableF64::new(-0.0)
+        );
+        assert_ne!(
+            "-0.0".parse::<HashableF64>().unwrap(),
+            HashableF64::new(0.0)
+        );
+        assert!("1.5x".parse::<HashableF64>().is_err());
+
+        assert_eq!("1.5".parse::<HashableF32>().unwrap(), HashableF32::new(1.5));
+        assert!(HashableF32::try_from("nan").unwrap().is_nan());
+        assert!("not a float".parse::<HashableF32>().is_err());
+    }
+
     // Covers: #[derive(Clone)] explicitly
     #[allow(clippy::clone_on_copy)]
     #[test]
@@ -296,4 +634,82 @@ mod tests {
         let d = c.clone();
         assert_eq!(c, d);
     }
+
+    fn hash_of<T: core::hash::Hash>(value: &T) -> u64 {
+        let mut hasher = ahash::AHasher::default();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_ordered_eq_consistent_with_ord() {
+        let values: Vec<HashableOrderedF64> = [
+            f64::NAN,
+            -f64::NAN,
+            f64::from_bits(f64::NAN.to_bits() | 1),
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            0.0,
+            -0.0,
+            1.0,
+            -1.0,
+            f64::MIN_POSITIVE,
+            f64::from_bits(1),
+            f64::MAX,
+        ]
+        .into_iter()
+        .map(HashableOrderedF64::new)
+        .collect();
+
+        for a in &values {
+            for b in &values {
+                assert_eq!(a == b, a.cmp(b).is_eq(), "{a:?} vs {b:?}");
+                assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+                assert_eq!(a.cmp(b), b.cmp(a).reverse());
+                if a == b {
+                    assert_eq!(hash_of(a), hash_of(b));
+                }
+            }
+        }
+
+        let f32s = [f32::NAN, -f32::NAN, 0.0, -0.0, 2.5].map(HashableOrderedF32::new);
+        for a in &f32s {
+            for b in &f32s {
+                assert_eq!(a == b, a.cmp(b).is_eq(), "{a:?} vs {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordered_in_btree_set() {
+        let set: BTreeSet<HashableOrderedF64> = [2.0, f64::NAN, -0.0, 0.0, -f64::NAN, 2.0, -1.5]
+            .into_iter()
+            .map(HashableOrderedF64::from)
+            .collect();
+        assert_eq!(set.len(), 6);
+
+        let sorted: Vec<f64> = set.iter().map(|v| v.into_inner()).collect();
+        assert!(sorted[0].is_nan() && sorted[0].is_sign_negative());
+        assert_eq!(
+            sorted[1..5].iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+            [
+                (-1.5f64).to_bits(),
+                (-0.0f64).to_bits(),
+                0.0f64.to_bits(),
+                2.0f64.to_bits(),
+            ]
+        );
+        assert!(sorted[5].is_nan() && sorted[5].is_sign_positive());
+        assert!(set.contains(&HashableOrderedF64::new(f64::NAN)));
+
+        let hashable: HashableF64 = HashableOrderedF64::new(1.0).into();
+        assert_eq!(
+            HashableOrderedF64::from(hashable),
+            HashableOrderedF64::new(1.0)
+        );
+        assert_eq!(
+            "-0.0".parse::<HashableOrderedF32>().unwrap(),
+            HashableOrderedF32::new(-0.0)
+        );
+    }
 }
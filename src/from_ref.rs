@@ -15,6 +15,54 @@ use core::ffi::CStr;
 ///
 /// Similar to [`ToOwned`] or [`Clone`], but it can be implemented on any
 /// combination of base type and `Borrowed` type.
+///
+/// Every `Clone` type implements `FromRef<Self>` through a blanket impl, so
+/// primitives such as `u32` or `bool` need no dedicated impls, and generic
+/// code bounded on `T: FromRef<B>` works for them out of the box:
+///
+/// ```
+/// use xgx_intern::FromRef;
+///
+/// fn to_owned_via<B: ?Sized, T: FromRef<B>>(val: &B) -> T {
+///     T::from_ref(val)
+/// }
+///
+/// let n: u32 = to_owned_via(&7u32);
+/// let flag: bool = to_owned_via(&true);
+/// let s: String = to_owned_via("hi");
+/// let bytes: Box<[u8]> = to_owned_via(&[1u8, 2][..]);
+///
+/// assert_eq!((n, flag, s.as_str(), &*bytes), (7, true, "hi", &[1, 2][..]));
+/// ```
+///
+/// Your own types can implement `FromRef` for any borrowed form, e.g. a
+/// newtype around `Box<str>` implementing `FromRef<str>`. This does not
+/// conflict with the blanket impl, since `str` is not the newtype itself:
+///
+/// ```
+/// use std::{borrow::Borrow, collections::hash_map::RandomState};
+///
+/// use xgx_intern::{FromRef, Interner};
+///
+/// #[derive(PartialEq, Eq, Hash, Clone)]
+/// struct Symbol(Box<str>);
+///
+/// impl Borrow<str> for Symbol {
+///     fn borrow(&self) -> &str {
+///         &self.0
+///     }
+/// }
+///
+/// impl FromRef<str> for Symbol {
+///     fn from_ref(val: &str) -> Self {
+///         Self(val.into())
+///     }
+/// }
+///
+/// let mut interner = Interner::<Symbol, RandomState>::new(RandomState::new());
+/// let h = interner.intern_ref("main").unwrap();
+/// assert_eq!(interner.lookup_handle("main").unwrap(), Some(h));
+/// ```
 pub trait FromRef<Borrowed: ?Sized> {
     /// Construct an owned type from a reference.
     fn from_ref(val: &Borrowed) -> Self;
@@ -70,6 +118,11 @@ impl<T: Clone> FromRef<T> for T {
         val.clone()
     }
 }
+impl<T: Clone> FromRef<T> for Arc<T> {
+    fn from_ref(val: &T) -> Self {
+        Self::new(val.clone())
+    }
+}
 
 // [T]
 impl<T: Clone> FromRef<[T]> for Box<[T]> {
@@ -191,6 +244,30 @@ mod tests {
         assert_eq!(&**box_path_interner.resolve(h_bp).unwrap(), p);
     }
 
+    #[test]
+    fn test_from_ref_in_generic_code() {
+        fn intern_any<B, T>(interner: &mut Interner<T, RandomState>, val: &B) -> u32
+        where
+            B: core::hash::Hash + Eq + ?Sized,
+            T: core::borrow::Borrow<B> + FromRef<B> + Eq + core::hash::Hash,
+        {
+            interner.intern_ref(val).unwrap()
+        }
+
+        let mut numbers = Interner::<u32, RandomState>::new(RandomState::new());
+        assert_eq!(intern_any(&mut numbers, &5u32), 0);
+        assert_eq!(intern_any(&mut numbers, &5u32), 0);
+
+        let mut strings = Interner::<String, RandomState>::new(RandomState::new());
+        assert_eq!(intern_any(&mut strings, "a"), 0);
+        assert_eq!(intern_any(&mut strings, &String::from("a")), 0);
+
+        let mut bytes = Interner::<Box<[u8]>, RandomState>::new(RandomState::new());
+        assert_eq!(intern_any(&mut bytes, &[1u8, 2][..]), 0);
+        assert_eq!(intern_any(&mut bytes, &[3u8][..]), 1);
+        assert_eq!(bytes.resolve(1).map(|b| &**b), Some(&[3u8][..]));
+    }
+
     #[test]
     fn test_from_ref_identity() {
         // Test the `impl<T: Clone> FromRef<T> for T` block
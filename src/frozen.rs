@@ -0,0 +1,280 @@
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::{Interner, InternerError, iter::Iter, store::Store};
+
+/// An immutable, read-only form of an [`Interner`].
+///
+/// Created with [`Interner::freeze`] once the load phase is over. Freezing
+/// shrinks the storage to fit, and the resulting type only exposes resolving
+/// and lookup methods taking `&self`. This makes it a natural fit for sharing
+/// behind an `Arc`: it is `Send + Sync` whenever `T` and `S` are.
+///
+/// Use [`thaw`](Self::thaw) to turn it back into a mutable `Interner`.
+///
+/// # Examples
+///
+/// ```
+/// use std::{collections::hash_map::RandomState, sync::Arc, thread};
+///
+/// use xgx_intern::Interner;
+///
+/// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+/// let hello = interner.intern_ref("hello").unwrap();
+///
+/// let frozen = Arc::new(interner.freeze());
+/// let shared = Arc::clone(&frozen);
+/// thread::spawn(move || assert_eq!(shared.resolve(hello).unwrap(), "hello"))
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(frozen.get("hello"), Some(hello));
+/// ```
+#[derive(Clone)]
+pub struct FrozenInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    pub(crate) items: Store<T, S>,
+    _handle: PhantomData<H>,
+}
+
+impl<T, S, H> fmt::Debug for FrozenInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrozenInterner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Consumes the interner and returns an immutable [`FrozenInterner`].
+    ///
+    /// The storage is shrunk to fit, so no growth slack is retained. All
+    /// handles remain valid.
+    #[must_use]
+    pub fn freeze(mut self) -> FrozenInterner<T, S, H> {
+        self.items.shrink_to_fit();
+        FrozenInterner {
+            items: self.items,
+            _handle: PhantomData,
+        }
+    }
+}
+
+impl<T, S, H> FrozenInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Converts the frozen interner back into a mutable [`Interner`].
+    ///
+    /// All handles remain valid. The returned interner has no item limit and
+    /// uses the default growth policy.
+    #[must_use]
+    pub fn thaw(self) -> Interner<T, S, H> {
+        Interner::from_store(self.items)
+    }
+
+    /// Resolves a handle back to a reference to the interned value.
+    ///
+    /// Returns `None` if the handle is invalid (e.g., out of bounds).
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        let idx = usize::try_from(handle).ok()?;
+        self.items.get_index(idx)
+    }
+
+    /// Returns the handle for `item` if present.
+    #[must_use]
+    #[inline]
+    pub fn get<Q>(&self, item: &Q) -> Option<H>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // Every index was a valid handle when the item was interned.
+        self.items
+            .get_index_of(item)
+            .and_then(|idx| H::try_from(idx).ok())
+    }
+
+    /// Returns true if an equal item is present.
+    #[must_use]
+    #[inline]
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.items.contains(item)
+    }
+
+    /// Returns the number of unique items.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the interner contains no items.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterates over all unique items in handle order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.items.iter()
+    }
+}
+
+impl<T, S, H> FrozenInterner<T, S, H>
+where
+    T: Eq + Hash + AsRef<str>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Flattens all strings into a single contiguous arena, without consuming
+    /// the frozen interner.
+    ///
+    /// The layout matches [`Interner::export_arena`]: the string for handle `h`
+    /// is `arena[offsets[h]..offsets[h + 1]]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the total byte length of the arena
+    /// exceeds the maximum value representable by the handle type `H`.
+    pub fn export_arena(&self) -> Result<(String, Vec<H>), InternerError> {
+        let total_bytes: usize = self.items.iter().map(|s| s.as_ref().len()).sum();
+        let mut arena = String::with_capacity(total_bytes);
+        let mut offsets = Vec::with_capacity(self.items.len() + 1);
+
+        offsets.push(H::try_from(0usize).map_err(|_| InternerError::Overflow)?);
+        for item in &self.items {
+            arena.push_str(item.as_ref());
+            offsets.push(H::try_from(arena.len()).map_err(|_| InternerError::Overflow)?);
+        }
+
+        Ok((arena, offsets))
+    }
+}
+
+impl<'a, T, S, H> IntoIterator for &'a FrozenInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use ahash::RandomState;
+
+    use super::FrozenInterner;
+    use crate::Interner;
+
+    fn create_interner() -> Interner<String, RandomState> {
+        let mut interner = Interner::new(RandomState::new());
+        for s in ["a", "b", "c"] {
+            interner.intern_ref(s).unwrap();
+        }
+        interner
+    }
+
+    #[test]
+    fn test_frozen_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<FrozenInterner<String, RandomState>>();
+    }
+
+    #[test]
+    fn test_freeze_agrees_with_original() {
+        let interner = create_interner();
+        let original = interner.clone();
+        let frozen = interner.freeze();
+
+        assert_eq!(frozen.len(), original.len());
+        for item in &original {
+            let expected = original.lookup_handle(item.as_str()).unwrap();
+            assert_eq!(frozen.get(item.as_str()), expected);
+        }
+        assert_eq!(frozen.get("z"), None);
+        assert!(frozen.contains("b"));
+        assert_eq!(frozen.iter().count(), 3);
+
+        let (arena, offsets) = frozen.export_arena().unwrap();
+        assert_eq!(arena, "abc");
+        assert_eq!(offsets, alloc::vec![0, 1, 2, 3]);
+
+        let mut thawed = frozen.thaw();
+        assert_eq!(thawed.intern_ref("c").unwrap(), 2);
+        assert_eq!(thawed.intern_ref("d").unwrap(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_resolve_from_multiple_threads() {
+        extern crate std;
+        use alloc::{string::ToString as _, sync::Arc, vec::Vec};
+        use std::thread;
+
+        let frozen = Arc::new(create_interner().freeze());
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let frozen = Arc::clone(&frozen);
+                thread::spawn(move || {
+                    for (h, expected) in ["a", "b", "c"].into_iter().enumerate() {
+                        let h = u32::try_from(h).unwrap();
+                        assert_eq!(frozen.resolve(h), Some(&expected.to_string()));
+                        assert_eq!(frozen.get(expected), Some(h));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+}
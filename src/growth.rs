@@ -0,0 +1,123 @@
+use core::hash::{BuildHasher, Hash};
+
+use crate::{InternerError, store::Store};
+
+/// Controls how an [`Interner`](crate::Interner) grows its storage once it is
+/// full.
+///
+/// # Tradeoff
+///
+/// [`Doubling`](Self::Doubling) keeps the amortized cost of an insert
+/// constant, but right after a resize up to half of the storage is unused: an
+/// interner holding 100M items may have room for 128M or more.
+///
+/// [`Linear`](Self::Linear) grows by a fixed number of items, so the slack is
+/// bounded by the step. In exchange, every resize copies all items, so a step
+/// that is small relative to the final size makes interning slow. Choose a
+/// step that is a sizeable fraction of the expected size, e.g. 10M for a
+/// 100M-item interner.
+///
+/// Only the storage of the values themselves grows linearly. The hash index
+/// next to it always grows in powers of two, but holds a single `usize` per
+/// item.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GrowthPolicy {
+    /// Grows geometrically, as a `Vec` does on its own. This is the default.
+    #[default]
+    Doubling,
+    /// Grows capacity in multiples of the given number of items.
+    ///
+    /// A step of `0` is treated as `1`.
+    Linear(usize),
+}
+
+impl GrowthPolicy {
+    /// Ensures `items` has room for `additional` more items, rounding the
+    /// reservation up according to the policy.
+    pub(crate) fn reserve<T, S>(self, items: &mut Store<T, S>, additional: usize)
+    where
+        T: Eq + Hash,
+        S: BuildHasher,
+    {
+        match self {
+            Self::Doubling => items.reserve(additional),
+            Self::Linear(step) => {
+                if let Some(reservation) = Self::linear_reservation(items, additional, step) {
+                    items.reserve_exact(reservation);
+                }
+            }
+        }
+    }
+
+    /// Like [`reserve`](Self::reserve), but reports allocation failures
+    /// instead of aborting.
+    pub(crate) fn try_reserve<T, S>(
+        self,
+        items: &mut Store<T, S>,
+        additional: usize,
+    ) -> Result<(), InternerError>
+    where
+        T: Eq + Hash,
+        S: BuildHasher,
+    {
+        match self {
+            Self::Doubling => items.try_reserve(additional),
+            Self::Linear(step) => Self::linear_reservation(items, additional, step)
+                .map_or(Ok(()), |reservation| items.try_reserve_exact(reservation)),
+        }
+    }
+
+    /// Returns how much a linear policy with `step` reserves to make room for
+    /// `additional` more items, or `None` if there is room already.
+    fn linear_reservation<T, S>(
+        items: &Store<T, S>,
+        additional: usize,
+        step: usize,
+    ) -> Option<usize> {
+        let free = items.capacity().saturating_sub(items.len());
+        if additional <= free {
+            return None;
+        }
+        let step = step.max(1);
+        let grow = (additional - free).div_ceil(step).saturating_mul(step);
+        Some(free.saturating_add(grow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::RandomState;
+
+    use super::GrowthPolicy;
+    use crate::Interner;
+
+    #[test]
+    fn test_linear_growth_by_increment() {
+        let mut interner = Interner::<u32, RandomState>::new_with_growth(
+            RandomState::new(),
+            GrowthPolicy::Linear(100),
+        );
+        assert_eq!(interner.growth_policy(), GrowthPolicy::Linear(100));
+
+        interner.intern_owned(0).unwrap();
+        assert_eq!(interner.capacity(), 100);
+
+        for i in 1..100 {
+            interner.intern_owned(i).unwrap();
+        }
+        assert_eq!(interner.capacity(), 100);
+
+        interner.intern_owned(100).unwrap();
+        assert_eq!(interner.capacity(), 200);
+
+        // Explicit reservations are rounded up to the increment as well.
+        interner.reserve(150);
+        assert_eq!(interner.capacity(), 300);
+    }
+
+    #[test]
+    fn test_doubling_is_default() {
+        let interner = Interner::<u32, RandomState>::new(RandomState::new());
+        assert_eq!(interner.growth_policy(), GrowthPolicy::Doubling);
+    }
+}
@@ -0,0 +1,110 @@
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::hash::RandomState;
+
+#[cfg(feature = "std")]
+use crate::Interner;
+
+/// A 16-bit handle, for interners with at most 65 536 items.
+pub type Handle16 = u16;
+
+/// A 32-bit handle, the default handle type of [`Interner`](crate::Interner).
+pub type Handle32 = u32;
+
+/// A 64-bit handle.
+///
+/// Only needed for more than 2^32 unique items, or when handles are stored
+/// alongside other 64-bit values.
+pub type Handle64 = u64;
+
+/// An interner for `T` using the standard library's `RandomState` and
+/// [`Handle32`] handles.
+#[cfg(feature = "std")]
+pub type DefaultInterner<T> = Interner<T, RandomState, Handle32>;
+
+/// An interner for `String`s with [`Handle32`] handles.
+///
+/// The hasher defaults to the standard library's `RandomState`.
+#[cfg(feature = "std")]
+pub type StringInterner<S = RandomState> = Interner<String, S, Handle32>;
+
+/// An interner for byte strings with [`Handle32`] handles.
+///
+/// The hasher defaults to the standard library's `RandomState`.
+#[cfg(feature = "std")]
+pub type BytesInterner<S = RandomState> = Interner<Vec<u8>, S, Handle32>;
+
+/// An interner hashing with a randomly seeded SipHash, for untrusted input.
+///
+/// The standard library's `RandomState` picks a fresh secret key per
+/// interner, so an attacker cannot craft inputs that all collide and degrade
+/// every lookup to a linear scan (hash flooding). Hashers like `ahash` or
+/// `FxHasher` are several times faster on short keys, and `FxHasher` is
+/// unseeded and trivially floodable; prefer them only for trusted input.
+///
+/// Handles are assigned in insertion order and do not depend on the seed,
+/// so interners with different seeds hand out the same handles for the same
+/// sequence of values.
+#[cfg(feature = "std")]
+pub type SecureInterner<T, H = Handle32> = Interner<T, RandomState, H>;
+
+#[cfg(feature = "std")]
+impl<T, H> Interner<T, RandomState, H>
+where
+    T: Eq + Hash,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner with a randomly seeded SipHash; see
+    /// [`SecureInterner`].
+    #[must_use]
+    pub fn new_secure() -> Self {
+        Self::new(RandomState::new())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use super::{
+        BytesInterner, DefaultInterner, Handle16, Handle32, SecureInterner, StringInterner,
+    };
+    use crate::Interner;
+
+    #[test]
+    fn test_aliases_construct() {
+        let mut strings: StringInterner = Interner::default();
+        let h: Handle32 = strings.intern_ref("hello").unwrap();
+        assert_eq!(strings.resolve(h).map(String::as_str), Some("hello"));
+
+        let mut fast = StringInterner::<ahash::RandomState>::default();
+        assert_eq!(fast.intern_ref("hello").unwrap(), 0);
+
+        let mut bytes: BytesInterner = Interner::default();
+        let b = bytes.intern_ref(&b"raw"[..]).unwrap();
+        assert_eq!(bytes.resolve(b).map(Vec::as_slice), Some(&b"raw"[..]));
+
+        let mut numbers = DefaultInterner::<u64>::default();
+        assert_eq!(numbers.intern_owned(7).unwrap(), 0);
+
+        let mut small = Interner::<u8, ahash::RandomState, Handle16>::default();
+        let s: Handle16 = small.intern_owned(1).unwrap();
+        assert_eq!(small.resolve(s), Some(&1));
+    }
+
+    #[test]
+    fn test_secure_handles_do_not_depend_on_seed() {
+        let mut a = SecureInterner::<String>::new_secure();
+        let mut b = SecureInterner::<String>::new_secure();
+        for word in ["x", "y", "x", "z", "y", "w"] {
+            assert_eq!(a.intern_ref(word).unwrap(), b.intern_ref(word).unwrap());
+        }
+        assert_eq!(a.as_values_slice(), b.as_values_slice());
+    }
+}
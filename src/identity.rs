@@ -0,0 +1,273 @@
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::{Interner, InternerError, iter::Iter};
+
+/// An interner for integers in which small values are their own handles.
+///
+/// When most values are small IDs, hashing and storing them is pure
+/// overhead. An `IdentityInterner` maps every value below a configurable
+/// `threshold` directly to the handle with the same number, without touching
+/// any table, and interns only the remaining values in a regular
+/// [`Interner`].
+///
+/// # Handle Space
+///
+/// - Handles `0..threshold` are the small values themselves. They are valid
+///   whether or not the value was ever interned, and resolving them is a
+///   plain conversion.
+/// - Handle `threshold + i` refers to the `i`-th stored large value. Values
+///   that are negative or at least `threshold` count as large.
+///
+/// Both ranges share the handle type `H`. A threshold beyond what `H` can
+/// represent makes interning the small values above that range fail with
+/// `InternerError::Overflow`, and the large values start overflowing as soon
+/// as `threshold + i` no longer fits in `H`. A threshold up to `H::MAX`
+/// leaves room for `H::MAX - threshold + 1` large values.
+///
+/// [`len`](Self::len) and [`iter`](Self::iter) only cover the stored large
+/// values.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::IdentityInterner;
+///
+/// let mut ids = IdentityInterner::<u64, RandomState>::new(RandomState::new(), 1 << 16);
+/// assert_eq!(ids.intern(42).unwrap(), 42);
+///
+/// let large = ids.intern(1 << 40).unwrap();
+/// assert_eq!(large, 1 << 16);
+/// assert_eq!(ids.resolve(large), Some(1 << 40));
+/// assert_eq!(ids.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct IdentityInterner<T, S, H = u32>
+where
+    T: Copy + Eq + Hash + TryFrom<usize>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<T> + TryFrom<H>,
+{
+    threshold: usize,
+    large: Interner<T, S, usize>,
+    _handle: PhantomData<H>,
+}
+
+impl<T, S, H> fmt::Debug for IdentityInterner<T, S, H>
+where
+    T: Copy + Eq + Hash + TryFrom<usize>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<T> + TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdentityInterner")
+            .field("threshold", &self.threshold)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> IdentityInterner<T, S, H>
+where
+    T: Copy + Eq + Hash + TryFrom<usize>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<T> + TryFrom<H>,
+{
+    /// Creates a new, empty interner in which the values below `threshold`
+    /// are their own handles.
+    #[must_use]
+    pub const fn new(hasher: S, threshold: usize) -> Self {
+        Self {
+            threshold,
+            large: Interner::new(hasher),
+            _handle: PhantomData,
+        }
+    }
+
+    /// Returns the threshold below which values are their own handles.
+    #[must_use]
+    #[inline]
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Interns a value, storing it only if it is not below the threshold.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented
+    /// by `H`; see the type-level docs.
+    pub fn intern(&mut self, value: T) -> Result<H, InternerError> {
+        if let Some(small) = self.small_index(value) {
+            return Self::to_handle(small);
+        }
+        let hash = match self.large.items.find_or_hash(&value) {
+            Ok(idx) => return self.large_handle(idx),
+            Err(hash) => hash,
+        };
+        let handle = self.large_handle(self.large.next_handle()?)?;
+        self.large.insert_hashed(hash, value);
+        Ok(handle)
+    }
+
+    /// Returns the handle for `value` if it is small or was interned, without
+    /// inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented
+    /// by `H`.
+    pub fn lookup_handle(&self, value: T) -> Result<Option<H>, InternerError> {
+        if let Some(small) = self.small_index(value) {
+            return Self::to_handle(small).map(Some);
+        }
+        self.large
+            .items
+            .get_index_of(&value)
+            .map(|idx| self.large_handle(idx))
+            .transpose()
+    }
+
+    /// Resolves a handle to its value.
+    ///
+    /// Small handles are converted back without any lookup. Returns `None`
+    /// if the handle refers to no stored large value.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<T> {
+        let idx = usize::try_from(handle).ok()?;
+        match idx.checked_sub(self.threshold) {
+            None => T::try_from(idx).ok(),
+            Some(large) => self.large.items.get_index(large).copied(),
+        }
+    }
+
+    /// Returns the number of stored large values.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.large.len()
+    }
+
+    /// Returns `true` if no large value is stored.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.large.is_empty()
+    }
+
+    /// Iterates over the stored large values, in handle order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.large.iter()
+    }
+
+    /// Returns `value` as an index if it is below the threshold.
+    #[inline]
+    fn small_index(&self, value: T) -> Option<usize> {
+        usize::try_from(value)
+            .ok()
+            .filter(|&idx| idx < self.threshold)
+    }
+
+    /// Returns the handle of the large value stored at `idx`.
+    #[inline]
+    fn large_handle(&self, idx: usize) -> Result<H, InternerError> {
+        self.threshold
+            .checked_add(idx)
+            .ok_or(InternerError::Overflow)
+            .and_then(Self::to_handle)
+    }
+
+    /// Converts a handle index to `H`.
+    #[inline]
+    fn to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ahash::RandomState;
+
+    use super::IdentityInterner;
+    use crate::InternerError;
+
+    #[test]
+    fn test_small_and_large_values() {
+        let mut interner = IdentityInterner::<u32, RandomState>::new(RandomState::new(), 1000);
+
+        // Small values are their own handles and are not stored.
+        assert_eq!(interner.intern(0).unwrap(), 0);
+        assert_eq!(interner.intern(999).unwrap(), 999);
+        assert_eq!(interner.intern(999).unwrap(), 999);
+        assert!(interner.is_empty());
+        assert_eq!(interner.resolve(7), Some(7));
+
+        // Large values are stored after the threshold, and deduplicated.
+        let a = interner.intern(1000).unwrap();
+        let b = interner.intern(u32::MAX).unwrap();
+        assert_eq!((a, b), (1000, 1001));
+        assert_eq!(interner.intern(1000).unwrap(), a);
+        assert_eq!(interner.intern(5).unwrap(), 5);
+        assert_eq!(interner.intern(u32::MAX).unwrap(), b);
+
+        assert_eq!(interner.len(), 2);
+        assert_eq!(
+            interner.iter().copied().collect::<Vec<_>>(),
+            [1000, u32::MAX]
+        );
+        assert_eq!(interner.resolve(a), Some(1000));
+        assert_eq!(interner.resolve(b), Some(u32::MAX));
+        assert_eq!(interner.resolve(1002), None);
+
+        assert_eq!(interner.lookup_handle(3).unwrap(), Some(3));
+        assert_eq!(interner.lookup_handle(u32::MAX).unwrap(), Some(b));
+        assert_eq!(interner.lookup_handle(2000).unwrap(), None);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_negative_values_are_large() {
+        let mut interner = IdentityInterner::<i64, RandomState, u16>::new(RandomState::new(), 10);
+        assert_eq!(interner.intern(-1).unwrap(), 10);
+        assert_eq!(interner.intern(9).unwrap(), 9);
+        assert_eq!(interner.intern(-1).unwrap(), 10);
+        assert_eq!(interner.resolve(10), Some(-1));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_handle_space_overflow() {
+        // 250 small handles leave room for 6 large values in a `u8`.
+        let mut interner = IdentityInterner::<u32, RandomState, u8>::new(RandomState::new(), 250);
+        for v in 0..6 {
+            assert_eq!(
+                interner.intern(1000 + v).unwrap(),
+                250 + u8::try_from(v).unwrap()
+            );
+        }
+        assert!(matches!(
+            interner.intern(2000),
+            Err(InternerError::Overflow)
+        ));
+        assert_eq!(interner.len(), 6);
+        assert_eq!(interner.intern(1003).unwrap(), 253);
+
+        // Small values beyond the handle type overflow as well.
+        let mut wide = IdentityInterner::<u32, RandomState, u8>::new(RandomState::new(), 1000);
+        assert_eq!(wide.intern(255).unwrap(), 255);
+        assert!(matches!(wide.intern(256), Err(InternerError::Overflow)));
+        assert!(matches!(wide.intern(5000), Err(InternerError::Overflow)));
+        assert!(wide.is_empty());
+    }
+}
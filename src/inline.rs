@@ -0,0 +1,285 @@
+use alloc::{borrow::Cow, boxed::Box, string::String};
+use core::{fmt, hash::BuildHasher, str};
+
+use crate::{Interner, InternerError};
+
+/// Marks a handle whose string is stored in the handle itself.
+const INLINE_TAG: u64 = 1 << 63;
+
+/// The longest string that fits in a handle.
+pub const MAX_INLINE_LEN: usize = 7;
+
+/// Bit offset of the length field of an inline handle.
+const LEN_SHIFT: u32 = 56;
+
+/// The most strings the table may hold, keeping table handles below
+/// `INLINE_TAG` on every platform.
+const MAX_TABLE_LEN: usize = usize::MAX >> 1;
+
+/// A string interner whose 64-bit handles carry short strings inline.
+///
+/// Strings of up to [`MAX_INLINE_LEN`] bytes are encoded directly into their
+/// handle and never touch the table; only longer strings are stored. When
+/// many distinct strings are short, this saves both the table entries and
+/// the hashing.
+///
+/// # Handle Layout
+///
+/// - Inline handles have the top bit set, the string length in bits 56 to
+///   62, and the UTF-8 bytes in the low 56 bits, first byte lowest. Unused
+///   bytes are zero, so every short string has exactly one encoding.
+/// - Table handles have the top bit clear and are plain indices.
+///
+/// Since short strings are never stored in the table, handle equality is
+/// equivalent to string equality across both kinds.
+///
+/// # Resolving
+///
+/// An inline handle owns no storage the interner could lend out, so
+/// [`resolve_with`](Self::resolve_with) decodes it into a stack buffer and
+/// passes a `&str` to a closure. [`resolve`](Self::resolve) returns a `Cow`
+/// that borrows table entries and allocates for inline ones.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::InlineStrInterner;
+///
+/// let mut interner = InlineStrInterner::new(RandomState::new());
+///
+/// let short = interner.intern("id").unwrap();
+/// let long = interner.intern("a longer string").unwrap();
+///
+/// assert!(InlineStrInterner::<RandomState>::is_inline(short));
+/// assert!(!InlineStrInterner::<RandomState>::is_inline(long));
+/// assert_eq!(interner.resolve_with(short, str::len), Some(2));
+/// assert_eq!(interner.resolve(long).as_deref(), Some("a longer string"));
+///
+/// // Only the long string takes up a table entry.
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct InlineStrInterner<S>
+where
+    S: BuildHasher,
+{
+    table: Interner<Box<str>, S, u64>,
+}
+
+impl<S> fmt::Debug for InlineStrInterner<S>
+where
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InlineStrInterner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<S> InlineStrInterner<S>
+where
+    S: BuildHasher,
+{
+    /// Creates a new, empty interner.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            table: Interner::new_with_limit(hasher, MAX_TABLE_LEN),
+        }
+    }
+
+    /// Interns a string, encoding it into the handle if it is short enough.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the table holds `usize::MAX / 2`
+    /// strings already.
+    pub fn intern(&mut self, s: &str) -> Result<u64, InternerError> {
+        if let Some(handle) = Self::encode(s) {
+            return Ok(handle);
+        }
+        self.table.intern_ref(s).map_err(|err| match err {
+            InternerError::LimitReached => InternerError::Overflow,
+            err => err,
+        })
+    }
+
+    /// Returns the handle for `s` if it is short or was interned, without
+    /// inserting.
+    #[must_use]
+    pub fn lookup_handle(&self, s: &str) -> Option<u64> {
+        Self::encode(s).or_else(|| self.table.lookup_handle(s).ok().flatten())
+    }
+
+    /// Calls `f` with the string behind `handle`.
+    ///
+    /// Returns `None` without calling `f` if the handle refers to no stored
+    /// string or is not a valid inline encoding.
+    pub fn resolve_with<R>(&self, handle: u64, f: impl FnOnce(&str) -> R) -> Option<R> {
+        if Self::is_inline(handle) {
+            let (bytes, len) = Self::decode(handle)?;
+            return str::from_utf8(&bytes[..len]).ok().map(f);
+        }
+        self.table.resolve(handle).map(|s| f(s))
+    }
+
+    /// Resolves a handle to its string.
+    ///
+    /// Table strings are borrowed; inline strings are decoded into a new
+    /// `String`. Prefer [`resolve_with`](Self::resolve_with) to avoid that
+    /// allocation.
+    #[must_use]
+    pub fn resolve(&self, handle: u64) -> Option<Cow<'_, str>> {
+        if Self::is_inline(handle) {
+            return self.resolve_with(handle, |s| Cow::Owned(String::from(s)));
+        }
+        self.table.resolve(handle).map(|s| Cow::Borrowed(&**s))
+    }
+
+    /// Returns `true` if `handle` carries its string inline.
+    #[must_use]
+    #[inline]
+    pub const fn is_inline(handle: u64) -> bool {
+        handle & INLINE_TAG != 0
+    }
+
+    /// Returns the number of strings stored in the table.
+    ///
+    /// Inline strings are not counted.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns `true` if no string is stored in the table.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Iterates over the strings stored in the table, in handle order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.table.iter().map(|s| &**s)
+    }
+
+    /// Encodes `s` into an inline handle if it is short enough.
+    fn encode(s: &str) -> Option<u64> {
+        let len = s.len();
+        if len > MAX_INLINE_LEN {
+            return None;
+        }
+        let mut bytes = [0; 8];
+        bytes[..len].copy_from_slice(s.as_bytes());
+        // `len` is at most 7, so the cast is lossless.
+        Some(INLINE_TAG | (len as u64) << LEN_SHIFT | u64::from_le_bytes(bytes))
+    }
+
+    /// Decodes an inline handle into its bytes and length.
+    ///
+    /// Returns `None` if the length field is out of range or a byte past
+    /// the length is set, which `encode` never produces.
+    fn decode(handle: u64) -> Option<([u8; 8], usize)> {
+        let len = usize::try_from((handle >> LEN_SHIFT) & 0x7f).ok()?;
+        let bytes = (handle & ((1 << LEN_SHIFT) - 1)).to_le_bytes();
+        if len > MAX_INLINE_LEN || bytes[len..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        Some((bytes, len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString as _, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::{INLINE_TAG, InlineStrInterner};
+
+    type Inline = InlineStrInterner<RandomState>;
+
+    #[test]
+    fn test_boundary_lengths() {
+        let mut interner = Inline::new(RandomState::new());
+
+        let empty = interner.intern("").unwrap();
+        let seven = interner.intern("1234567").unwrap();
+        let eight = interner.intern("12345678").unwrap();
+        assert!(Inline::is_inline(empty));
+        assert!(Inline::is_inline(seven));
+        assert!(!Inline::is_inline(eight));
+        assert_eq!(interner.len(), 1);
+
+        assert_eq!(interner.resolve(empty).as_deref(), Some(""));
+        assert_eq!(interner.resolve(seven).as_deref(), Some("1234567"));
+        assert_eq!(interner.resolve(eight).as_deref(), Some("12345678"));
+        assert_eq!(interner.intern("12345678").unwrap(), eight);
+        assert_eq!(interner.lookup_handle("1234567"), Some(seven));
+        assert_eq!(interner.lookup_handle("123456789"), None);
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let mut interner = Inline::new(RandomState::new());
+
+        // Seven bytes, three characters.
+        let inline = interner.intern("é€a").unwrap();
+        assert!(Inline::is_inline(inline));
+        assert_eq!(
+            interner.resolve_with(inline, str::to_string).unwrap(),
+            "é€a"
+        );
+
+        // Eight bytes, two characters.
+        let stored = interner.intern("😀😀").unwrap();
+        assert!(!Inline::is_inline(stored));
+        assert_eq!(interner.resolve(stored).as_deref(), Some("😀😀"));
+    }
+
+    #[test]
+    fn test_handles_never_collide() {
+        let mut interner = Inline::new(RandomState::new());
+        let strings = [
+            "",
+            "a",
+            "\0",
+            "a\0",
+            "ab",
+            "abcdefg",
+            "abcdefgh",
+            "\0\0\0\0\0\0\0\0",
+        ];
+        let handles: Vec<_> = strings
+            .iter()
+            .map(|s| interner.intern(s).unwrap())
+            .collect();
+
+        for (i, a) in handles.iter().enumerate() {
+            for (j, b) in handles.iter().enumerate() {
+                assert_eq!(a == b, i == j, "{:?} vs {:?}", strings[i], strings[j]);
+            }
+        }
+        for (s, &h) in strings.iter().zip(&handles) {
+            assert_eq!(interner.resolve(h).as_deref(), Some(*s));
+            assert_eq!(interner.intern(s).unwrap(), h);
+        }
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_handles() {
+        let interner = Inline::new(RandomState::new());
+        assert_eq!(interner.resolve(0), None);
+        // A length beyond the inline maximum.
+        assert_eq!(interner.resolve(INLINE_TAG | 8 << 56), None);
+        // A byte set past the length.
+        assert_eq!(interner.resolve(INLINE_TAG | 1 << 56 | 0x4142), None);
+        // Invalid UTF-8.
+        assert_eq!(interner.resolve(INLINE_TAG | 1 << 56 | 0xff), None);
+    }
+}
@@ -0,0 +1,194 @@
+use alloc::borrow::{Cow, ToOwned};
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::{FromRef, Interner, InternerError};
+
+/// A value that [`Interner::intern`] accepts, dispatching to the matching
+/// `intern_*` method.
+///
+/// It is implemented for an owned `T` (via `intern_owned`), a borrowed `&Q`
+/// (via `intern_ref`), and a `Cow<Q>` (via `intern_cow`), so generic code
+/// can intern its input without knowing whether it owns it.
+///
+/// # Markers
+///
+/// The parameter `M` is one of [`ByValue`], [`ByRef`], or [`ByCow`]. It only
+/// keeps the three implementations apart, since an owned `T` may itself be a
+/// reference or a `Cow`, and is always inferred. Generic code forwards it:
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::{Interner, IntoInternable};
+///
+/// fn intern_twice<V, M>(interner: &mut Interner<String, RandomState>, v: V, w: V) -> (u32, u32)
+/// where
+///     V: IntoInternable<String, M>,
+/// {
+///     (interner.intern(v).unwrap(), interner.intern(w).unwrap())
+/// }
+///
+/// let mut interner = Interner::new(RandomState::new());
+/// assert_eq!(intern_twice(&mut interner, "a", "b"), (0, 1));
+/// assert_eq!(intern_twice(&mut interner, "b".to_string(), "c".to_string()), (1, 2));
+/// ```
+pub trait IntoInternable<T, M>
+where
+    T: Eq + Hash,
+{
+    /// Interns `self` into `interner`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the `intern_*` method used.
+    fn intern_into<S, H>(self, interner: &mut Interner<T, S, H>) -> Result<H, InternerError>
+    where
+        S: BuildHasher,
+        H: Copy + TryFrom<usize>,
+        usize: TryFrom<H>;
+}
+
+/// Marks the [`IntoInternable`] implementation for owned values.
+#[derive(Debug)]
+pub struct ByValue;
+
+/// Marks the [`IntoInternable`] implementation for references to `Q`.
+#[derive(Debug)]
+pub struct ByRef<Q: ?Sized>(PhantomData<fn(&Q)>);
+
+/// Marks the [`IntoInternable`] implementation for `Cow<Q>`.
+#[derive(Debug)]
+pub struct ByCow<Q: ?Sized>(PhantomData<fn(&Q)>);
+
+impl<T> IntoInternable<T, ByValue> for T
+where
+    T: Eq + Hash,
+{
+    #[track_caller]
+    #[inline]
+    fn intern_into<S, H>(self, interner: &mut Interner<T, S, H>) -> Result<H, InternerError>
+    where
+        S: BuildHasher,
+        H: Copy + TryFrom<usize>,
+        usize: TryFrom<H>,
+    {
+        interner.intern_owned(self)
+    }
+}
+
+impl<T, Q> IntoInternable<T, ByRef<Q>> for &Q
+where
+    T: Eq + Hash + Borrow<Q> + FromRef<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    #[track_caller]
+    #[inline]
+    fn intern_into<S, H>(self, interner: &mut Interner<T, S, H>) -> Result<H, InternerError>
+    where
+        S: BuildHasher,
+        H: Copy + TryFrom<usize>,
+        usize: TryFrom<H>,
+    {
+        interner.intern_ref(self)
+    }
+}
+
+impl<T, Q> IntoInternable<T, ByCow<Q>> for Cow<'_, Q>
+where
+    T: Eq + Hash + Borrow<Q> + Clone,
+    Q: ToOwned<Owned = T> + Hash + Eq + ?Sized,
+{
+    #[track_caller]
+    #[inline]
+    fn intern_into<S, H>(self, interner: &mut Interner<T, S, H>) -> Result<H, InternerError>
+    where
+        S: BuildHasher,
+        H: Copy + TryFrom<usize>,
+        usize: TryFrom<H>,
+    {
+        interner.intern_cow(self)
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Interns an owned value, a reference, or a `Cow`, using
+    /// [`intern_owned`](Self::intern_owned), [`intern_ref`](Self::intern_ref),
+    /// or [`intern_cow`](Self::intern_cow) respectively.
+    ///
+    /// See [`IntoInternable`] for how the method is picked.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the `intern_*` method used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{borrow::Cow, collections::hash_map::RandomState};
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let a = interner.intern("a").unwrap();
+    /// assert_eq!(interner.intern("a".to_string()).unwrap(), a);
+    /// assert_eq!(interner.intern(Cow::Borrowed("a")).unwrap(), a);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn intern<V, M>(&mut self, value: V) -> Result<H, InternerError>
+    where
+        V: IntoInternable<T, M>,
+    {
+        value.intern_into(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::Cow, string::String};
+
+    use ahash::RandomState;
+
+    use crate::Interner;
+
+    #[test]
+    fn test_intern_dispatch() {
+        let mut interner: Interner<String, RandomState> = Interner::new(RandomState::new());
+        let owned = interner.intern(String::from("owned")).unwrap();
+        let borrowed = interner.intern("borrowed").unwrap();
+        let cow = interner.intern(Cow::Borrowed("cow")).unwrap();
+        assert_eq!((owned, borrowed, cow), (0, 1, 2));
+
+        assert_eq!(interner.intern("owned").unwrap(), owned);
+        assert_eq!(interner.intern(&String::from("cow")).unwrap(), cow);
+        assert_eq!(
+            interner
+                .intern(Cow::<str>::Owned(String::from("borrowed")))
+                .unwrap(),
+            borrowed
+        );
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn test_intern_reference_values() {
+        // With references as `T`, a reference is interned by value.
+        let mut interner: Interner<&str, RandomState> = Interner::new(RandomState::new());
+        let a = interner.intern("a").unwrap();
+        assert_eq!(interner.intern("a").unwrap(), a);
+        assert_eq!(interner.resolve(a), Some(&"a"));
+
+        let mut numbers: Interner<u32, RandomState> = Interner::new(RandomState::new());
+        assert_eq!(numbers.intern(5).unwrap(), numbers.intern(&5).unwrap());
+    }
+}
@@ -0,0 +1,275 @@
+extern crate alloc;
+
+use alloc::vec;
+use core::{fmt, iter::FusedIterator, slice};
+
+/// An iterator over the unique items of an interner, in handle order.
+///
+/// Returned by [`Interner::iter`](crate::Interner::iter),
+/// [`FrozenInterner::iter`](crate::FrozenInterner::iter), and the
+/// `IntoIterator` impls for their references.
+pub struct Iter<'a, T> {
+    inner: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    #[inline]
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            inner: items.iter(),
+        }
+    }
+
+    /// Returns the items not yet yielded as a slice.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &'a [T] {
+        self.inner.as_slice()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n)
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T> Default for Iter<'_, T> {
+    #[inline]
+    fn default() -> Self {
+        Self { inner: [].iter() }
+    }
+}
+
+/// An owning iterator over the unique items of an interner, in handle order.
+///
+/// Returned by the `IntoIterator` impl of [`Interner`](crate::Interner).
+pub struct IntoIter<T> {
+    inner: vec::IntoIter<T>,
+}
+
+impl<T> IntoIter<T> {
+    #[inline]
+    pub(crate) fn new(items: alloc::vec::Vec<T>) -> Self {
+        Self {
+            inner: items.into_iter(),
+        }
+    }
+
+    /// Returns the items not yet yielded as a slice.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n)
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T: Clone> Clone for IntoIter<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.inner.as_slice()).finish()
+    }
+}
+
+impl<T> Default for IntoIter<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(alloc::vec::Vec::new())
+    }
+}
+
+/// A parallel iterator over the unique items of an interner, in handle order.
+///
+/// Returned by [`Interner::par_iter`](crate::Interner::par_iter) and
+/// [`FrozenInterner::par_values`](crate::FrozenInterner::par_values).
+#[cfg(feature = "rayon")]
+pub struct ParIter<'a, T: Sync> {
+    inner: rayon::slice::Iter<'a, T>,
+}
+
+#[cfg(feature = "rayon")]
+mod par {
+    use core::fmt;
+
+    use rayon::iter::{
+        IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+        plumbing::{Consumer, ProducerCallback, UnindexedConsumer},
+    };
+
+    use super::ParIter;
+
+    impl<'a, T: Sync> ParIter<'a, T> {
+        #[inline]
+        pub(crate) fn new(items: &'a [T]) -> Self {
+            Self {
+                inner: items.into_par_iter(),
+            }
+        }
+    }
+
+    impl<'a, T: Sync> ParallelIterator for ParIter<'a, T> {
+        type Item = &'a T;
+
+        fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where
+            C: UnindexedConsumer<Self::Item>,
+        {
+            self.inner.drive_unindexed(consumer)
+        }
+
+        fn opt_len(&self) -> Option<usize> {
+            self.inner.opt_len()
+        }
+    }
+
+    impl<T: Sync> IndexedParallelIterator for ParIter<'_, T> {
+        fn drive<C>(self, consumer: C) -> C::Result
+        where
+            C: Consumer<Self::Item>,
+        {
+            self.inner.drive(consumer)
+        }
+
+        fn len(&self) -> usize {
+            self.inner.len()
+        }
+
+        fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where
+            CB: ProducerCallback<Self::Item>,
+        {
+            self.inner.with_producer(callback)
+        }
+    }
+
+    impl<T: Sync> Clone for ParIter<'_, T> {
+        fn clone(&self) -> Self {
+            Self {
+                inner: self.inner.clone(),
+            }
+        }
+    }
+
+    impl<T: Sync + fmt::Debug> fmt::Debug for ParIter<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("ParIter").field(&self.inner).finish()
+        }
+    }
+}
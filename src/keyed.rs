@@ -0,0 +1,461 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    iter::{FusedIterator, Zip},
+    marker::PhantomData,
+    slice,
+};
+
+use crate::{InternerError, store::Store};
+
+/// An interner that deduplicates values by a derived key while storing the
+/// first-seen full value.
+///
+/// Plain interning compares whole values, and canonicalizing values before
+/// interning them loses the original. A `KeyedInterner` instead projects each
+/// value to a key `K` (e.g. a normalized form or an ID field) and only stores
+/// a value if no value with an equal key has been interned yet. Later values
+/// with an equal key are rejected and receive the existing handle.
+///
+/// # Projection
+///
+/// The projection can be supplied per call with
+/// [`intern_by_key`](Self::intern_by_key), or owned by the interner via
+/// [`with_projection`](KeyedInterner::with_projection) or
+/// [`new_by_key`](KeyedInterner::new_by_key), in which case
+/// [`intern`](Self::intern) applies it to every insert. Owning the projection is
+/// recommended, as mixing projections for the same interner breaks
+/// deduplication.
+///
+/// # Memory Cost
+///
+/// Each entry stores its key next to its value, in addition to the hash
+/// index over the keys. For keys that are cheap copies (IDs) this is
+/// negligible; for keys that are derived strings, it roughly doubles the
+/// memory spent on each entry compared to a plain `Interner`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::KeyedInterner;
+///
+/// let mut interner: KeyedInterner<String, String, RandomState, u32, _> =
+///     KeyedInterner::with_projection(RandomState::new(), |s: &String| s.to_lowercase());
+///
+/// let (h1, new1) = interner.intern("Hello".to_string()).unwrap();
+/// let (h2, new2) = interner.intern("HELLO".to_string()).unwrap();
+///
+/// assert_eq!(h1, h2);
+/// assert!(new1 && !new2);
+/// assert_eq!(interner.resolve(h1).unwrap(), "Hello");
+/// ```
+#[derive(Clone)]
+pub struct KeyedInterner<T, K, S, H = u32, P = ()>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    keys: Store<K, S>,
+    /// The value stored under each key, at the same position.
+    values: Vec<T>,
+    projection: P,
+    _handle: PhantomData<H>,
+}
+
+impl<T, K, S, H, P> fmt::Debug for KeyedInterner<T, K, S, H, P>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedInterner")
+            .field("len", &self.len())
+            .field("capacity", &self.keys.capacity())
+            .finish()
+    }
+}
+
+impl<T, K, S, H> Default for KeyedInterner<T, K, S, H>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, K, S, H> KeyedInterner<T, K, S, H>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty keyed interner without an owned projection.
+    ///
+    /// Use [`intern_by_key`](Self::intern_by_key) to insert values.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            keys: Store::with_hasher(hasher),
+            values: Vec::new(),
+            projection: (),
+            _handle: PhantomData,
+        }
+    }
+}
+
+/// Derives the deduplication key of a value for a [`KeyedInterner`].
+///
+/// Implemented for every closure or function `Fn(&T) -> K`, and for [`ByKey`].
+pub trait Projection<T, K> {
+    /// Returns the key of `value`.
+    fn project(&self, value: &T) -> K;
+}
+
+impl<T, K, F> Projection<T, K> for F
+where
+    F: Fn(&T) -> K,
+{
+    #[inline]
+    fn project(&self, value: &T) -> K {
+        self(value)
+    }
+}
+
+/// A projection borrowing the key from a field of the value.
+///
+/// Created by [`KeyedInterner::new_by_key`]. The borrowed key is cloned into
+/// the interner's index.
+pub struct ByKey<T, K>(pub fn(&T) -> &K);
+
+impl<T, K> Clone for ByKey<T, K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, K> Copy for ByKey<T, K> {}
+
+impl<T, K: Clone> Projection<T, K> for ByKey<T, K> {
+    #[inline]
+    fn project(&self, value: &T) -> K {
+        (self.0)(value).clone()
+    }
+}
+
+impl<T, K, S, H> KeyedInterner<T, K, S, H, ByKey<T, K>>
+where
+    K: Eq + Hash + Clone,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty keyed interner deduplicating by a key field.
+    ///
+    /// This is a shorthand for [`with_projection`](KeyedInterner::with_projection)
+    /// for the common case where the key is a field of the value, such as an ID,
+    /// and avoids implementing a custom `Eq` or newtype-wrapping every record.
+    #[must_use]
+    pub const fn new_by_key(hasher: S, key_fn: fn(&T) -> &K) -> Self {
+        Self::with_projection(hasher, ByKey(key_fn))
+    }
+}
+
+impl<T, K, S, H, P> KeyedInterner<T, K, S, H, P>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    P: Projection<T, K>,
+{
+    /// Creates a new, empty keyed interner that owns its projection.
+    ///
+    /// Every call to [`intern`](Self::intern) derives the key with `projection`,
+    /// which guarantees that all inserts are deduplicated consistently.
+    #[must_use]
+    pub const fn with_projection(hasher: S, projection: P) -> Self {
+        Self {
+            keys: Store::with_hasher(hasher),
+            values: Vec::new(),
+            projection,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Interns `value` under the key derived by the owned projection.
+    ///
+    /// Returns the handle and `true` if `value` was stored, or the handle of the
+    /// existing value and `false` if a value with an equal key was already
+    /// present. In the latter case, `value` is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern(&mut self, value: T) -> Result<(H, bool), InternerError> {
+        let key = self.projection.project(&value);
+        self.intern_with_key(key, value)
+    }
+}
+
+impl<T, K, S, H, P> KeyedInterner<T, K, S, H, P>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Interns `value` under the key derived by `key_fn`.
+    ///
+    /// Returns the handle and `true` if `value` was stored, or the handle of the
+    /// existing value and `false` if a value with an equal key was already
+    /// present. In the latter case, `value` is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern_by_key<F>(&mut self, value: T, key_fn: F) -> Result<(H, bool), InternerError>
+    where
+        F: FnOnce(&T) -> K,
+    {
+        let key = key_fn(&value);
+        self.intern_with_key(key, value)
+    }
+
+    /// Interns `value` under an explicitly provided `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern_with_key(&mut self, key: K, value: T) -> Result<(H, bool), InternerError> {
+        let hash = match self.keys.find_or_hash(&key) {
+            Ok(idx) => return Ok((Self::idx_to_handle(idx)?, false)),
+            Err(hash) => hash,
+        };
+        let handle = Self::idx_to_handle(self.keys.len())?;
+        self.keys.push_with_hash(hash, key);
+        self.values.push(value);
+        Ok((handle, true))
+    }
+
+    /// Returns the handle of the value stored under `key`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub fn lookup_by_key<Q>(&self, key: &Q) -> Result<Option<H>, InternerError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.keys
+            .get_index_of(key)
+            .map_or(Ok(None), |idx| Ok(Some(Self::idx_to_handle(idx)?)))
+    }
+
+    /// Resolves a handle to the stored value.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        let idx = usize::try_from(handle).ok()?;
+        self.values.get(idx)
+    }
+
+    /// Resolves a handle to the key its value was stored under.
+    #[must_use]
+    #[inline]
+    pub fn resolve_key(&self, handle: H) -> Option<&K> {
+        let idx = usize::try_from(handle).ok()?;
+        self.keys.get_index(idx)
+    }
+
+    /// Returns the number of unique keys (and stored values).
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the interner contains no values.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over all stored `(key, value)` pairs in insertion order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, T> {
+        Iter {
+            inner: self.keys.as_slice().iter().zip(&self.values),
+        }
+    }
+
+    /// Internal helper to safely convert a `usize` index to a handle `H`.
+    #[inline]
+    fn idx_to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+/// An iterator over the `(key, value)` pairs of a [`KeyedInterner`], in
+/// handle order.
+///
+/// Returned by [`KeyedInterner::iter`].
+pub struct Iter<'a, K, T> {
+    inner: Zip<slice::Iter<'a, K>, slice::Iter<'a, T>>,
+}
+
+impl<'a, K, T> Iterator for Iter<'a, K, T> {
+    type Item = (&'a K, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n)
+    }
+}
+
+impl<K, T> DoubleEndedIterator for Iter<'_, K, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<K, T> ExactSizeIterator for Iter<'_, K, T> {}
+
+impl<K, T> FusedIterator for Iter<'_, K, T> {}
+
+impl<K, T> Clone for Iter<'_, K, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K: fmt::Debug, T: fmt::Debug> fmt::Debug for Iter<'_, K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString as _};
+
+    use ahash::RandomState;
+
+    use super::KeyedInterner;
+
+    #[derive(Debug, PartialEq)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    #[test]
+    fn test_intern_by_key_keeps_first_value() {
+        let mut interner = KeyedInterner::<User, u32, RandomState>::new(RandomState::new());
+
+        let (h1, inserted1) = interner
+            .intern_by_key(
+                User {
+                    id: 7,
+                    name: "first".to_string(),
+                },
+                |u| u.id,
+            )
+            .unwrap();
+        let (h2, inserted2) = interner
+            .intern_by_key(
+                User {
+                    id: 7,
+                    name: "second".to_string(),
+                },
+                |u| u.id,
+            )
+            .unwrap();
+
+        assert_eq!(h1, h2);
+        assert!(inserted1);
+        assert!(!inserted2);
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.resolve(h1).unwrap().name, "first");
+        assert_eq!(interner.resolve_key(h1), Some(&7));
+        assert_eq!(interner.lookup_by_key(&7).unwrap(), Some(h1));
+        assert_eq!(interner.lookup_by_key(&8).unwrap(), None);
+    }
+
+    #[test]
+    fn test_new_by_key_dedups_on_field() {
+        let mut interner =
+            KeyedInterner::<User, u32, RandomState, u32, _>::new_by_key(RandomState::new(), |u| {
+                &u.id
+            });
+
+        let (h1, _) = interner
+            .intern(User {
+                id: 1,
+                name: "alice".to_string(),
+            })
+            .unwrap();
+        let (h2, inserted) = interner
+            .intern(User {
+                id: 1,
+                name: "alice (renamed)".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(h1, h2);
+        assert!(!inserted);
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.resolve(h1).unwrap().name, "alice");
+    }
+
+    #[test]
+    fn test_owned_projection() {
+        let mut interner = KeyedInterner::<String, String, RandomState, u16, _>::with_projection(
+            RandomState::new(),
+            |s: &String| s.trim().to_lowercase(),
+        );
+
+        let (a, _) = interner.intern("  Apple".to_string()).unwrap();
+        let (b, inserted) = interner.intern("APPLE ".to_string()).unwrap();
+        let (c, _) = interner.intern("pear".to_string()).unwrap();
+
+        assert_eq!(a, b);
+        assert!(!inserted);
+        assert_ne!(a, c);
+        assert_eq!(interner.resolve(a).unwrap(), "  Apple");
+        assert_eq!(interner.lookup_by_key("apple").unwrap(), Some(a));
+        assert_eq!(interner.iter().count(), 2);
+    }
+}
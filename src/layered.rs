@@ -0,0 +1,299 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{FromRef, Interner, InternerError};
+
+/// An interner layered on top of a shared, read-only base interner.
+///
+/// Lookups consult the base first; only items missing from the base are stored
+/// in a local overlay. This makes it cheap to extend a large interner (e.g. one
+/// loaded from a previous compilation session) without cloning it.
+///
+/// Handles are stable across both layers: base items keep their original
+/// handles, and overlay items are numbered starting at `base.len()`. Resolving
+/// a base handle never touches the overlay's hash table.
+///
+/// # Examples
+///
+/// ```
+/// use std::{collections::hash_map::RandomState, sync::Arc};
+///
+/// use xgx_intern::{Interner, LayeredInterner};
+///
+/// let mut base = Interner::<String, RandomState>::new(RandomState::new());
+/// let hello = base.intern_ref("hello").unwrap();
+/// let base = Arc::new(base);
+///
+/// let mut layered = LayeredInterner::new(Arc::clone(&base), RandomState::new());
+/// assert_eq!(layered.intern_ref("hello").unwrap(), hello);
+///
+/// let world = layered.intern_ref("world").unwrap();
+/// assert_eq!(world, 1);
+/// assert_eq!(layered.overlay_len(), 1);
+/// assert_eq!(base.len(), 1);
+/// ```
+pub struct LayeredInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    base: Arc<Interner<T, S, H>>,
+    overlay: Interner<T, S, H>,
+}
+
+impl<T, S, H> fmt::Debug for LayeredInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayeredInterner")
+            .field("base_len", &self.base.len())
+            .field("overlay_len", &self.overlay.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> LayeredInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new layered interner over `base`, with an empty overlay
+    /// using the given `BuildHasher`.
+    #[must_use]
+    pub const fn new(base: Arc<Interner<T, S, H>>, hasher: S) -> Self {
+        Self {
+            base,
+            overlay: Interner::new(hasher),
+        }
+    }
+
+    /// Returns the shared base interner.
+    #[must_use]
+    #[inline]
+    pub const fn base(&self) -> &Arc<Interner<T, S, H>> {
+        &self.base
+    }
+
+    /// Interns an owned value, storing it in the overlay if the base does not
+    /// already contain it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the combined handle space of both
+    /// layers is exhausted.
+    pub fn intern_owned(&mut self, item: T) -> Result<H, InternerError> {
+        if let Some(idx) = self.base.items.get_index_of(&item) {
+            return Interner::<T, S, H>::idx_to_handle(idx);
+        }
+        let hash = match self.overlay.items.find_or_hash(&item) {
+            Ok(idx) => return self.overlay_handle(idx),
+            Err(hash) => hash,
+        };
+        let handle = self.overlay_handle(self.overlay.len())?;
+        self.overlay.insert_hashed(hash, item);
+        Ok(handle)
+    }
+
+    /// Interns a borrowed value, cloning it into the overlay only if neither
+    /// layer already contains it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the combined handle space of both
+    /// layers is exhausted.
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(idx) = self.base.items.get_index_of(item) {
+            return Interner::<T, S, H>::idx_to_handle(idx);
+        }
+        let hash = match self.overlay.items.find_or_hash(item) {
+            Ok(idx) => return self.overlay_handle(idx),
+            Err(hash) => hash,
+        };
+        let handle = self.overlay_handle(self.overlay.len())?;
+        self.overlay.insert_hashed(hash, T::from_ref(item));
+        Ok(handle)
+    }
+
+    /// Returns the handle for `item` if present in either layer, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub fn lookup_handle<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(idx) = self.base.items.get_index_of(item) {
+            return Interner::<T, S, H>::idx_to_handle(idx).map(Some);
+        }
+        self.overlay
+            .items
+            .get_index_of(item)
+            .map_or(Ok(None), |idx| Ok(Some(self.overlay_handle(idx)?)))
+    }
+
+    /// Returns true if an equal item is present in either layer.
+    #[inline]
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.base.contains(item) || self.overlay.contains(item)
+    }
+
+    /// Resolves a handle from either layer.
+    ///
+    /// Handles below `base().len()` are resolved against the base only.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        let idx = usize::try_from(handle).ok()?;
+        let base_len = self.base.len();
+        if idx < base_len {
+            self.base.items.get_index(idx)
+        } else {
+            self.overlay.items.get_index(idx - base_len)
+        }
+    }
+
+    /// Returns the total number of unique items across both layers.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.base.len() + self.overlay.len()
+    }
+
+    /// Returns `true` if both layers are empty.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty() && self.overlay.is_empty()
+    }
+
+    /// Returns the number of items stored in the overlay.
+    #[must_use]
+    #[inline]
+    pub fn overlay_len(&self) -> usize {
+        self.overlay.len()
+    }
+
+    /// Iterates over all unique items, base items first, in handle order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.base.iter().chain(self.overlay.iter())
+    }
+
+    /// Consumes the layered interner and returns only the overlay.
+    ///
+    /// Item `i` of the returned interner corresponds to the layered handle
+    /// `base().len() + i`. This is the part that needs persisting after an
+    /// incremental session.
+    #[must_use]
+    pub fn into_overlay(self) -> Interner<T, S, H> {
+        self.overlay
+    }
+
+    /// Converts an overlay index to a layered handle.
+    #[inline]
+    fn overlay_handle(&self, idx: usize) -> Result<H, InternerError> {
+        let idx = self
+            .base
+            .len()
+            .checked_add(idx)
+            .ok_or(InternerError::Overflow)?;
+        Interner::<T, S, H>::idx_to_handle(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString as _},
+        sync::Arc,
+    };
+
+    use ahash::RandomState;
+
+    use super::LayeredInterner;
+    use crate::{Interner, InternerError};
+
+    fn create_base() -> Arc<Interner<String, RandomState>> {
+        let mut base = Interner::new(RandomState::new());
+        base.intern_ref("a").unwrap();
+        base.intern_ref("b").unwrap();
+        Arc::new(base)
+    }
+
+    #[test]
+    fn test_dedups_against_base_and_offsets_overlay() {
+        let base = create_base();
+        let mut layered = LayeredInterner::new(Arc::clone(&base), RandomState::new());
+
+        assert_eq!(layered.intern_ref("b").unwrap(), 1);
+        assert_eq!(layered.overlay_len(), 0);
+
+        let c = layered.intern_owned("c".to_string()).unwrap();
+        let d = layered.intern_ref("d").unwrap();
+        assert_eq!((c, d), (2, 3));
+        assert_eq!(layered.intern_ref("c").unwrap(), c);
+
+        assert_eq!(layered.len(), 4);
+        assert_eq!(layered.resolve(0), Some(&"a".to_string()));
+        assert_eq!(layered.resolve(d), Some(&"d".to_string()));
+        assert_eq!(layered.resolve(4), None);
+        assert_eq!(layered.lookup_handle("d").unwrap(), Some(d));
+        assert_eq!(layered.lookup_handle("a").unwrap(), Some(0));
+        assert_eq!(layered.lookup_handle("z").unwrap(), None);
+        assert!(layered.contains("a") && layered.contains("c"));
+        assert_eq!(layered.iter().count(), 4);
+    }
+
+    #[test]
+    fn test_into_overlay_leaves_base_untouched() {
+        let base = create_base();
+        let mut layered = LayeredInterner::new(Arc::clone(&base), RandomState::new());
+        layered.intern_ref("c").unwrap();
+
+        let overlay = layered.into_overlay();
+        assert_eq!(overlay.len(), 1);
+        assert_eq!(overlay.resolve(0), Some(&"c".to_string()));
+
+        assert_eq!(Arc::strong_count(&base), 1);
+        assert_eq!(base.len(), 2);
+        assert!(!base.contains("c"));
+    }
+
+    #[test]
+    fn test_overflow_counts_both_layers() {
+        let mut base = Interner::<u16, RandomState, u8>::new(RandomState::new());
+        for i in 0..=255 {
+            base.intern_owned(i).unwrap();
+        }
+        let mut layered = LayeredInterner::new(Arc::new(base), RandomState::new());
+
+        assert_eq!(layered.intern_owned(255).unwrap(), 255);
+        assert!(matches!(
+            layered.intern_owned(256),
+            Err(InternerError::Overflow)
+        ));
+        assert_eq!(layered.overlay_len(), 0);
+    }
+}
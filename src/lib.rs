@@ -14,31 +14,261 @@ pub mod float;
 /// Provides the `FromRef` trait for constructing owned types from references.
 pub mod from_ref;
 
+/// Provides the `IntoInternable` trait behind `Interner::intern`.
+pub mod internable;
+
+/// Provides `ArcInterner`, an interner whose resolved values are owned `Arc`s.
+pub mod arc_interner;
+
 /// Provides the memory-efficient `ArenaString` type for low-overhead interning.
 pub mod arena_string;
 
+/// Provides `analyze`, which estimates the savings of interning a sample of
+/// strings.
+pub mod analyze;
+
+/// Provides `ArenaInterner`, a string interner storing all strings in a
+/// single arena.
+pub mod arena_interner;
+
+/// Provides `ArenaView` and `ArenaImportError`, for loading arenas written by
+/// `export_arena` with strict validation.
+pub mod arena_import;
+
+/// Provides `AnyInterner`, a registry holding one interner per interned type.
+pub mod any_interner;
+
+mod boxed;
+
+mod pooled;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+
+#[cfg(feature = "metrics")]
+mod metrics_impl;
+
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+
+#[cfg(feature = "malloc_size_of")]
+mod malloc_size_of_impl;
+
+mod filter;
+
+mod store;
+
+/// Provides `ContentAddressedInterner`, which deduplicates blobs by their
+/// BLAKE3 digest without storing them.
+#[cfg(feature = "blake3")]
+pub mod content_addressed;
+
+/// Provides `AllocInterner` and `ArenaStrInterner`, which allocate all their
+/// storage from a custom allocator.
+#[cfg(feature = "allocator-api2")]
+pub mod allocator;
+
+/// Provides `CompressedInterner`, which keeps rarely resolved values
+/// LZ4-compressed in memory.
+#[cfg(feature = "lz4")]
+pub mod compressed;
+
+/// Provides `CellInterner`, a single-threaded interner usable through `&self`.
+pub mod cell;
+
+/// Provides `CachingResolver`, which caches the last resolved handle.
+pub mod caching;
+
+/// Provides `DynState` and `DynInterner`, which erase the hasher type to
+/// reduce monomorphization.
+pub mod dynamic;
+
+/// Provides `FrozenInterner`, an immutable, shareable form of an `Interner`.
+pub mod frozen;
+
+/// Provides `GrowthPolicy`, which controls how an `Interner` grows its storage.
+pub mod growth;
+
+/// Provides `PrehashedValue`, a wrapper caching the hash of large values.
+pub mod prehashed;
+
+/// Provides `MemoryUsage` and the `HeapSize` trait for estimating memory use.
+pub mod memory;
+
+/// Provides `InternerStats`, hit/miss counters for an `Interner`.
+#[cfg(feature = "stats")]
+pub mod stats;
+
+/// Provides the `ShrinkToFit` trait for trimming the capacity of stored values.
+pub mod shrink;
+
+/// Provides `DeterministicState`, a seeded `BuildHasher` for reproducible
+/// interner behavior.
+pub mod deterministic;
+
+/// Provides handle type aliases and aliases for common `Interner` configurations.
+pub mod handle;
+
+/// Provides `IdentityInterner`, an integer interner in which small values are
+/// their own handles.
+pub mod identity;
+
+/// Provides `InlineStrInterner`, a string interner whose handles carry short
+/// strings inline.
+pub mod inline;
+
+/// Provides `Iter`, `IntoIter`, and `ParIter`, the iterators over the items
+/// of an `Interner`.
+pub mod iter;
+
+/// Provides `KeyedInterner`, which deduplicates values by a derived key.
+pub mod keyed;
+
+/// Provides `LayeredInterner`, a mutable overlay on top of a shared base interner.
+pub mod layered;
+
+/// Provides `ThreadedInterner`, a thread-safe interner behind an internal lock.
+#[cfg(feature = "std")]
+pub mod threaded;
+
+/// Provides `ShardedInterner`, a concurrent interner split into independently
+/// locked shards.
+#[cfg(feature = "std")]
+pub mod sharded;
+
+/// Provides `ConcurrentInterner`, a concurrent interner whose resolve path
+/// takes no lock.
+#[cfg(feature = "std")]
+pub mod concurrent;
+
+/// Provides `SnapshotInterner`, which publishes immutable snapshots of an
+/// interner through an `ArcSwap`.
+#[cfg(feature = "arc-swap")]
+pub mod snapshot;
+
+/// Provides `AsyncInterner`, an interner for async code behind a Tokio lock.
+#[cfg(feature = "tokio")]
+pub mod async_interner;
+
+/// Provides `LruInterner`, a bounded interner evicting the least recently
+/// used value.
+pub mod lru;
+
+/// Provides `ObservedInterner`, which calls an observer for every newly
+/// inserted item.
+pub mod observed;
+
+/// Provides `WeakInterner`, which holds values weakly so unused ones can be
+/// purged.
+pub mod weak;
+
+/// Provides `PersistentInterner`, a string interner backed by an append-only
+/// log file.
+#[cfg(feature = "persist")]
+pub mod persistent;
+
+/// Provides `RcInterner`, which removes entries once every reference is released.
+pub mod refcounted;
+
+/// Provides `InternerPool`, which merges worker-local interners into one.
+pub mod pool;
+
+/// Provides `SmallInterner`, which stores its first few items inline before
+/// spilling into an `Interner`.
+pub mod small;
+
+/// Provides `ResolvedArena`, an exported string arena that resolves handles,
+/// and `ArenaOverflow`.
+pub mod resolved;
+
+/// Provides `SerializedHandles`, which stores handles with a fingerprint of
+/// the interner that issued them.
+pub mod serialized;
+
+/// Provides `SubstrInterner`, a string interner that stores substrings as
+/// references into already interned parent strings.
+pub mod substr;
+
+#[cfg(feature = "allocator-api2")]
+pub use allocator::{AllocInterner, ArenaStrInterner};
+pub use analyze::{DedupReport, analyze};
+pub use any_interner::AnyInterner;
+pub use arc_interner::ArcInterner;
+pub use arena_import::{ArenaImportError, ArenaView};
+pub use arena_interner::ArenaInterner;
 pub use arena_string::ArenaString;
-pub use float::{HashableF32, HashableF64};
+#[cfg(feature = "tokio")]
+pub use async_interner::AsyncInterner;
+pub use caching::CachingResolver;
+pub use cell::CellInterner;
+#[cfg(feature = "lz4")]
+pub use compressed::CompressedInterner;
+#[cfg(feature = "std")]
+pub use concurrent::ConcurrentInterner;
+#[cfg(feature = "blake3")]
+pub use content_addressed::{ContentAddressedInterner, Digest};
+pub use deterministic::DeterministicState;
+pub use dynamic::{DynInterner, DynState};
+pub use float::{HashableF32, HashableF64, HashableOrderedF32, HashableOrderedF64};
 pub use from_ref::FromRef;
+pub use frozen::FrozenInterner;
+pub use growth::GrowthPolicy;
+#[cfg(feature = "std")]
+pub use handle::{BytesInterner, DefaultInterner, SecureInterner, StringInterner};
+pub use handle::{Handle16, Handle32, Handle64};
+pub use identity::IdentityInterner;
+pub use inline::InlineStrInterner;
+pub use internable::IntoInternable;
+pub use keyed::KeyedInterner;
+pub use layered::LayeredInterner;
+pub use lru::{LruHandle, LruInterner};
+pub use memory::{HeapSize, MemoryUsage};
+pub use observed::ObservedInterner;
+#[cfg(feature = "persist")]
+pub use persistent::PersistentInterner;
+pub use pool::{InternerPool, LocalInterner, Remap};
+pub use prehashed::PrehashedValue;
+pub use refcounted::RcInterner;
+pub use resolved::{ArenaOverflow, ResolvedArena};
+pub use serialized::{HANDLES_FORMAT_VERSION, SerializedHandles};
+#[cfg(feature = "std")]
+pub use sharded::ShardedInterner;
+pub use shrink::ShrinkToFit;
+pub use small::SmallInterner;
+#[cfg(feature = "arc-swap")]
+pub use snapshot::SnapshotInterner;
+#[cfg(feature = "stats")]
+pub use stats::{DedupSavings, InternerStats};
+pub use substr::SubstrInterner;
+#[cfg(feature = "std")]
+pub use threaded::ThreadedInterner;
+pub use weak::WeakInterner;
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use alloc::{
     borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::TryReserveError,
     string::String,
     vec::Vec,
 };
 use core::{
     borrow::Borrow,
+    cmp::Ordering,
     fmt,
     hash::{BuildHasher, Hash},
     marker::PhantomData,
+    ops::Deref,
+    panic::Location,
 };
 
-use indexmap::IndexSet;
+use store::Store;
 
 /// Represents errors that can occur during an interning operation.
-#[derive(Clone, Copy, Debug, thiserror::Error)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
 pub enum InternerError {
     /// Occurs when the number of unique items exceeds the maximum value
     /// representable by the handle type `H`.
@@ -47,6 +277,65 @@ pub enum InternerError {
     /// on the attempt to intern the 2^32-th unique item.
     #[error("Interner handle space exhausted")]
     Overflow,
+    /// Occurs when inserting a new unique item would exceed the item limit
+    /// configured with [`Interner::new_with_limit`].
+    ///
+    /// Unlike [`Overflow`](Self::Overflow), this is independent of the width of
+    /// the handle type `H`. Lookups of already interned items still succeed.
+    #[error("Interner item limit reached")]
+    LimitReached,
+    /// Occurs when a handle passed to an operation does not resolve to an
+    /// interned item.
+    #[error("Interner handle is invalid")]
+    InvalidHandle,
+    /// Occurs when a byte range is out of bounds, reversed, or does not fall on
+    /// UTF-8 character boundaries.
+    #[error("Interner byte range is invalid")]
+    InvalidRange,
+    /// Occurs when an item that is required to be unique is already present.
+    #[error("Interner item is a duplicate")]
+    Duplicate,
+    /// Occurs when reserving storage fails, because the requested capacity
+    /// overflows `usize` or the allocator cannot provide it.
+    #[error("Interner storage allocation failed")]
+    AllocationFailed,
+    /// Occurs when serialized data has an unknown format version or is
+    /// malformed.
+    #[error("Interner serialized data has an unsupported format")]
+    UnsupportedFormat,
+    /// Occurs when serialized handles are loaded into an interner whose
+    /// contents differ from the one that issued them.
+    #[error("Interner handles were issued by a different interner")]
+    FingerprintMismatch,
+}
+
+impl From<TryReserveError> for InternerError {
+    fn from(_: TryReserveError) -> Self {
+        Self::AllocationFailed
+    }
+}
+
+/// The error returned by [`Interner::intern_owned_all_distinct`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum DuplicateError<T> {
+    /// An item was already present, either interned before the call or
+    /// earlier in the same input. Holds the offending item.
+    #[error("Interner item is a duplicate")]
+    Duplicate(T),
+    /// Interning a distinct item failed.
+    #[error(transparent)]
+    Interner(#[from] InternerError),
+}
+
+/// The error returned by [`Interner::resolve_existing_handle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum MissingItemError {
+    /// The item was never interned.
+    #[error("Interner item is missing")]
+    Missing,
+    /// The item is present, but looking up its handle failed.
+    #[error(transparent)]
+    Interner(#[from] InternerError),
 }
 
 /// A generic, high-performance interner for deduplicating values.
@@ -70,6 +359,14 @@ pub enum InternerError {
 ///   be customized (e.g., `u16` for memory savings if the number of unique items is low,
 ///   or `u64` if it is very high).
 ///
+/// # Non-`Clone` Values
+///
+/// `T` does not need to implement `Clone`. Only the methods that copy a
+/// borrowed value into the interner (`intern_ref`, `intern_cow`, and their
+/// variants) and cloning the interner itself require it. Values owning a
+/// unique resource can be interned with `intern_owned` and used through
+/// `resolve`, `iter`, and `export` as usual.
+///
 /// # Examples
 ///
 /// ```
@@ -109,7 +406,22 @@ where
     H: Copy + TryFrom<usize>, // for index -> handle
     usize: TryFrom<H>,        // for handle -> index
 {
-    items: IndexSet<T, S>,
+    items: Store<T, S>,
+    max_items: usize,
+    growth: GrowthPolicy,
+    /// Whether insertions are re-verified in debug builds; see `with_checked`.
+    checked: bool,
+    /// Hit/miss counters; see `stats`.
+    #[cfg(feature = "stats")]
+    stats: stats::Counters<T>,
+    /// Metric handles; see `with_metrics`.
+    #[cfg(feature = "metrics")]
+    metrics: Option<metrics_impl::Metrics<T>>,
+    /// Where the last handle overflow was reported; see `last_overflow_location`.
+    overflow_location: Option<&'static Location<'static>>,
+    /// Returned for new items once handles run out; see
+    /// `with_overflow_handle`.
+    overflow_handle: Option<H>,
     _handle: PhantomData<H>,
 }
 
@@ -141,6 +453,72 @@ where
     }
 }
 
+impl<T, S, H> fmt::Display for Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Writes a terse summary such as `Interner(len=42, cap=64)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Interner(len={}, cap={})", self.len(), self.capacity())
+    }
+}
+
+/// Interners are equal if they hold equal items in the same order, so equal
+/// interners map every handle to equal items. Hashers, capacity, and
+/// settings are ignored.
+impl<T, S, H> PartialEq for Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_values_slice() == other.as_values_slice()
+    }
+}
+
+impl<T, S, H> Eq for Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+}
+
+/// Compares the items lexicographically in insertion order, like slices.
+impl<T, S, H> PartialOrd for Interner<T, S, H>
+where
+    T: Ord + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares the items lexicographically in insertion order, like slices.
+impl<T, S, H> Ord for Interner<T, S, H>
+where
+    T: Ord + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_values_slice().cmp(other.as_values_slice())
+    }
+}
+
 impl<T, S, H> Interner<T, S, H>
 where
     T: Eq + Hash,
@@ -156,75 +534,674 @@ where
     /// performance gains.
     #[must_use]
     pub const fn new(hasher: S) -> Self {
+        Self::from_store(Store::with_hasher(hasher))
+    }
+
+    /// Creates a new interner with a specified capacity and `BuildHasher`.
+    ///
+    /// This is useful when the number of unique items to be interned is known
+    /// in advance. Pre-allocating capacity can prevent multiple reallocations
+    /// of the internal hash map, improving performance.
+    #[must_use]
+    pub fn with_capacity(hasher: S, capacity: usize) -> Self {
+        Self::from_store(Store::with_capacity_and_hasher(capacity, hasher))
+    }
+
+    /// Creates a new interner that refuses to store more than `max_items`
+    /// unique items.
+    ///
+    /// This bounds memory usage regardless of the width of the handle type `H`.
+    /// Once the limit is reached, interning a new item returns
+    /// `InternerError::LimitReached`, while interning or looking up an item that
+    /// is already present keeps succeeding. This is shorthand for
+    /// [`new`](Self::new) followed by [`with_limit`](Self::with_limit).
+    #[must_use]
+    pub const fn new_with_limit(hasher: S, max_items: usize) -> Self {
+        Self::new(hasher).with_limit(max_items)
+    }
+
+    /// Makes the interner refuse to store more than `max_items` unique items;
+    /// see [`new_with_limit`](Self::new_with_limit).
+    ///
+    /// Items already stored beyond the limit are kept, but no new ones are
+    /// accepted while the interner holds `max_items` or more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::{Interner, InternerError};
+    ///
+    /// let mut interner = Interner::<u32, RandomState>::new(RandomState::new()).with_limit(2);
+    /// interner.intern_owned(1).unwrap();
+    /// interner.intern_owned(2).unwrap();
+    /// assert!(matches!(interner.intern_owned(3), Err(InternerError::LimitReached)));
+    /// assert_eq!(interner.intern_owned(1).unwrap(), 0);
+    /// ```
+    #[must_use]
+    pub const fn with_limit(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+
+    /// Creates a new, empty interner that grows its storage according to
+    /// `policy`.
+    ///
+    /// See [`GrowthPolicy`] for the tradeoff between doubling and linear
+    /// growth. This is shorthand for [`new`](Self::new) followed by
+    /// [`with_growth`](Self::with_growth).
+    #[must_use]
+    pub const fn new_with_growth(hasher: S, policy: GrowthPolicy) -> Self {
+        Self::new(hasher).with_growth(policy)
+    }
+
+    /// Makes the interner grow its storage according to `policy` from now
+    /// on; see [`new_with_growth`](Self::new_with_growth).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::{GrowthPolicy, Interner};
+    ///
+    /// let mut interner = Interner::<u32, RandomState>::new(RandomState::new())
+    ///     .with_growth(GrowthPolicy::Linear(100))
+    ///     .with_limit(1000);
+    /// interner.intern_owned(7).unwrap();
+    /// assert_eq!(interner.growth_policy(), GrowthPolicy::Linear(100));
+    /// assert_eq!(interner.capacity(), 100);
+    /// ```
+    #[must_use]
+    pub const fn with_growth(mut self, policy: GrowthPolicy) -> Self {
+        self.growth = policy;
+        self
+    }
+
+    /// Creates a new, empty interner that verifies the `Hash`/`Eq` contract
+    /// of `T` on every insertion in builds with debug assertions.
+    ///
+    /// After a new item is inserted, it is looked up again, and the lookup
+    /// must find it under its new handle. A `Hash` that is not deterministic,
+    /// or an `Eq` that is not reflexive, fails this check; such types would
+    /// otherwise silently intern equal values more than once. This is meant
+    /// for catching buggy `T` implementations in tests, and costs one extra
+    /// lookup per insertion. Without debug assertions, it behaves exactly like
+    /// [`new`](Self::new). This is shorthand for [`new`](Self::new) followed
+    /// by [`with_checked(true)`](Self::with_checked).
+    ///
+    /// # Panics
+    ///
+    /// Interning panics if the check fails.
+    #[must_use]
+    pub const fn new_checked(hasher: S) -> Self {
+        Self::new(hasher).with_checked(true)
+    }
+
+    /// Turns the debug-build verification of `T`'s `Hash`/`Eq` contract on
+    /// every insertion on or off; see [`new_checked`](Self::new_checked).
+    ///
+    /// # Panics
+    ///
+    /// Once enabled, interning panics if the check fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new())
+    ///     .with_negative_filter(10)
+    ///     .with_checked(true);
+    /// let h = interner.intern_ref("hello").unwrap();
+    /// assert_eq!(interner.lookup_handle("hello").unwrap(), Some(h));
+    /// ```
+    #[must_use]
+    pub const fn with_checked(mut self, enabled: bool) -> Self {
+        self.checked = enabled;
+        self
+    }
+
+    /// Creates a new, empty interner that maps new items to `fallback`
+    /// instead of failing once the handle space of `H` is exhausted.
+    ///
+    /// # ⚠️ Lossy Deduplication
+    ///
+    /// After overflow, every new item is **not stored**: interning it returns
+    /// `fallback`, which resolves to whatever item `fallback` designates (or
+    /// to nothing, if no item has that handle), never to the item itself.
+    /// Distinct items then share one handle, so comparing handles no longer
+    /// implies comparing values, and [`lookup_handle`](Self::lookup_handle)
+    /// still reports such items as absent. Items interned before the overflow
+    /// keep their own handles and resolve normally.
+    ///
+    /// This suits workloads where exceeding the handle space is rare and a
+    /// degraded mode beats a hard failure, typically with `fallback` being
+    /// the handle of a sentinel such as `"<overflow>"`, interned first.
+    /// Only `InternerError::Overflow` is replaced: reaching an item limit or
+    /// failing to allocate is still an error, and so is overflow in
+    /// [`intern_owned_all_distinct`](Self::intern_owned_all_distinct), whose
+    /// handles must be distinct. Overflows are still recorded for
+    /// [`last_overflow_location`](Self::last_overflow_location).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState, u8>::new_with_overflow_handle(RandomState::new(), 0);
+    /// let sentinel = interner.intern_ref("<overflow>").unwrap();
+    /// for i in 1..256 {
+    ///     interner.intern_ref(&i.to_string()).unwrap();
+    /// }
+    ///
+    /// // The handle space is exhausted, so a new item maps to the sentinel.
+    /// let h = interner.intern_ref("one too many").unwrap();
+    /// assert_eq!(h, sentinel);
+    /// assert_eq!(interner.resolve(h).map(String::as_str), Some("<overflow>"));
+    /// ```
+    ///
+    /// This is shorthand for [`new`](Self::new) followed by
+    /// [`with_overflow_handle`](Self::with_overflow_handle).
+    #[must_use]
+    pub const fn new_with_overflow_handle(hasher: S, fallback: H) -> Self {
+        Self::new(hasher).with_overflow_handle(fallback)
+    }
+
+    /// Makes the interner map new items to `fallback` instead of failing once
+    /// the handle space of `H` is exhausted; see
+    /// [`new_with_overflow_handle`](Self::new_with_overflow_handle) for the
+    /// lossy deduplication this implies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::{Interner, InternerError};
+    ///
+    /// let mut interner = Interner::<u16, RandomState, u8>::new(RandomState::new())
+    ///     .with_overflow_handle(0)
+    ///     .with_limit(300);
+    /// for i in 0..=255 {
+    ///     interner.intern_owned(i).unwrap();
+    /// }
+    /// assert_eq!(interner.intern_owned(256).unwrap(), 0);
+    ///
+    /// // Reaching the item limit is still an error.
+    /// let mut limited = Interner::<u16, RandomState, u8>::new(RandomState::new())
+    ///     .with_overflow_handle(0)
+    ///     .with_limit(1);
+    /// limited.intern_owned(1).unwrap();
+    /// assert!(matches!(limited.intern_owned(2), Err(InternerError::LimitReached)));
+    /// ```
+    #[must_use]
+    pub const fn with_overflow_handle(mut self, fallback: H) -> Self {
+        self.overflow_handle = Some(fallback);
+        self
+    }
+
+    /// Internal helper wrapping `items` in an interner with the default
+    /// settings, which the public constructors override field by field.
+    #[inline]
+    const fn from_store(items: Store<T, S>) -> Self {
         Self {
-            items: IndexSet::with_hasher(hasher),
+            items,
+            max_items: usize::MAX,
+            growth: GrowthPolicy::Doubling,
+            checked: false,
+            #[cfg(feature = "stats")]
+            stats: stats::Counters::new(),
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            overflow_location: None,
+            overflow_handle: None,
             _handle: PhantomData,
         }
     }
 
-    /// Creates a new interner with a specified capacity and `BuildHasher`.
+    /// Creates an interner from borrowed items, converting only the first
+    /// occurrence of each distinct item into a `T`.
+    ///
+    /// This is the borrowed counterpart of collecting owned values: every
+    /// item goes through [`intern_ref`](Self::intern_ref), so repeated items
+    /// are neither cloned nor stored twice. Handles follow the order of
+    /// first occurrence.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the distinct items exceed the
+    /// handle capacity of `H`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let words = ["a", "b", "a", "c"];
+    /// let interner = Interner::<String, RandomState>::from_refs(RandomState::new(), words).unwrap();
+    /// assert_eq!(interner.len(), 3);
+    /// assert_eq!(interner.lookup_handle("c").unwrap(), Some(2));
+    /// ```
+    #[track_caller]
+    pub fn from_refs<'a, Q, I>(hasher: S, iter: I) -> Result<Self, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized + 'a,
+        I: IntoIterator<Item = &'a Q>,
+    {
+        let mut interner = Self::new(hasher);
+        for item in iter {
+            interner.intern_ref(item)?;
+        }
+        Ok(interner)
+    }
+
+    /// Adds a Bloom filter that answers most lookups of absent values
+    /// without probing the hash index, using `bits_per_entry` bits per item.
+    ///
+    /// This speeds up workloads in which most lookups and intern calls are
+    /// for new values. The filter only has false positives: an absent value
+    /// may still be looked up in the index, but a present value is never
+    /// reported absent. With 10 bits per item, about 1% of absent values pass
+    /// the filter. Passing `0` removes the filter.
+    ///
+    /// The filter is sized for twice the current capacity and rebuilt from
+    /// all items when the interner outgrows it or items are removed. Without
+    /// a filter, lookups go straight to the index, as before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner =
+    ///     Interner::<String, RandomState>::new(RandomState::new()).with_negative_filter(10);
+    /// let h = interner.intern_ref("hello").unwrap();
+    /// assert_eq!(interner.lookup_handle("hello").unwrap(), Some(h));
+    /// assert_eq!(interner.lookup_handle("world").unwrap(), None);
+    /// ```
+    #[must_use]
+    pub fn with_negative_filter(mut self, bits_per_entry: usize) -> Self {
+        self.items.set_negative_filter(bits_per_entry);
+        self
+    }
+
+    /// Returns the bits per item of the negative lookup filter, or `None` if
+    /// there is none; see [`with_negative_filter`](Self::with_negative_filter).
+    #[must_use]
+    #[inline]
+    pub fn negative_filter_bits(&self) -> Option<usize> {
+        self.items.negative_filter_bits()
+    }
+
+    /// Makes the hash index grow incrementally, so that no single insertion
+    /// rehashes every item.
+    ///
+    /// Normally, an insertion that outgrows the index rehashes all items into
+    /// a larger one at once, an O(n) pause that can blow a latency budget.
+    /// With incremental growth, the larger index is allocated right away, but
+    /// the items are moved over a few at a time by each following insertion;
+    /// until all are moved, lookups consult both indexes. Call
+    /// [`finish_rehash`](Self::finish_rehash) to pay the rest at a convenient
+    /// time.
+    ///
+    /// Growing the values themselves still reallocates and copies them, which
+    /// is much cheaper than rehashing; [`reserve`](Self::reserve) up front to
+    /// avoid that too. Passing `false` switches back, finishing any rehash in
+    /// progress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner =
+    ///     Interner::<u32, RandomState>::new(RandomState::new()).with_incremental_rehash(true);
+    /// for i in 0..1000 {
+    ///     interner.intern_owned(i).unwrap();
+    /// }
+    /// interner.finish_rehash();
+    /// assert!(!interner.rehash_in_progress());
+    /// assert_eq!(interner.lookup_handle(&500).unwrap(), Some(500));
+    /// ```
+    #[must_use]
+    pub fn with_incremental_rehash(mut self, enabled: bool) -> Self {
+        self.items.set_incremental(enabled);
+        self
+    }
+
+    /// Returns `true` if an incremental rehash has items left to move; see
+    /// [`with_incremental_rehash`](Self::with_incremental_rehash).
+    #[must_use]
+    #[inline]
+    pub fn rehash_in_progress(&self) -> bool {
+        self.items.rehash_remaining() > 0
+    }
+
+    /// Moves all remaining items of an incremental rehash into the new
+    /// index at once. Does nothing if no rehash is in progress.
+    pub fn finish_rehash(&mut self) {
+        self.items.finish_rehash();
+    }
+
+    /// Returns the policy used to grow the storage.
+    #[must_use]
+    #[inline]
+    pub const fn growth_policy(&self) -> GrowthPolicy {
+        self.growth
+    }
+
+    /// Returns the maximum number of unique items this interner will store.
+    ///
+    /// This is `usize::MAX` unless the interner was created with
+    /// [`new_with_limit`](Self::new_with_limit).
+    #[must_use]
+    #[inline]
+    pub const fn limit(&self) -> usize {
+        self.max_items
+    }
+
+    /// Returns the handle returned for new items once the handle space is
+    /// exhausted.
+    ///
+    /// This is `None`, meaning overflow is an error, unless one was set with
+    /// [`with_overflow_handle`](Self::with_overflow_handle) or
+    /// [`new_with_overflow_handle`](Self::new_with_overflow_handle).
+    #[must_use]
+    #[inline]
+    pub const fn overflow_handle(&self) -> Option<H> {
+        self.overflow_handle
+    }
+
+    /// Interns an owned value, taking ownership.
+    ///
+    /// If the value already exists in the interner, its handle is returned.
+    /// Otherwise, the value is stored and a new handle is created and returned.
+    ///
+    /// This is the most efficient method when you already have an owned value,
+    /// as it avoids any potential clones.
+    /// The item is hashed only once, whether or not it was already present.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted,
+    /// or `InternerError::LimitReached` if the configured item limit is reached.
+    /// An interner created with
+    /// [`new_with_overflow_handle`](Self::new_with_overflow_handle) returns its
+    /// fallback handle instead of `InternerError::Overflow`.
+    #[track_caller]
+    pub fn intern_owned(&mut self, item: T) -> Result<H, InternerError> {
+        self.track_handle(|this| {
+            // Check for overflow *before* inserting, so a failure leaves the set
+            // untouched. Without room for a new handle, only a lookup can succeed.
+            let handle = match this.next_handle() {
+                Ok(handle) => handle,
+                Err(err) => {
+                    let idx = this.items.get_index_of(&item).ok_or(err)?;
+                    this.record_hit(idx);
+                    return Self::idx_to_handle(idx);
+                }
+            };
+
+            // A linear reservation made for a hit would be wasted, so probe first
+            // when the storage is full. Doubling growth is left to `insert_full`.
+            if this.growth != GrowthPolicy::Doubling && this.items.len() == this.items.capacity() {
+                let hash = match this.items.find_or_hash(&item) {
+                    Ok(idx) => {
+                        this.record_hit(idx);
+                        return Self::idx_to_handle(idx);
+                    }
+                    Err(hash) => hash,
+                };
+                this.insert_hashed(hash, item);
+                this.record_miss();
+                return Ok(handle);
+            }
+
+            // Looks up and inserts with a single hash of `item`.
+            match this.items.insert_full(item) {
+                (idx, true) => {
+                    this.check_inserted(idx);
+                    this.record_miss();
+                    Ok(handle)
+                }
+                (idx, false) => {
+                    this.record_hit(idx);
+                    Self::idx_to_handle(idx)
+                }
+            }
+        })
+    }
+
+    /// Interns a borrowed value by reference.
+    ///
+    /// If a value equal to `item` already exists in the interner, its handle is
+    /// returned without any allocation. If the value is not present, `item` is
+    /// cloned, the clone is stored, and a new handle is returned.
+    ///
+    /// This method requires `T: FromRef` and is ideal for cases where you have
+    /// a reference to a value or slice and want to avoid cloning or boxing if
+    /// it's already been interned.
+    ///
+    /// The item is hashed only once, and a miss reuses that hash to insert the
+    /// clone.
+    ///
+    /// The clone is made with [`FromRef`], not `ToOwned`, so the stored type
+    /// need not be the borrowed type's `ToOwned::Owned`: a `Box<[u8]>`
+    /// interner accepts `&[u8]`, even though `[u8]` converts to `Vec<u8>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new item is inserted and the
+    /// interner's handle capacity is exhausted, or `InternerError::LimitReached`
+    /// if the configured item limit is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<Box<[u8]>, RandomState>::new(RandomState::new());
+    /// let key: &[u8] = b"\x00binary\xff";
+    /// let h = interner.intern_ref(key).unwrap();
+    /// assert_eq!(interner.intern_ref(&key.to_vec()[..]).unwrap(), h);
+    /// assert_eq!(interner.resolve(h).map(|b| &**b), Some(key));
+    /// ```
+    #[track_caller]
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.track_handle(|this| {
+            let hash = match this.items.find_or_hash(item) {
+                Ok(idx) => {
+                    this.record_hit(idx);
+                    return Self::idx_to_handle(idx);
+                }
+                Err(hash) => hash,
+            };
+            let h = this.next_handle()?;
+            this.insert_hashed(hash, T::from_ref(item));
+            this.record_miss();
+            Ok(h)
+        })
+    }
+
+    /// Like [`intern_ref`](Self::intern_ref), but also returns whether
+    /// `item` was cloned and inserted (`true`) or already present (`false`).
+    ///
+    /// A `true` marks the calls that allocated for the clone (and possibly
+    /// for growing the storage), which is what allocation budgets need to
+    /// count; a `false` hit allocated nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `intern_ref`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let (h, inserted) = interner.intern_ref_tracked("hello").unwrap();
+    /// assert!(inserted);
+    /// assert_eq!(interner.intern_ref_tracked("hello").unwrap(), (h, false));
+    /// ```
+    #[track_caller]
+    pub fn intern_ref_tracked<Q>(&mut self, item: &Q) -> Result<(H, bool), InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let len = self.len();
+        let h = self.intern_ref(item)?;
+        Ok((h, self.len() > len))
+    }
+
+    /// Like [`intern_owned`](Self::intern_owned), but reserves room for a
+    /// new item fallibly, so allocation failure is reported instead of
+    /// aborting the process.
     ///
-    /// This is useful when the number of unique items to be interned is known
-    /// in advance. Pre-allocating capacity can prevent multiple reallocations
-    /// of the internal hash map, improving performance.
-    #[must_use]
-    pub fn with_capacity(hasher: S, capacity: usize) -> Self {
-        Self {
-            items: IndexSet::with_capacity_and_hasher(capacity, hasher),
-            _handle: PhantomData,
-        }
+    /// # Errors
+    ///
+    /// Returns the errors of `intern_owned`, or
+    /// `InternerError::AllocationFailed` if the storage cannot grow. The
+    /// interner is left unchanged on error.
+    #[track_caller]
+    pub fn try_intern_owned(&mut self, item: T) -> Result<H, InternerError> {
+        self.track_handle(|this| {
+            let hash = match this.items.find_or_hash(&item) {
+                Ok(idx) => {
+                    this.record_hit(idx);
+                    return Self::idx_to_handle(idx);
+                }
+                Err(hash) => hash,
+            };
+            let h = this.next_handle()?;
+            this.try_reserve(1)?;
+            this.insert_hashed(hash, item);
+            this.record_miss();
+            Ok(h)
+        })
     }
 
-    /// Interns an owned value, taking ownership.
-    ///
-    /// If the value already exists in the interner, its handle is returned.
-    /// Otherwise, the value is stored and a new handle is created and returned.
+    /// Like [`intern_ref`](Self::intern_ref), but reserves room for a new
+    /// item fallibly; see [`try_intern_owned`](Self::try_intern_owned).
     ///
-    /// This is the most efficient method when you already have an owned value,
-    /// as it avoids any potential clones.
+    /// Only the interner's own storage is reserved fallibly. Cloning `item`
+    /// into a new `T` may still abort on allocation failure.
     ///
     /// # Errors
     ///
-    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
-    pub fn intern_owned(&mut self, item: T) -> Result<H, InternerError> {
-        // Look up the item first. The `Borrow<T>` trait bound on `get_index_of`
-        // allows us to look up an owned `T` using a reference.
-        if let Some(idx) = self.items.get_index_of(&item) {
-            return Self::idx_to_handle(idx);
-        }
-
-        // If the item is new, check for overflow *before* inserting to
-        // maintain a consistent state if the operation fails.
-        let handle = Self::idx_to_handle(self.items.len())?;
-        self.items.insert(item);
-        Ok(handle)
+    /// Returns the errors of `intern_ref`, or
+    /// `InternerError::AllocationFailed` if the storage cannot grow. The
+    /// interner is left unchanged on error.
+    #[track_caller]
+    pub fn try_intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.track_handle(|this| {
+            let hash = match this.items.find_or_hash(item) {
+                Ok(idx) => {
+                    this.record_hit(idx);
+                    return Self::idx_to_handle(idx);
+                }
+                Err(hash) => hash,
+            };
+            let h = this.next_handle()?;
+            this.try_reserve(1)?;
+            this.insert_hashed(hash, T::from_ref(item));
+            this.record_miss();
+            Ok(h)
+        })
     }
 
-    /// Interns a borrowed value by reference.
+    /// Interns a value whose hash was computed by the caller, creating the
+    /// value with `make` only if `key` is not present yet.
     ///
-    /// If a value equal to `item` already exists in the interner, its handle is
-    /// returned without any allocation. If the value is not present, `item` is
-    /// cloned, the clone is stored, and a new handle is returned.
+    /// `hash` is used verbatim and the interner's hasher is not invoked, so
+    /// it must be the hash of `key` under the interner's `BuildHasher`, e.g.
+    /// computed with [`BuildHasher::hash_one`] on a clone of it. `make` must
+    /// return a value equal to `key`.
     ///
-    /// This method requires `T: FromRef` and is ideal for cases where you have
-    /// a reference to a value or slice and want to avoid cloning or boxing if
-    /// it's already been interned.
+    /// A wrong hash is not undefined behavior, but the value ends up where
+    /// lookups by the correct hash never find it, so it is missed and can be
+    /// interned twice. Interners created with
+    /// [`new_checked`](Self::new_checked) assert that `hash` is correct in
+    /// builds with debug assertions.
+    ///
+    /// This skips hashing keys that are expensive to hash (e.g. long strings
+    /// sharing a prefix) when their hash is already known, say because it was
+    /// cached next to the key. Present keys still cost one full comparison.
+    /// If the hash is not known in advance, a cheaper `Hash` implementation
+    /// on the key type (e.g. hashing only the length and a prefix) is the
+    /// better fix, as every lookup benefits.
     ///
     /// # Errors
     ///
     /// Returns `InternerError::Overflow` if a new item is inserted and the
-    /// interner's handle capacity is exhausted.
-    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    /// interner's handle capacity is exhausted, or `InternerError::LimitReached`
+    /// if the configured item limit is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{collections::hash_map::RandomState, hash::BuildHasher};
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let hasher = RandomState::new();
+    /// let hash = hasher.hash_one("token");
+    /// let mut interner = Interner::<String, RandomState>::new(hasher);
+    ///
+    /// let h = interner
+    ///     .intern_ref_prehashed(hash, "token", || "token".to_string())
+    ///     .unwrap();
+    /// assert_eq!(interner.intern_ref("token").unwrap(), h);
+    /// assert_eq!(interner.lookup_prehashed(hash, "token").unwrap(), Some(h));
+    /// ```
+    #[track_caller]
+    pub fn intern_ref_prehashed<Q>(
+        &mut self,
+        hash: u64,
+        key: &Q,
+        make: impl FnOnce() -> T,
+    ) -> Result<H, InternerError>
     where
-        T: Borrow<Q> + FromRef<Q>,
+        T: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        if let Some(idx) = self.items.get_index_of(item) {
-            return Self::idx_to_handle(idx);
-        }
-        let h = Self::idx_to_handle(self.items.len())?;
-        self.items.insert(T::from_ref(item));
-        Ok(h)
+        self.check_hash(hash, key);
+        self.track_handle(|this| {
+            if let Some(idx) = this.items.find_hashed(hash, key) {
+                this.record_hit(idx);
+                return Self::idx_to_handle(idx);
+            }
+            let h = this.next_handle()?;
+            this.insert_hashed(hash, make());
+            this.record_miss();
+            Ok(h)
+        })
     }
 
     /// Interns a value wrapped in a `Cow` (Clone-on-Write).
@@ -242,33 +1219,118 @@ where
     /// # Errors
     ///
     /// Returns `InternerError::Overflow` if a new item is inserted and the
-    /// interner's handle capacity is exhausted.
+    /// interner's handle capacity is exhausted, or `InternerError::LimitReached`
+    /// if the configured item limit is reached.
+    #[track_caller]
     pub fn intern_cow<Q>(&mut self, item: Cow<'_, Q>) -> Result<H, InternerError>
     where
         T: Borrow<Q> + Clone,
         Q: ToOwned<Owned = T> + Hash + Eq + ?Sized,
     {
-        if let Some(idx) = self.items.get_index_of(item.as_ref()) {
-            return Self::idx_to_handle(idx);
-        }
-        let h = Self::idx_to_handle(self.items.len())?;
-        self.items.insert(item.into_owned());
-        Ok(h)
+        self.track_handle(|this| {
+            let item = match item {
+                Cow::Owned(item) => return this.intern_owned(item),
+                Cow::Borrowed(item) => item,
+            };
+            let hash = match this.items.find_or_hash(item) {
+                Ok(idx) => {
+                    this.record_hit(idx);
+                    return Self::idx_to_handle(idx);
+                }
+                Err(hash) => hash,
+            };
+            let h = this.next_handle()?;
+            this.insert_hashed(hash, item.to_owned());
+            this.record_miss();
+            Ok(h)
+        })
     }
 
     /// Returns the existing handle for `key` or inserts a newly constructed value.
+    #[track_caller]
     pub fn intern_ref_or_insert_with<Q, F>(&mut self, key: &Q, make: F) -> Result<H, InternerError>
     where
         T: Borrow<Q> + Clone,
         Q: Hash + Eq + ?Sized,
         F: FnOnce() -> T,
     {
-        if let Some(idx) = self.items.get_index_of(key) {
-            return Self::idx_to_handle(idx);
+        self.track_handle(|this| {
+            if let Some(idx) = this.items.get_index_of(key) {
+                this.record_hit(idx);
+                return Self::idx_to_handle(idx);
+            }
+            let h = this.next_handle()?;
+            this.insert_new(make());
+            this.record_miss();
+            Ok(h)
+        })
+    }
+
+    /// Interns every item of a slice of `Copy` values, returning their handles
+    /// in order.
+    ///
+    /// Capacity for `items.len()` new items is reserved once up front, and each
+    /// item is interned by copy. Repeated items map to the same handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted,
+    /// or `InternerError::LimitReached` if the configured item limit is reached.
+    /// Items before the failing one remain interned.
+    #[track_caller]
+    pub fn intern_copied(&mut self, items: &[T]) -> Result<Vec<H>, InternerError>
+    where
+        T: Copy,
+    {
+        self.track_overflow(|this| {
+            this.reserve(items.len());
+            let mut handles = Vec::with_capacity(items.len());
+            for &item in items {
+                handles.push(this.intern_owned(item)?);
+            }
+            Ok(handles)
+        })
+    }
+
+    /// Interns every item, requiring all of them to be new, and returns their
+    /// handles in order.
+    ///
+    /// This validates uniqueness while interning, e.g. for a set of distinct
+    /// configuration keys, instead of silently deduplicating.
+    ///
+    /// # Errors
+    ///
+    /// - `DuplicateError::Duplicate` with the offending item if it was already
+    ///   present, including when it occurs twice in `items`.
+    /// - `DuplicateError::Interner` if interning fails, e.g. with
+    ///   `InternerError::Overflow`.
+    ///
+    /// Items before the failing one remain interned.
+    #[track_caller]
+    pub fn intern_owned_all_distinct<I>(&mut self, items: I) -> Result<Vec<H>, DuplicateError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let result = items
+            .into_iter()
+            .map(|item| {
+                let hash = match self.items.find_or_hash(&item) {
+                    Ok(idx) => {
+                        self.record_hit(idx);
+                        return Err(DuplicateError::Duplicate(item));
+                    }
+                    Err(hash) => hash,
+                };
+                let handle = self.next_handle()?;
+                self.insert_hashed(hash, item);
+                self.record_miss();
+                Ok(handle)
+            })
+            .collect();
+        if let Err(DuplicateError::Interner(InternerError::Overflow)) = result {
+            self.record_overflow();
         }
-        let h = Self::idx_to_handle(self.items.len())?;
-        self.items.insert(make());
-        Ok(h)
+        result
     }
 
     /// Returns the handle for `item` if present, without inserting or cloning.
@@ -278,9 +1340,50 @@ where
         T: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        self.items
-            .get_index_of(item)
-            .map_or(Ok(None), |idx| Ok(Some(Self::idx_to_handle(idx)?)))
+        let idx = self.items.get_index_of(item);
+        self.record_lookup(idx.is_some());
+        idx.map_or(Ok(None), |idx| Ok(Some(Self::idx_to_handle(idx)?)))
+    }
+
+    /// Like [`lookup_handle`](Self::lookup_handle), but uses a hash computed
+    /// by the caller instead of invoking the interner's hasher.
+    ///
+    /// See [`intern_ref_prehashed`](Self::intern_ref_prehashed) for the
+    /// requirements on `hash`; a wrong one makes the lookup miss.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the item's index cannot be
+    /// represented by the handle type `H`.
+    pub fn lookup_prehashed<Q>(&self, hash: u64, key: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.check_hash(hash, key);
+        let idx = self.items.find_hashed(hash, key);
+        self.record_lookup(idx.is_some());
+        idx.map_or(Ok(None), |idx| Ok(Some(Self::idx_to_handle(idx)?)))
+    }
+
+    /// Returns the handle for `item`, treating its absence as an error.
+    ///
+    /// This is [`lookup_handle`](Self::lookup_handle) for phases that must
+    /// only reference already interned items: instead of silently interning or
+    /// returning `None`, a missing item is reported as an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MissingItemError::Missing` if `item` was never interned, or
+    /// `MissingItemError::Interner` with `InternerError::Overflow` if the
+    /// handle cannot be represented by `H`.
+    #[inline]
+    pub fn resolve_existing_handle<Q>(&self, item: &Q) -> Result<H, MissingItemError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.lookup_handle(item)?.ok_or(MissingItemError::Missing)
     }
 
     /// Returns true if an equal item is present.
@@ -290,7 +1393,23 @@ where
         T: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        self.items.contains(item)
+        let found = self.items.contains(item);
+        self.record_lookup(found);
+        found
+    }
+
+    /// Returns true if `handle` currently resolves to an item equal to `value`.
+    ///
+    /// Invalid handles return false. This is convenient for consistency
+    /// assertions, e.g. `debug_assert!(interner.handle_matches(h, "x"))`.
+    #[inline]
+    pub fn handle_matches<Q>(&self, handle: H, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.resolve(handle)
+            .is_some_and(|item| item.borrow() == value)
     }
 
     /// Removes a value from the interner and returns the Handle and the Value.
@@ -343,8 +1462,9 @@ where
         // shift_remove_full returns (index, value)
         // We use shift_remove to preserve the relative order of remaining items.
         let (idx, val) = self.items.shift_remove_full(item)?;
+        self.record_removed();
 
-        // The index returned by IndexSet is guaranteed to fit in usize.
+        // The returned index is guaranteed to fit in usize.
         // We convert it back to H to return to the user.
         // We suppress the error here because if it was in the map, it had a valid handle.
         let handle = H::try_from(idx).ok()?;
@@ -369,7 +1489,9 @@ where
     /// See [`repair_handles`](Self::repair_handles) for a helper that automates this.
     pub fn remove_handle(&mut self, handle: H) -> Option<T> {
         let idx = usize::try_from(handle).ok()?;
-        self.items.shift_remove_index(idx)
+        let value = self.items.shift_remove_index(idx)?;
+        self.record_removed();
+        Some(value)
     }
 
     /// A helper to update a collection of handles after a removal.
@@ -416,16 +1538,169 @@ where
         }
     }
 
+    /// Shortens the interner to its first `len` items, dropping the rest,
+    /// like `Vec::truncate`.
+    ///
+    /// Handles below `len` stay valid; handles from `len` onwards become
+    /// invalid, and the next new item is assigned handle `len` again. Does
+    /// nothing if `len` is not less than the current length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let a = interner.intern_ref("a").unwrap();
+    /// let b = interner.intern_ref("b").unwrap();
+    ///
+    /// interner.truncate(1);
+    /// assert_eq!(interner.resolve(a).map(String::as_str), Some("a"));
+    /// assert_eq!(interner.resolve(b), None);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.items.len() {
+            self.items.truncate(len);
+            self.record_removed();
+        }
+    }
+
+    /// Splits the interner at `at`, moving every item with an index of `at`
+    /// or more into a new interner, which is returned.
+    ///
+    /// `self` keeps the items before `at` and their handles. The returned
+    /// interner shares the hasher, limit, growth policy, checked mode, and
+    /// overflow handle of `self`. With the `stats` feature, its statistics
+    /// start from zero, but track deduplication savings if `self` does. It
+    /// reports no metrics until `with_metrics` is called on it.
+    ///
+    /// # Handle Rebasing
+    ///
+    /// Items in the returned interner are reindexed from zero: the item that
+    /// was behind handle `h` is now behind handle `h - at`. Handles below `at`
+    /// are unaffected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than [`len`](Self::len).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let prelude = interner.intern_ref("print").unwrap();
+    /// let at = interner.intern_ref("main").unwrap();
+    /// let local = interner.intern_ref("x").unwrap();
+    ///
+    /// let file = interner.split_off(at);
+    /// assert_eq!(interner.len(), 1);
+    /// assert_eq!(interner.resolve(prelude).map(String::as_str), Some("print"));
+    /// assert_eq!(file.resolve(local - at).map(String::as_str), Some("x"));
+    /// ```
+    #[must_use]
+    pub fn split_off(&mut self, at: H) -> Self
+    where
+        S: Clone,
+    {
+        // An unrepresentable index is out of bounds, so `split_off` panics.
+        let at = usize::try_from(at).unwrap_or(usize::MAX);
+        let items = self.items.split_off(at);
+        self.record_removed();
+        let mut rest = Self::from_store(items);
+        rest.max_items = self.max_items;
+        rest.growth = self.growth;
+        rest.checked = self.checked;
+        rest.overflow_handle = self.overflow_handle;
+        #[cfg(feature = "stats")]
+        {
+            rest.stats = self.stats.fresh();
+        }
+        rest
+    }
+
     /// Current capacity, in number of items.
     #[inline]
     pub fn capacity(&self) -> usize {
         self.items.capacity()
     }
 
+    /// Returns how many more unique items fit in the handle space of `H`
+    /// before interning fails with `InternerError::Overflow`, or `None` if
+    /// `H` can represent more handles than fit in a `usize`.
+    ///
+    /// Unlike [`capacity`](Self::capacity), this is independent of the
+    /// allocation. An item limit from
+    /// [`new_with_limit`](Self::new_with_limit) is not taken into account.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<u32, RandomState, u8>::new(RandomState::new());
+    /// interner.intern_owned(7).unwrap();
+    /// assert_eq!(interner.remaining_handle_capacity(), Some(255));
+    /// ```
+    #[must_use]
+    pub fn remaining_handle_capacity(&self) -> Option<usize> {
+        Self::handle_space().map(|handles| handles.saturating_sub(self.items.len()))
+    }
+
     /// Reserves capacity for at least `additional` more items.
+    ///
+    /// The reservation is rounded up according to the
+    /// [growth policy](Self::growth_policy).
     #[inline]
     pub fn reserve(&mut self, additional: usize) {
-        self.items.reserve(additional);
+        self.growth.reserve(&mut self.items, additional);
+    }
+
+    /// Like [`reserve`](Self::reserve), but reports allocation failures
+    /// instead of aborting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::AllocationFailed` if the requested capacity
+    /// overflows `usize` or cannot be allocated. The items are left
+    /// unchanged.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), InternerError> {
+        self.growth.try_reserve(&mut self.items, additional)
+    }
+
+    /// Reserves capacity for exactly `additional` more items, ignoring the
+    /// growth policy. Use this when the final size is known up front.
+    ///
+    /// The hash index still rounds up to its own bucket sizes.
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.items.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for every handle `H` can represent, so that filling
+    /// the handle space never reallocates.
+    ///
+    /// The reservation is exact and ignores the growth policy. If an item
+    /// limit is configured, only up to the limit is reserved.
+    ///
+    /// # Errors
+    ///
+    /// - `InternerError::Overflow` if `H` can represent more handles than fit
+    ///   in a `usize`.
+    /// - `InternerError::AllocationFailed` if the capacity cannot be
+    ///   allocated. The interner is left unchanged.
+    pub fn reserve_full_handle_space(&mut self) -> Result<(), InternerError> {
+        let handles = Self::handle_space().ok_or(InternerError::Overflow)?;
+        let additional = handles.min(self.max_items).saturating_sub(self.items.len());
+        self.items.try_reserve_exact(additional)
     }
 
     /// Shrinks capacity to fit the current length.
@@ -438,6 +1713,181 @@ where
     #[inline]
     pub fn clear(&mut self) {
         self.items.clear();
+        self.record_removed();
+    }
+
+    /// Internal helper returning the handle for the next new item.
+    ///
+    /// Checks the configured item limit before the handle space.
+    #[inline]
+    fn next_handle(&self) -> Result<H, InternerError> {
+        let len = self.items.len();
+        if len >= self.max_items {
+            return Err(InternerError::LimitReached);
+        }
+        Self::idx_to_handle(len)
+    }
+
+    /// Internal helper returning how many handles `H` can represent, or
+    /// `None` if they do not fit in a `usize`.
+    fn handle_space() -> Option<usize> {
+        if H::try_from(usize::MAX).is_ok() {
+            return None;
+        }
+        // Binary search for the largest index `H` can represent.
+        let (mut lo, mut hi) = (0, usize::MAX);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if H::try_from(mid).is_ok() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Some(if H::try_from(lo).is_ok() { lo + 1 } else { 0 })
+    }
+
+    /// Internal helper storing an item known to be new, growing the storage
+    /// according to the growth policy first.
+    #[inline]
+    fn insert_new(&mut self, item: T) {
+        self.growth.reserve(&mut self.items, 1);
+        let idx = self.items.push_new(item);
+        self.check_inserted(idx);
+    }
+
+    /// Like [`insert_new`](Self::insert_new), but reuses the `hash` of `item`
+    /// computed by the lookup that missed.
+    #[inline]
+    fn insert_hashed(&mut self, hash: u64, item: T) {
+        self.growth.reserve(&mut self.items, 1);
+        let idx = self.items.push_with_hash(hash, item);
+        self.check_inserted(idx);
+    }
+
+    /// Internal helper verifying a caller-provided hash of `key`, for
+    /// interners created with `new_checked`.
+    #[inline]
+    fn check_hash<Q>(&self, hash: u64, key: &Q)
+    where
+        Q: Hash + ?Sized,
+    {
+        if cfg!(debug_assertions) && self.checked {
+            assert!(
+                self.items.hash(key) == hash,
+                "the provided hash does not match the interner's hash of the key"
+            );
+        }
+    }
+
+    /// Internal helper verifying that the item just inserted at `idx` can be
+    /// looked up again, for interners created with `new_checked`.
+    #[inline]
+    fn check_inserted(&self, idx: usize) {
+        if cfg!(debug_assertions) && self.checked {
+            let found = self
+                .items
+                .get_index(idx)
+                .and_then(|item| self.items.get_index_of(item));
+            assert!(
+                found == Some(idx),
+                "interned item at index {idx} cannot be looked up again: \
+                 the `Hash` and `Eq` implementations of the item type are inconsistent"
+            );
+        }
+    }
+
+    /// Internal helper counting an intern call that found the item at `idx`,
+    /// for `stats` and metrics. A no-op without those features.
+    #[inline]
+    fn record_hit(&mut self, idx: usize) {
+        #[cfg(feature = "stats")]
+        if let Some(item) = self.items.get_index(idx) {
+            self.stats.add_hit(item);
+        }
+        #[cfg(not(feature = "stats"))]
+        let _ = idx;
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.record_hit();
+        }
+    }
+
+    /// Internal helper counting an intern call that inserted a new item, for
+    /// `stats` and metrics. The new item must be the last one. A no-op
+    /// without those features.
+    #[inline]
+    fn record_miss(&self) {
+        #[cfg(feature = "stats")]
+        self.stats.add_miss();
+        #[cfg(feature = "metrics")]
+        if let Some((metrics, item)) = self.metrics.as_ref().zip(self.items.as_slice().last()) {
+            metrics.record_insert(item, self.items.len());
+        }
+    }
+
+    /// Internal helper updating `stats` and metrics after items were
+    /// removed. A no-op without those features.
+    #[inline]
+    fn record_removed(&mut self) {
+        #[cfg(feature = "stats")]
+        if self.items.is_empty() {
+            self.stats.forget_hit_bytes();
+        }
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.refresh(self.items.as_slice());
+        }
+    }
+
+    /// Internal helper counting a lookup for `stats`. A no-op without the
+    /// `stats` feature.
+    #[inline]
+    fn record_lookup(&self, found: bool) {
+        #[cfg(feature = "stats")]
+        self.stats.add_lookup(found);
+        #[cfg(not(feature = "stats"))]
+        let _ = found;
+    }
+
+    /// Internal helper running an intern operation and remembering the
+    /// caller's location if it fails with `InternerError::Overflow`.
+    #[track_caller]
+    #[inline]
+    fn track_overflow<R>(
+        &mut self,
+        op: impl FnOnce(&mut Self) -> Result<R, InternerError>,
+    ) -> Result<R, InternerError> {
+        let result = op(self);
+        if let Err(InternerError::Overflow) = result {
+            self.record_overflow();
+        }
+        result
+    }
+
+    /// Internal helper running an intern operation like `track_overflow`,
+    /// returning the fallback handle, if any, instead of
+    /// `InternerError::Overflow`.
+    #[track_caller]
+    #[inline]
+    fn track_handle(
+        &mut self,
+        op: impl FnOnce(&mut Self) -> Result<H, InternerError>,
+    ) -> Result<H, InternerError> {
+        match (self.track_overflow(op), self.overflow_handle) {
+            (Err(InternerError::Overflow), Some(fallback)) => Ok(fallback),
+            (result, _) => result,
+        }
+    }
+
+    /// Internal helper storing the caller's location for
+    /// `last_overflow_location`. A no-op in release builds.
+    #[track_caller]
+    #[inline]
+    fn record_overflow(&mut self) {
+        if cfg!(debug_assertions) {
+            self.overflow_location = Some(Location::caller());
+        }
     }
 
     /// Internal helper to safely convert a `usize` index to a handle `H`.
@@ -459,6 +1909,105 @@ where
         self.items.get_index(idx)
     }
 
+    /// Resolves a handle to a `Cow::Borrowed` of the interned value.
+    ///
+    /// The value always stays in the interner; the `Cow` only makes the result
+    /// fit APIs expecting a `Cow<T>`, and callers needing ownership can use
+    /// `into_owned`, which clones. Returns `None` if the handle is invalid.
+    #[must_use]
+    #[inline]
+    pub fn resolve_cow(&self, handle: H) -> Option<Cow<'_, T>>
+    where
+        T: Clone,
+    {
+        self.resolve(handle).map(Cow::Borrowed)
+    }
+
+    /// Returns `true` if both handles resolve to values pointing at the same
+    /// allocation, e.g. the same `Arc<str>`.
+    ///
+    /// For pointer types like `Rc` and `Arc`, the interner stores one pointer
+    /// per distinct value, so equal handles always share an allocation. This checks that directly, which is useful to
+    /// verify that storage really is shared. Returns `false` if either handle
+    /// is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{collections::hash_map::RandomState, sync::Arc};
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<Arc<str>, RandomState>::new(RandomState::new());
+    /// let a = interner.intern_ref("shared").unwrap();
+    /// let b = interner.intern_ref("shared").unwrap();
+    /// assert!(interner.resolve_ptr_eq(a, b));
+    /// assert!(Arc::ptr_eq(
+    ///     interner.resolve(a).unwrap(),
+    ///     interner.resolve(b).unwrap()
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn resolve_ptr_eq(&self, a: H, b: H) -> bool
+    where
+        T: Deref,
+    {
+        match (self.resolve(a), self.resolve(b)) {
+            (Some(a), Some(b)) => core::ptr::eq(&**a, &**b),
+            _ => false,
+        }
+    }
+
+    /// Resolves a handle to a mutable reference to the interned value.
+    ///
+    /// # ⚠️ Hash Invariant
+    ///
+    /// This is intended for types whose `Hash` and `Eq` only depend on part of
+    /// the value (e.g. a key field), so that the rest of the value (the payload)
+    /// can be edited in place.
+    ///
+    /// **Never modify anything that affects `Hash` or `Eq`.** Doing so silently
+    /// corrupts the interner: lookups of the old or new value may fail, and
+    /// equal values may be interned twice under different handles. This is not
+    /// memory-unsafe, but the interner's behavior is unspecified from then on.
+    #[must_use]
+    #[inline]
+    pub fn resolve_mut(&mut self, handle: H) -> Option<&mut T> {
+        let idx: usize = usize::try_from(handle).ok()?;
+        self.items.get_index_mut(idx)
+    }
+
+    /// Resolves several handles to mutable references at once.
+    ///
+    /// Returns `None` if any handle is invalid or two handles are equal, like
+    /// `slice::get_disjoint_mut`.
+    ///
+    /// # ⚠️ Hash Invariant
+    ///
+    /// The same rules as for [`resolve_mut`](Self::resolve_mut) apply: never
+    /// modify anything that affects `Hash` or `Eq`.
+    #[must_use]
+    pub fn get_disjoint_mut<const N: usize>(&mut self, handles: [H; N]) -> Option<[&mut T; N]> {
+        let mut indices = [0; N];
+        for (idx, handle) in indices.iter_mut().zip(handles) {
+            *idx = usize::try_from(handle).ok()?;
+        }
+        self.items.as_mut_slice().get_disjoint_mut(indices).ok()
+    }
+
+    /// Calls `f` with a mutable reference to every item, in insertion order.
+    ///
+    /// # ⚠️ Hash Invariant
+    ///
+    /// The same rules as for [`resolve_mut`](Self::resolve_mut) apply: `f` must
+    /// not modify anything that affects `Hash` or `Eq`.
+    pub fn for_each_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        self.items.as_mut_slice().iter_mut().for_each(f);
+    }
+
     /// Returns the number of unique items currently stored in the interner.
     #[must_use]
     #[inline]
@@ -473,15 +2022,39 @@ where
         self.items.is_empty()
     }
 
+    /// Returns the source location of the last intern call that failed with
+    /// `InternerError::Overflow`.
+    ///
+    /// The `intern_*` methods are `#[track_caller]`, so this points at your
+    /// code rather than at the interner. The location is only recorded in
+    /// debug builds; release builds always return `None`.
+    #[must_use]
+    #[inline]
+    pub const fn last_overflow_location(&self) -> Option<&'static Location<'static>> {
+        self.overflow_location
+    }
+
     /// Iterates over all unique items in insertion order.
     ///
+    /// The iterator is double-ended and exact-sized, so `iter().rev()` yields
+    /// the items newest first.
+    ///
     /// Note: `&Interner` also implements `IntoIterator`, so you can write:
     /// `for item in &interner { /* item: &T */ }`
     #[inline]
-    pub fn iter(&self) -> indexmap::set::Iter<'_, T> {
+    pub fn iter(&self) -> iter::Iter<'_, T> {
         self.items.iter()
     }
 
+    /// Returns all unique items as a slice, in insertion order.
+    ///
+    /// The item behind a handle `h` is at index `h` of the slice.
+    #[must_use]
+    #[inline]
+    pub fn as_values_slice(&self) -> &[T] {
+        self.items.as_slice()
+    }
+
     /// Consumes the interner and returns a vector of all unique items.
     ///
     /// The items in the returned vector are ordered by their first insertion.
@@ -493,7 +2066,91 @@ where
     #[doc(alias = "into_vec")]
     #[must_use]
     pub fn export(self) -> Vec<T> {
-        self.items.into_iter().collect()
+        self.items.into_vec()
+    }
+
+    /// Like [`export`](Self::export), but returns a boxed slice without any
+    /// spare capacity, for items kept for the rest of the program.
+    ///
+    /// If the storage has spare capacity, this reallocates once to drop it.
+    #[must_use]
+    pub fn export_boxed(self) -> Box<[T]> {
+        self.items.into_vec().into_boxed_slice()
+    }
+
+    /// Consumes the interner and returns all unique items sorted by `cmp`,
+    /// together with a remap table from old handles to new positions.
+    ///
+    /// The remap table is indexed by the old handle's index: `remap[old]` is the
+    /// position of that item in the returned vector. This makes it possible to
+    /// rewrite stored handles to match the sorted layout, e.g. for emitting a
+    /// deterministic output file.
+    ///
+    /// The sort is stable, so items comparing equal keep their insertion order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let b = interner.intern_ref("b").unwrap();
+    /// let a = interner.intern_ref("a").unwrap();
+    ///
+    /// let (items, remap) = interner.export_sorted_by(|x, y| x.cmp(y));
+    /// assert_eq!(items, ["a", "b"]);
+    /// assert_eq!(remap[b as usize], 1);
+    /// assert_eq!(remap[a as usize], 0);
+    /// ```
+    #[must_use]
+    pub fn export_sorted_by<F>(self, mut cmp: F) -> (Vec<T>, Vec<H>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut entries: Vec<(usize, T)> = self.items.into_iter().enumerate().collect();
+        entries.sort_by(|(_, a), (_, b)| cmp(a, b));
+
+        let mut positions = alloc::vec![0usize; entries.len()];
+        for (new, (old, _)) in entries.iter().enumerate() {
+            positions[*old] = new;
+        }
+
+        // Every position is smaller than the number of items, and each of those
+        // indices was a valid handle when its item was interned, so the
+        // conversion cannot fail.
+        let remap = positions
+            .into_iter()
+            .filter_map(|pos| H::try_from(pos).ok())
+            .collect();
+        let items = entries.into_iter().map(|(_, item)| item).collect();
+
+        (items, remap)
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + Ord,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Returns the smallest interned value, or `None` if the interner is empty.
+    ///
+    /// This is an O(n) scan over all items.
+    #[must_use]
+    pub fn min_item(&self) -> Option<&T> {
+        self.items.iter().min()
+    }
+
+    /// Returns the largest interned value, or `None` if the interner is empty.
+    ///
+    /// This is an O(n) scan over all items.
+    #[must_use]
+    pub fn max_item(&self) -> Option<&T> {
+        self.items.iter().max()
     }
 }
 
@@ -505,7 +2162,7 @@ where
     usize: TryFrom<H>,
 {
     type Item = &'a T;
-    type IntoIter = indexmap::set::Iter<'a, T>;
+    type IntoIter = iter::Iter<'a, T>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -521,7 +2178,7 @@ where
     usize: TryFrom<H>,
 {
     type Item = T;
-    type IntoIter = indexmap::set::IntoIter<T>;
+    type IntoIter = iter::IntoIter<T>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -536,6 +2193,23 @@ where
     H: Copy + TryFrom<usize>,
     usize: TryFrom<H>,
 {
+    /// Returns the length in bytes of the arena [`export_arena`](Self::export_arena)
+    /// would produce, without consuming the interner.
+    ///
+    /// This is the sum of the byte lengths of all interned strings.
+    #[must_use]
+    pub fn arena_size(&self) -> usize {
+        self.items.iter().map(|s| s.as_ref().len()).sum()
+    }
+
+    /// Returns the number of offsets [`export_arena`](Self::export_arena)
+    /// would produce, i.e. `len() + 1`.
+    #[must_use]
+    #[inline]
+    pub fn arena_entry_count(&self) -> usize {
+        self.items.len() + 1
+    }
+
     /// Consumes the interner and flattens all strings into a single contiguous arena.
     ///
     /// This returns a tuple containing:
@@ -561,28 +2235,283 @@ where
     ///
     /// Returns `InternerError::Overflow` if the total aggregated byte length of the arena
     /// exceeds the maximum value representable by your handle type `H`.
+    ///
+    /// This is [`export_arena_with_offsets`](Self::export_arena_with_offsets) with
+    /// `H` as the offset type.
     pub fn export_arena(self) -> Result<(String, Vec<H>), InternerError> {
+        self.export_arena_with_offsets()
+            .map_err(|_| InternerError::Overflow)
+    }
+
+    /// Consumes the interner and flattens all strings into a single contiguous
+    /// arena, with offsets of type `O`.
+    ///
+    /// The layout is that of [`export_arena`](Self::export_arena). The offsets
+    /// table holds `len() + 1` entries, so its width matters for many short
+    /// strings: `u32` offsets take half the space of `usize` on 64-bit targets
+    /// and suit any arena up to 4 GiB, which makes `u32` the recommended choice.
+    ///
+    /// The arena is sized in one pass and copied in a second one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArenaOverflow`] if the length of the arena does not fit in
+    /// `O`. This is checked before anything is copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::{ArenaOverflow, Interner};
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// interner.intern_ref("hello").unwrap();
+    /// interner.intern_ref("world").unwrap();
+    ///
+    /// let (arena, offsets) = interner.clone().export_arena_with_offsets::<u32>().unwrap();
+    /// assert_eq!((arena.as_str(), offsets.as_slice()), ("helloworld", &[0, 5, 10][..]));
+    ///
+    /// interner.intern_ref(&"!".repeat(300)).unwrap();
+    /// let err = interner.export_arena_with_offsets::<u8>().unwrap_err();
+    /// assert_eq!(err, ArenaOverflow { len: 310 });
+    /// ```
+    pub fn export_arena_with_offsets<O>(self) -> Result<(String, Vec<O>), ArenaOverflow>
+    where
+        O: TryFrom<usize>,
+    {
         // 1. Calculate total bytes needed to perform exactly ONE allocation.
         // We iterate once to count. This is cheap (RAM access).
-        let total_bytes: usize = self.items.iter().map(|s| s.as_ref().len()).sum();
-        let count = self.items.len();
+        let total_bytes = self.arena_size();
+        let overflow = ArenaOverflow { len: total_bytes };
+        // Offsets never exceed the total, so if it fits, they all do.
+        O::try_from(total_bytes).map_err(|_| overflow)?;
 
         // 2. Allocate the arena and the offsets table.
         let mut arena = String::with_capacity(total_bytes);
-        let mut offsets = Vec::with_capacity(count + 1);
+        let mut offsets = Vec::with_capacity(self.arena_entry_count());
 
         // 3. The first offset is always 0.
-        offsets.push(H::try_from(0usize).map_err(|_| InternerError::Overflow)?);
+        offsets.push(O::try_from(0).map_err(|_| overflow)?);
 
         // 4. Fill the arena.
-        // IndexSet iteration preserves insertion order, so handle IDs remain valid.
+        // Iteration preserves insertion order, so handle IDs remain valid.
         for item in self.items {
             arena.push_str(item.as_ref());
-            offsets.push(H::try_from(arena.len()).map_err(|_| InternerError::Overflow)?);
+            offsets.push(O::try_from(arena.len()).map_err(|_| overflow)?);
         }
 
         Ok((arena, offsets))
     }
+
+    /// Flattens all strings into a single contiguous arena, copying them and
+    /// leaving the interner usable.
+    ///
+    /// The layout is that of [`export_arena`](Self::export_arena): the string
+    /// for handle `h` is `arena[offsets[h]..offsets[h + 1]]`. This suits
+    /// periodic checkpoints of a long-lived interner: since items are only
+    /// ever appended, a later export extends an earlier one, whose arena and
+    /// offsets are prefixes of the new ones unless items were removed in
+    /// between. Offsets are `usize`, so they never overflow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// interner.intern_ref("foo").unwrap();
+    /// let (first, _) = interner.export_arena_ref();
+    ///
+    /// interner.intern_ref("bar").unwrap();
+    /// let (second, offsets) = interner.export_arena_ref();
+    /// assert_eq!((first.as_str(), second.as_str()), ("foo", "foobar"));
+    /// assert_eq!(offsets, [0, 3, 6]);
+    /// ```
+    #[must_use]
+    pub fn export_arena_ref(&self) -> (String, Vec<usize>) {
+        let mut arena = String::with_capacity(self.arena_size());
+        let mut offsets = Vec::with_capacity(self.arena_entry_count());
+        offsets.push(0);
+        for item in &self.items {
+            arena.push_str(item.as_ref());
+            offsets.push(arena.len());
+        }
+        (arena, offsets)
+    }
+
+    /// Streams all strings to `w` as a single contiguous arena, without
+    /// building it in memory, and returns the offsets table.
+    ///
+    /// The bytes written and the offsets are laid out exactly like the output
+    /// of [`export_arena`](Self::export_arena): the string for handle `h` is
+    /// at `offsets[h]..offsets[h + 1]`. This makes the written file suitable
+    /// for memory-mapping. Offsets are `usize`, so they never overflow.
+    ///
+    /// Each item is passed to `w` separately; wrap unbuffered writers such as
+    /// a `File` in a `BufWriter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `w`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// interner.intern_ref("hello").unwrap();
+    /// interner.intern_ref("world").unwrap();
+    ///
+    /// let mut file = Vec::new();
+    /// let offsets = interner.write_arena(&mut file).unwrap();
+    /// assert_eq!(file, b"helloworld");
+    /// assert_eq!(offsets, [0, 5, 10]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_arena<W>(&self, w: &mut W) -> std::io::Result<Vec<usize>>
+    where
+        W: std::io::Write + ?Sized,
+    {
+        let mut offsets = Vec::with_capacity(self.arena_entry_count());
+        let mut end = 0;
+        offsets.push(end);
+        for item in &self.items {
+            let bytes = item.as_ref().as_bytes();
+            w.write_all(bytes)?;
+            end += bytes.len();
+            offsets.push(end);
+        }
+        Ok(offsets)
+    }
+
+    /// Streams the arena into a new file at `path` through a `BufWriter`,
+    /// like [`write_arena`](Self::write_arena), and returns the offsets.
+    ///
+    /// An existing file is truncated. The buffer is flushed before
+    /// returning, so errors from the final write are reported rather than
+    /// lost on drop; the file is not synced to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from creating or writing the file. The file may
+    /// then hold a prefix of the arena.
+    #[cfg(feature = "std")]
+    pub fn write_arena_file(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<Vec<usize>> {
+        use std::io::Write as _;
+
+        let mut w = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let offsets = self.write_arena(&mut w)?;
+        w.flush()?;
+        Ok(offsets)
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + AsRef<[u8]>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Consumes the interner and flattens all byte slices into a single
+    /// contiguous arena.
+    ///
+    /// This is [`export_arena`](Self::export_arena) for byte-like items such
+    /// as `Vec<u8>` or `Box<[u8]>`, with the same layout: the bytes for
+    /// handle `h` are `arena[offsets[h]..offsets[h + 1]]`. There are always
+    /// `len() + 1` offsets starting at `0`, so an empty item is an empty
+    /// range and an empty interner yields `[0]`. Offsets are `usize`, so they
+    /// never overflow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<Vec<u8>, RandomState>::new(RandomState::new());
+    /// interner.intern_owned(vec![1, 0, 2]).unwrap();
+    /// interner.intern_owned(vec![]).unwrap();
+    /// interner.intern_owned(vec![3]).unwrap();
+    ///
+    /// let (arena, offsets) = interner.export_arena_bytes();
+    /// assert_eq!(arena, [1, 0, 2, 3]);
+    /// assert_eq!(offsets, [0, 3, 3, 4]);
+    /// ```
+    #[must_use]
+    pub fn export_arena_bytes(self) -> (Vec<u8>, Vec<usize>) {
+        let total_bytes = self.items.iter().map(|item| item.as_ref().len()).sum();
+        let mut arena = Vec::with_capacity(total_bytes);
+        let mut offsets = Vec::with_capacity(self.items.len() + 1);
+        offsets.push(0);
+        for item in self.items {
+            arena.extend_from_slice(item.as_ref());
+            offsets.push(arena.len());
+        }
+        (arena, offsets)
+    }
+
+    /// Flattens all items into a single contiguous byte arena, copying them
+    /// and leaving the interner usable.
+    ///
+    /// This is the non-consuming [`export_arena_bytes`](Self::export_arena_bytes),
+    /// with the same layout; see [`export_arena_ref`](Self::export_arena_ref)
+    /// for checkpointing with it.
+    #[must_use]
+    pub fn export_arena_bytes_ref(&self) -> (Vec<u8>, Vec<usize>) {
+        let total_bytes = self.items.iter().map(|item| item.as_ref().len()).sum();
+        let mut arena = Vec::with_capacity(total_bytes);
+        let mut offsets = Vec::with_capacity(self.items.len() + 1);
+        offsets.push(0);
+        for item in &self.items {
+            arena.extend_from_slice(item.as_ref());
+            offsets.push(arena.len());
+        }
+        (arena, offsets)
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + Borrow<str> + FromRef<str>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Reconstructs a string interner from the output of [`export_arena`](Self::export_arena).
+    ///
+    /// The string for handle `h` is taken from `arena[offsets[h]..offsets[h + 1]]`
+    /// and interned in order, so the reconstructed interner reproduces the
+    /// original handles.
+    ///
+    /// This is [`import_arena`](Self::import_arena) with its descriptive
+    /// [`ArenaImportError`] collapsed into an `InternerError`.
+    ///
+    /// # Errors
+    ///
+    /// - `InternerError::InvalidRange` if `offsets` is empty, does not start
+    ///   at `0` and end at `arena.len()`, is not monotonic, or does not fall on
+    ///   UTF-8 character boundaries.
+    /// - `InternerError::Duplicate` if two ranges contain the same string, since
+    ///   the original handles could then not be reproduced.
+    /// - `InternerError::Overflow` if the number of strings exceeds the handle space.
+    pub fn from_arena(hasher: S, arena: &str, offsets: &[H]) -> Result<Self, InternerError> {
+        Self::import_arena(hasher, arena, offsets).map_err(|e| match e {
+            ArenaImportError::Duplicate { .. } => InternerError::Duplicate,
+            ArenaImportError::Interner(e) => e,
+            _ => InternerError::InvalidRange,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -595,12 +2524,30 @@ mod tests {
         sync::Arc,
         vec::Vec,
     };
-    use core::hash::BuildHasherDefault;
+    use core::{
+        cell::Cell,
+        cmp::Ordering,
+        hash::{BuildHasher, BuildHasherDefault},
+    };
 
     use ahash::RandomState;
     use rustc_hash::FxHasher;
 
-    use super::{Interner, InternerError};
+    use super::{DuplicateError, GrowthPolicy, Interner, InternerError, MissingItemError};
+
+    /// A `BuildHasher` counting how many hashers it has built, i.e. how many
+    /// values have been hashed.
+    #[derive(Clone, Default)]
+    struct CountingState(Rc<Cell<usize>>);
+
+    impl BuildHasher for CountingState {
+        type Hasher = FxHasher;
+
+        fn build_hasher(&self) -> FxHasher {
+            self.0.set(self.0.get() + 1);
+            FxHasher::default()
+        }
+    }
 
     // A helper to create a standard interner for tests that use strings.
     fn create_string_interner() -> Interner<String, RandomState> {
@@ -625,6 +2572,28 @@ mod tests {
         assert_eq!(interner.resolve(handle), Some(&item));
     }
 
+    #[test]
+    fn test_non_clone_values() {
+        /// A value owning a unique resource, like a file descriptor.
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        struct Resource {
+            fd: u32,
+        }
+
+        let mut interner: Interner<Resource, RandomState> = Interner::new(RandomState::new());
+        let a = interner.intern_owned(Resource { fd: 3 }).unwrap();
+        let b = interner.intern_owned(Resource { fd: 4 }).unwrap();
+        assert_eq!(interner.intern_owned(Resource { fd: 3 }).unwrap(), a);
+
+        assert_eq!(interner.resolve(b), Some(&Resource { fd: 4 }));
+        assert_eq!(
+            interner.lookup_handle(&Resource { fd: 4 }).unwrap(),
+            Some(b)
+        );
+        assert_eq!(interner.iter().map(|r| r.fd).collect::<Vec<_>>(), [3, 4]);
+        assert_eq!(interner.export(), [Resource { fd: 3 }, Resource { fd: 4 }]);
+    }
+
     #[test]
     fn test_intern_owned_duplicate_returns_same_handle() {
         let mut interner = create_string_interner();
@@ -704,6 +2673,162 @@ mod tests {
         assert_eq!(interner.len(), 1);
     }
 
+    #[test]
+    fn test_intern_owned_hashes_once() {
+        let state = CountingState::default();
+        let hashes = Rc::clone(&state.0);
+        let mut interner = Interner::<String, CountingState>::new_with_limit(state, 100);
+        // Growing the index rehashes the stored items, so reserve up front.
+        interner.reserve(100);
+
+        // Misses.
+        for i in 0..100 {
+            let before = hashes.get();
+            interner.intern_owned(i.to_string()).unwrap();
+            assert_eq!(hashes.get() - before, 1, "miss {i}");
+        }
+
+        // Hits.
+        for i in 0..100 {
+            let before = hashes.get();
+            assert_eq!(interner.intern_owned(i.to_string()).unwrap(), i);
+            assert_eq!(hashes.get() - before, 1, "hit {i}");
+        }
+
+        // At the limit, a hit still succeeds and a miss leaves the set untouched.
+        let before = hashes.get();
+        assert_eq!(interner.intern_owned("7".to_string()).unwrap(), 7);
+        assert!(matches!(
+            interner.intern_owned("new".to_string()),
+            Err(InternerError::LimitReached)
+        ));
+        assert_eq!(hashes.get() - before, 2);
+        assert_eq!(interner.len(), 100);
+
+        let before = hashes.get();
+        interner.intern_ref("42").unwrap();
+        interner
+            .intern_cow(Cow::<String>::Owned("43".to_string()))
+            .unwrap();
+        assert_eq!(hashes.get() - before, 2);
+    }
+
+    #[test]
+    fn test_intern_ref_misses_hash_once() {
+        let state = CountingState::default();
+        let hashes = Rc::clone(&state.0);
+        let mut interner = Interner::<String, CountingState>::new(state);
+        // Growing the index rehashes the stored items, so reserve up front.
+        interner.reserve(300);
+
+        for i in 0..100 {
+            let key = i.to_string();
+            let before = hashes.get();
+            assert_eq!(interner.intern_ref(key.as_str()).unwrap(), i);
+            assert_eq!(hashes.get() - before, 1, "intern_ref miss {i}");
+        }
+        for i in 100..200 {
+            let key = i.to_string();
+            let before = hashes.get();
+            assert_eq!(interner.intern_cow(Cow::Borrowed(key.as_str())).unwrap(), i);
+            assert_eq!(hashes.get() - before, 1, "intern_cow miss {i}");
+        }
+        let before = hashes.get();
+        let distinct = interner
+            .intern_owned_all_distinct((200..300).map(|i| i.to_string()))
+            .unwrap();
+        assert_eq!(hashes.get() - before, 100);
+        assert_eq!(distinct.first(), Some(&200));
+
+        // The reused hashes must find the values again.
+        for i in 0..300 {
+            assert_eq!(
+                interner.lookup_handle(i.to_string().as_str()).unwrap(),
+                Some(i)
+            );
+        }
+    }
+
+    #[test]
+    fn test_intern_ref_prehashed_matches_intern_ref_on_large_keys() {
+        let hasher = RandomState::new();
+        let mut hashed = Interner::<String, RandomState>::new_checked(hasher.clone());
+        let mut plain = Interner::<String, RandomState>::new(hasher.clone());
+
+        // 10 KB keys that differ only in their last bytes.
+        let prefix = "p".repeat(10_000);
+        for i in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3] {
+            let key = alloc::format!("{prefix}{i}");
+            let hash = hasher.hash_one(key.as_str());
+            assert_eq!(
+                hashed
+                    .intern_ref_prehashed(hash, key.as_str(), || key.clone())
+                    .unwrap(),
+                plain.intern_ref(key.as_str()).unwrap()
+            );
+        }
+        assert_eq!(hashed.as_values_slice(), plain.as_values_slice());
+        assert_eq!(hashed.len(), 7);
+    }
+
+    #[test]
+    fn test_intern_ref_tracked() {
+        let mut interner = create_string_interner();
+        let (a, inserted) = interner.intern_ref_tracked("alpha").unwrap();
+        assert!(inserted);
+        let (b, inserted) = interner.intern_ref_tracked("beta").unwrap();
+        assert!(inserted);
+        assert_eq!(interner.intern_ref_tracked("alpha").unwrap(), (a, false));
+        assert_eq!(interner.intern_ref_tracked("beta").unwrap(), (b, false));
+        assert_eq!(interner.len(), 2);
+
+        let mut limited = Interner::<String, RandomState>::new_with_limit(RandomState::new(), 1);
+        assert_eq!(limited.intern_ref_tracked("kept").unwrap(), (0, true));
+        assert_eq!(limited.intern_ref_tracked("kept").unwrap(), (0, false));
+        assert!(matches!(
+            limited.intern_ref_tracked("dropped"),
+            Err(InternerError::LimitReached)
+        ));
+    }
+
+    #[test]
+    fn test_prehashed_skips_hasher() {
+        let state = CountingState::default();
+        let hashes = Rc::clone(&state.0);
+        let mut interner = Interner::<String, CountingState>::new(state);
+        interner.reserve(10);
+        // `CountingState` builds plain `FxHasher`s, so hash the same way.
+        let upstream = BuildHasherDefault::<FxHasher>::default();
+
+        let normal = interner.intern_ref("normal").unwrap();
+        let before = hashes.get();
+        for (i, key) in ["a", "b", "a", "normal"].into_iter().enumerate() {
+            let hash = upstream.hash_one(key);
+            let h = interner
+                .intern_ref_prehashed(hash, key, || key.to_string())
+                .unwrap();
+            assert_eq!(h, [1, 2, 1, normal][i]);
+            assert_eq!(interner.lookup_prehashed(hash, key).unwrap(), Some(h));
+        }
+        assert_eq!(hashes.get(), before);
+        assert_eq!(interner.len(), 3);
+
+        // Prehashed values are found by normal lookups, and vice versa.
+        assert_eq!(interner.lookup_handle("a").unwrap(), Some(1));
+        assert_eq!(interner.intern_ref("b").unwrap(), 2);
+
+        // A wrong hash only misses.
+        assert_eq!(interner.lookup_prehashed(0, "a").unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "does not match")]
+    fn test_prehashed_checked_rejects_wrong_hash() {
+        let mut interner: Interner<String, RandomState> = Interner::new_checked(RandomState::new());
+        let _ = interner.intern_ref_prehashed(0, "a", || "a".to_string());
+    }
+
     #[test]
     fn test_intern_cow_variants() {
         let mut interner = create_string_interner();
@@ -810,12 +2935,186 @@ mod tests {
         }
         assert_eq!(interner.len(), 256);
 
-        // The next unique insertion (the 257th) should fail.
-        let overflow_res = interner.intern_owned(256);
-        assert!(matches!(overflow_res, Err(InternerError::Overflow)));
+        // The next unique insertion (the 257th) should fail.
+        let overflow_res = interner.intern_owned(256);
+        assert!(matches!(overflow_res, Err(InternerError::Overflow)));
+
+        // The length should not have changed after the failed insertion.
+        assert_eq!(interner.len(), 256);
+    }
+
+    #[test]
+    fn test_overflow_falls_back_to_handle() {
+        let mut interner: Interner<u16, RandomState, u8> =
+            Interner::new_with_overflow_handle(RandomState::new(), 0);
+        assert_eq!(interner.overflow_handle(), Some(0));
+        assert_eq!(
+            Interner::<u16, RandomState, u8>::new(RandomState::new()).overflow_handle(),
+            None
+        );
+
+        // Value 0 is the sentinel that overflowing items resolve to.
+        for i in 0..=255 {
+            assert_eq!(interner.intern_owned(i).unwrap(), i as u8);
+        }
+
+        // New items map to the fallback without being stored.
+        assert_eq!(interner.intern_owned(256).unwrap(), 0);
+        assert_eq!(interner.intern_ref(&257).unwrap(), 0);
+        assert_eq!(interner.intern_ref_tracked(&258).unwrap(), (0, false));
+        assert_eq!(interner.try_intern_owned(259).unwrap(), 0);
+        assert_eq!(interner.intern_copied(&[260, 7]).unwrap(), [0, 7]);
+        assert_eq!(interner.len(), 256);
+        assert_eq!(interner.resolve(0), Some(&0));
+        assert_eq!(interner.lookup_handle(&256).unwrap(), None);
+
+        // Existing items keep their own handles.
+        assert_eq!(interner.intern_owned(200).unwrap(), 200);
+        assert_eq!(interner.resolve(255), Some(&255));
+
+        // Distinct handles cannot be faked, so this still fails.
+        assert!(matches!(
+            interner.intern_owned_all_distinct([1000]),
+            Err(DuplicateError::Interner(InternerError::Overflow))
+        ));
+    }
+
+    #[test]
+    fn test_builders_compose() {
+        let mut interner = Interner::<u16, RandomState, u8>::new(RandomState::new())
+            .with_growth(GrowthPolicy::Linear(64))
+            .with_checked(true)
+            .with_overflow_handle(0)
+            .with_limit(300);
+        assert_eq!(interner.growth_policy(), GrowthPolicy::Linear(64));
+        assert_eq!(interner.overflow_handle(), Some(0));
+        assert_eq!(interner.limit(), 300);
+
+        for i in 0..=255 {
+            assert_eq!(interner.intern_owned(i).unwrap(), i as u8);
+        }
+        assert_eq!(interner.capacity(), 256);
+        assert_eq!(interner.intern_owned(256).unwrap(), 0);
+
+        // The limit is checked before the fallback applies.
+        interner = interner.with_limit(256);
+        assert!(matches!(
+            interner.intern_owned(257),
+            Err(InternerError::LimitReached)
+        ));
+    }
+
+    #[test]
+    fn test_overflow_records_caller_location() {
+        let mut interner: Interner<u16, RandomState, u8> = Interner::new(RandomState::new());
+        for i in 0..=255 {
+            interner.intern_owned(i).unwrap();
+        }
+        assert!(interner.last_overflow_location().is_none());
+
+        // A hit still succeeds and records nothing.
+        interner.intern_ref(&7).unwrap();
+        assert!(interner.last_overflow_location().is_none());
+
+        let line = line!() + 1;
+        let result = interner.intern_ref(&300);
+        assert!(matches!(result, Err(InternerError::Overflow)));
+
+        let location = interner.last_overflow_location();
+        if cfg!(debug_assertions) {
+            let location = location.unwrap();
+            assert_eq!(location.file(), core::panic::Location::caller().file());
+            assert_eq!(location.line(), line);
+        } else {
+            assert!(location.is_none());
+        }
+
+        let line = line!() + 1;
+        let result = interner.intern_owned_all_distinct([1000, 1001]);
+        assert!(matches!(
+            result,
+            Err(DuplicateError::Interner(InternerError::Overflow))
+        ));
+        if cfg!(debug_assertions) {
+            assert_eq!(interner.last_overflow_location().unwrap().line(), line);
+        }
+    }
+
+    #[test]
+    fn test_item_limit_reached() {
+        let mut interner: Interner<u32, RandomState> =
+            Interner::new_with_limit(RandomState::new(), 10_000);
+        assert_eq!(interner.limit(), 10_000);
+
+        for i in 0..10_000 {
+            interner.intern_owned(i).unwrap();
+        }
+
+        // The 10001st unique insert is refused, regardless of the handle width.
+        assert!(matches!(
+            interner.intern_owned(10_000),
+            Err(InternerError::LimitReached)
+        ));
+        assert!(matches!(
+            interner.intern_ref(&10_000),
+            Err(InternerError::LimitReached)
+        ));
+        assert_eq!(interner.len(), 10_000);
+
+        // Existing items can still be interned and looked up.
+        assert_eq!(interner.intern_owned(42).unwrap(), 42);
+        assert_eq!(interner.lookup_handle(&9_999).unwrap(), Some(9_999));
+    }
+
+    #[test]
+    fn test_mutate_payload_in_place() {
+        // Only `key` participates in `Hash` and `Eq`.
+        #[derive(Debug)]
+        struct Record {
+            key: u32,
+            hits: u32,
+        }
+        impl PartialEq for Record {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+        impl Eq for Record {}
+        impl core::hash::Hash for Record {
+            fn hash<S: core::hash::Hasher>(&self, state: &mut S) {
+                self.key.hash(state);
+            }
+        }
+
+        let mut interner: Interner<Record, RandomState> = Interner::new(RandomState::new());
+        let h1 = interner.intern_owned(Record { key: 1, hits: 0 }).unwrap();
+        let h2 = interner.intern_owned(Record { key: 2, hits: 0 }).unwrap();
+
+        interner.resolve_mut(h1).unwrap().hits += 10;
+        interner.for_each_mut(|record| record.hits += 1);
+        assert!(interner.resolve_mut(99).is_none());
+
+        assert_eq!(interner.resolve(h1).unwrap().hits, 11);
+        assert_eq!(interner.resolve(h2).unwrap().hits, 1);
 
-        // The length should not have changed after the failed insertion.
-        assert_eq!(interner.len(), 256);
+        // Lookups by key still work after the payload edits.
+        let probe = Record { key: 1, hits: 0 };
+        assert_eq!(interner.lookup_handle(&probe).unwrap(), Some(h1));
+        assert_eq!(interner.intern_owned(probe).unwrap(), h1);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mut interner: Interner<i32, RandomState> = Interner::new(RandomState::new());
+        assert_eq!(interner.min_item(), None);
+        assert_eq!(interner.max_item(), None);
+
+        for i in [5, -3, 12, 0, 12] {
+            interner.intern_owned(i).unwrap();
+        }
+        assert_eq!(interner.min_item(), Some(&-3));
+        assert_eq!(interner.max_item(), Some(&12));
     }
 
     #[test]
@@ -831,6 +3130,19 @@ mod tests {
         assert_eq!(interner.len(), 1);
     }
 
+    #[test]
+    fn test_export_boxed() {
+        let mut interner = create_string_interner();
+        interner.reserve(100);
+        for s in ["b", "a", "b", "c"] {
+            interner.intern_ref(s).unwrap();
+        }
+        let vec = interner.clone().export();
+        let boxed = interner.export_boxed();
+        assert_eq!(*boxed, *vec);
+        assert_eq!(boxed.len(), 3);
+    }
+
     #[test]
     fn test_export_preserves_insertion_order() {
         let mut interner = create_string_interner();
@@ -883,6 +3195,356 @@ mod tests {
         assert!(!interner.contains("def"));
     }
 
+    #[test]
+    fn test_handle_matches() {
+        let mut interner = create_string_interner();
+        let a = interner.intern_ref("a").unwrap();
+        let b = interner.intern_ref("b").unwrap();
+
+        assert!(interner.handle_matches(a, "a"));
+        assert!(interner.handle_matches(b, "b"));
+        assert!(!interner.handle_matches(a, "b"));
+        assert!(!interner.handle_matches(b, "missing"));
+        assert!(!interner.handle_matches(2, "a"));
+        assert!(!interner.handle_matches(u32::MAX, "a"));
+    }
+
+    #[test]
+    fn test_try_reserve_and_reserve_exact() {
+        let mut interner = create_string_interner();
+        interner.intern_ref("a").unwrap();
+
+        interner.try_reserve(50).unwrap();
+        assert!(interner.capacity() >= 51);
+        interner.reserve_exact(200);
+        assert!(interner.capacity() >= 201);
+
+        // A request that cannot be satisfied leaves the interner unchanged.
+        let capacity = interner.capacity();
+        assert!(matches!(
+            interner.try_reserve(usize::MAX),
+            Err(InternerError::AllocationFailed)
+        ));
+        assert_eq!(interner.capacity(), capacity);
+        assert_eq!(interner.as_values_slice(), ["a"]);
+
+        // Linear growth rounds fallible reservations up to the step as well.
+        let mut linear = Interner::<u32, RandomState>::new_with_growth(
+            RandomState::new(),
+            crate::GrowthPolicy::Linear(100),
+        );
+        linear.try_reserve(1).unwrap();
+        assert_eq!(linear.capacity(), 100);
+        assert!(matches!(
+            linear.try_reserve(usize::MAX),
+            Err(InternerError::AllocationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_try_intern() {
+        let mut interner = create_string_interner();
+        let a = interner.try_intern_ref("a").unwrap();
+        let b = interner.try_intern_owned("b".to_string()).unwrap();
+        assert_eq!((a, b), (0, 1));
+        assert_eq!(interner.try_intern_owned("a".to_string()).unwrap(), a);
+        assert_eq!(interner.try_intern_ref("b").unwrap(), b);
+        assert_eq!(interner.len(), 2);
+
+        let mut limited: Interner<u32, RandomState> =
+            Interner::new_with_limit(RandomState::new(), 1);
+        limited.try_intern_owned(1).unwrap();
+        assert!(matches!(
+            limited.try_intern_owned(2),
+            Err(InternerError::LimitReached)
+        ));
+        assert_eq!(limited.try_intern_owned(1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_try_reserve_error_conversion() {
+        let err = Vec::<u8>::new().try_reserve(usize::MAX).unwrap_err();
+        assert!(matches!(
+            InternerError::from(err),
+            InternerError::AllocationFailed
+        ));
+    }
+
+    #[test]
+    fn test_reserve_full_handle_space() {
+        let mut interner = Interner::<u32, RandomState, u8>::new(RandomState::new());
+        interner.intern_owned(1000).unwrap();
+        interner.reserve_full_handle_space().unwrap();
+        let capacity = interner.capacity();
+        assert!(capacity >= 256);
+
+        for i in 0..255 {
+            interner.intern_owned(i).unwrap();
+        }
+        assert_eq!(interner.len(), 256);
+        assert_eq!(interner.capacity(), capacity);
+        assert!(matches!(
+            interner.intern_owned(255),
+            Err(InternerError::Overflow)
+        ));
+
+        // With a limit, only the limit is reserved.
+        let mut limited = Interner::<u32, RandomState, u16>::new_with_limit(RandomState::new(), 10);
+        limited.reserve_full_handle_space().unwrap();
+        assert!(limited.capacity() >= 10);
+        assert!(limited.capacity() < 1000);
+
+        // `usize` handles cannot all be reserved.
+        let mut wide = Interner::<u32, RandomState, usize>::new(RandomState::new());
+        assert!(matches!(
+            wide.reserve_full_handle_space(),
+            Err(InternerError::Overflow)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "stats"))]
+    fn test_no_stats_field_without_feature() {
+        type Fx = BuildHasherDefault<FxHasher>;
+        assert_eq!(
+            core::mem::size_of::<Interner<u8, Fx>>(),
+            core::mem::size_of::<(
+                crate::store::Store<u8, Fx>,
+                usize,
+                crate::GrowthPolicy,
+                bool,
+                Option<&'static core::panic::Location<'static>>,
+                Option<u32>
+            )>()
+        );
+    }
+
+    #[test]
+    fn test_get_disjoint_mut() {
+        let mut interner: Interner<String, RandomState> = create_string_interner();
+        let a = interner.intern_owned("1".to_string()).unwrap();
+        let b = interner.intern_owned("2".to_string()).unwrap();
+        let c = interner.intern_owned("3".to_string()).unwrap();
+
+        let [x, y] = interner.get_disjoint_mut([c, a]).unwrap();
+        core::mem::swap(x, y);
+        assert_eq!(interner.resolve(a), Some(&"3".to_string()));
+        assert_eq!(interner.resolve(c), Some(&"1".to_string()));
+
+        assert!(interner.get_disjoint_mut([a, b, a]).is_none());
+        assert!(interner.get_disjoint_mut([b, 3]).is_none());
+        assert_eq!(
+            interner.get_disjoint_mut([]).map(|r: [_; 0]| r.len()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_negative_filter_has_no_false_negatives() {
+        let mut interner: Interner<u64, RandomState> =
+            Interner::new(RandomState::new()).with_negative_filter(8);
+        assert_eq!(interner.negative_filter_bits(), Some(8));
+
+        // A fixed-seed LCG, so failures are reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            state >> 40
+        };
+
+        let mut present = alloc::collections::BTreeSet::new();
+        for round in 0..5000 {
+            let value = next();
+            if round % 7 == 0 {
+                if interner.remove(&value).is_some() {
+                    present.remove(&value);
+                }
+            } else {
+                interner.intern_owned(value).unwrap();
+                present.insert(value);
+            }
+            assert_eq!(interner.contains(&value), present.contains(&value));
+        }
+        for value in &present {
+            assert!(interner.lookup_handle(value).unwrap().is_some());
+        }
+        assert_eq!(interner.len(), present.len());
+
+        let tail = interner.split_off(100);
+        assert_eq!(tail.negative_filter_bits(), Some(8));
+        assert!(tail.iter().all(|value| tail.contains(value)));
+        assert!(interner.iter().all(|value| interner.contains(value)));
+
+        interner.clear();
+        assert!(present.iter().all(|value| !interner.contains(value)));
+        interner.intern_owned(42).unwrap();
+        assert!(interner.contains(&42));
+    }
+
+    #[test]
+    fn test_negative_filter_disabled() {
+        let mut interner = create_string_interner();
+        assert_eq!(interner.negative_filter_bits(), None);
+        let h = interner.intern_ref("a").unwrap();
+
+        // Adding and then removing a filter leaves lookups unchanged.
+        let mut interner = interner.with_negative_filter(4);
+        assert_eq!(interner.lookup_handle("a").unwrap(), Some(h));
+        interner = interner.with_negative_filter(0);
+        assert_eq!(interner.negative_filter_bits(), None);
+        assert_eq!(interner.lookup_handle("a").unwrap(), Some(h));
+        assert_eq!(interner.lookup_handle("b").unwrap(), None);
+    }
+
+    #[test]
+    fn test_resolve_ptr_eq() {
+        let mut interner: Interner<Arc<str>, RandomState> = Interner::new(RandomState::new());
+        let a = interner.intern_ref("shared").unwrap();
+        let b = interner.intern_ref("shared").unwrap();
+        let c = interner.intern_ref("other").unwrap();
+
+        assert!(interner.resolve_ptr_eq(a, b));
+        assert!(Arc::ptr_eq(
+            interner.resolve(a).unwrap(),
+            interner.resolve(b).unwrap()
+        ));
+        assert!(!interner.resolve_ptr_eq(a, c));
+        assert!(!interner.resolve_ptr_eq(a, 99));
+
+        // An `Arc` interned by value is kept, not replaced.
+        let owned: Arc<str> = Arc::from("owned");
+        let d = interner.intern_owned(Arc::clone(&owned)).unwrap();
+        assert_eq!(interner.intern_ref("owned").unwrap(), d);
+        assert!(Arc::ptr_eq(interner.resolve(d).unwrap(), &owned));
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut interner = create_string_interner();
+        for s in ["a", "b", "c", "d"] {
+            interner.intern_ref(s).unwrap();
+        }
+
+        interner.truncate(10);
+        assert_eq!(interner.len(), 4);
+
+        interner.truncate(2);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.resolve(1).map(String::as_str), Some("b"));
+        assert_eq!(interner.resolve(2), None);
+        assert_eq!(interner.lookup_handle("c").unwrap(), None);
+        assert_eq!(interner.lookup_handle("a").unwrap(), Some(0));
+
+        // Dropped values are interned anew at the freed handles.
+        assert_eq!(interner.intern_ref("d").unwrap(), 2);
+        assert_eq!(interner.intern_ref("b").unwrap(), 1);
+
+        interner.truncate(0);
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn test_as_values_slice() {
+        let mut interner = create_string_interner();
+        let a = interner.intern_ref("a").unwrap();
+        let b = interner.intern_ref("b").unwrap();
+        interner.intern_ref("a").unwrap();
+
+        let values = interner.as_values_slice();
+        assert_eq!(values, ["a", "b"]);
+        assert_eq!(values[a as usize], "a");
+        assert_eq!(values[b as usize], "b");
+    }
+
+    /// A type whose `Hash` changes on every call, violating the contract.
+    #[derive(PartialEq, Eq)]
+    struct UnstableHash(u32);
+
+    impl core::hash::Hash for UnstableHash {
+        fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+            static CALLS: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+            self.0.hash(state);
+            CALLS
+                .fetch_add(1, core::sync::atomic::Ordering::Relaxed)
+                .hash(state);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "`Hash` and `Eq` implementations of the item type are inconsistent")]
+    fn test_new_checked_catches_broken_hash() {
+        let mut interner = Interner::<UnstableHash, RandomState>::new_checked(RandomState::new());
+        // A single lookup may still hit the right slot by chance, so intern a
+        // few items.
+        for i in 0..64 {
+            let _ = interner.intern_owned(UnstableHash(i));
+        }
+    }
+
+    #[test]
+    fn test_new_checked_accepts_sound_types() {
+        let mut interner = Interner::<String, RandomState>::new_checked(RandomState::new());
+        for s in ["a", "b", "a", "c"] {
+            interner.intern_ref(s).unwrap();
+        }
+        interner
+            .intern_cow(Cow::<String>::Owned("d".to_string()))
+            .unwrap();
+        assert_eq!(interner.len(), 4);
+
+        // Unchecked interners accept the broken type silently, including
+        // checked ones switched back off.
+        let mut unchecked = Interner::<UnstableHash, RandomState>::new_checked(RandomState::new())
+            .with_checked(false);
+        for _ in 0..8 {
+            unchecked.intern_owned(UnstableHash(1)).unwrap();
+        }
+        assert!(unchecked.len() > 1);
+    }
+
+    #[test]
+    fn test_resolve_cow() {
+        let mut interner = create_string_interner();
+        let h = interner.intern_ref("cow").unwrap();
+
+        let cow = interner.resolve_cow(h).unwrap();
+        assert!(matches!(cow, Cow::Borrowed(_)));
+        assert_eq!(cow.as_ref(), interner.resolve(h).unwrap());
+        assert_eq!(cow.into_owned(), "cow");
+        assert!(interner.resolve_cow(1).is_none());
+    }
+
+    #[test]
+    fn test_intern_owned_all_distinct() {
+        let mut interner = create_string_interner();
+        let keys = ["host", "port", "user"].map(String::from);
+        let handles = interner.intern_owned_all_distinct(keys).unwrap();
+        assert_eq!(handles, [0, 1, 2]);
+
+        // Already present before the call.
+        let err = interner
+            .intern_owned_all_distinct(["path".to_string(), "port".to_string()])
+            .unwrap_err();
+        assert!(matches!(&err, DuplicateError::Duplicate(key) if key == "port"));
+        assert_eq!(interner.len(), 4);
+
+        // Repeated within the input.
+        let err = interner
+            .intern_owned_all_distinct(["a".to_string(), "b".to_string(), "a".to_string()])
+            .unwrap_err();
+        assert!(matches!(&err, DuplicateError::Duplicate(key) if key == "a"));
+        assert_eq!(err.to_string(), "Interner item is a duplicate");
+
+        let mut small = Interner::<u32, RandomState, u8>::new_with_limit(RandomState::new(), 2);
+        assert!(matches!(
+            small.intern_owned_all_distinct([1, 2, 3]),
+            Err(DuplicateError::Interner(InternerError::LimitReached))
+        ));
+    }
+
     #[test]
     fn test_interner_utilities() {
         let mut interner = Interner::<String, RandomState>::with_capacity(RandomState::new(), 10);
@@ -933,6 +3595,85 @@ mod tests {
         assert_eq!(s2, "world");
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_arena() {
+        let mut interner = create_string_interner();
+        for s in ["hello", "", "wörld", "!"] {
+            interner.intern_ref(s).unwrap();
+        }
+
+        let mut out = Vec::new();
+        let offsets = interner.write_arena(&mut out).unwrap();
+        assert_eq!(offsets, [0, 5, 5, 11, 12]);
+        assert_eq!(offsets.len(), interner.arena_entry_count());
+        assert_eq!(out.len(), interner.arena_size());
+
+        let arena = core::str::from_utf8(&out).unwrap();
+        for (h, window) in offsets.windows(2).enumerate() {
+            let h = u32::try_from(h).unwrap();
+            assert_eq!(
+                Some(&arena[window[0]..window[1]]),
+                interner.resolve(h).map(String::as_str)
+            );
+        }
+
+        // The same layout as the in-memory export.
+        let (exported, exported_offsets) = interner.export_arena().unwrap();
+        assert_eq!(exported.as_bytes(), out);
+        assert!(exported_offsets.iter().map(|&o| o as usize).eq(offsets));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_arena_errors_and_file() {
+        /// Accepts `capacity` bytes, then fails every write.
+        struct FailingWriter {
+            written: Vec<u8>,
+            capacity: usize,
+        }
+
+        impl std::io::Write for FailingWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                let n = buf.len().min(self.capacity - self.written.len());
+                if n == 0 && !buf.is_empty() {
+                    return Err(std::io::Error::other("disk full"));
+                }
+                self.written.extend_from_slice(&buf[..n]);
+                Ok(n)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut interner = create_string_interner();
+        for s in ["alpha", "beta", "gamma"] {
+            interner.intern_ref(s).unwrap();
+        }
+
+        // The error surfaces even though the first item was written in part.
+        let mut sink = FailingWriter {
+            written: Vec::new(),
+            capacity: 7,
+        };
+        let err = interner.write_arena(&mut sink).unwrap_err();
+        assert_eq!(err.to_string(), "disk full");
+        assert_eq!(sink.written, b"alphabe");
+        assert_eq!(interner.len(), 3);
+
+        let path = std::env::temp_dir().join(alloc::format!(
+            "xgx-intern-{}-arena.bin",
+            std::process::id()
+        ));
+        let offsets = interner.write_arena_file(&path).unwrap();
+        let (arena, exported_offsets) = interner.clone().export_arena().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), arena.as_bytes());
+        assert!(exported_offsets.iter().map(|&o| o as usize).eq(offsets));
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_intern_ref_or_insert_with() {
         let mut interner = create_string_interner();
@@ -956,6 +3697,34 @@ mod tests {
         assert!(!called, "Closure should not be called if item exists");
     }
 
+    #[test]
+    fn test_intern_copied() {
+        let mut interner = Interner::<u32, RandomState>::new(RandomState::new());
+        interner.intern_owned(7).unwrap();
+
+        let handles = interner.intern_copied(&[3, 7, 3, 9, 9, 3]).unwrap();
+        assert_eq!(handles, alloc::vec![1, 0, 1, 2, 2, 1]);
+        assert_eq!(interner.len(), 3);
+        assert!(interner.capacity() >= 6);
+        assert_eq!(interner.resolve(2), Some(&9));
+
+        assert_eq!(interner.intern_copied(&[]).unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_resolve_existing_handle() {
+        let mut interner = create_string_interner();
+        let h = interner.intern_ref("present").unwrap();
+
+        assert_eq!(interner.resolve_existing_handle("present").unwrap(), h);
+        assert_eq!(
+            interner.resolve_existing_handle("absent"),
+            Err(MissingItemError::Missing)
+        );
+        // The failed lookup did not intern anything.
+        assert_eq!(interner.len(), 1);
+    }
+
     #[test]
     fn test_error_display() {
         let err = InternerError::Overflow;
@@ -970,11 +3739,83 @@ mod tests {
 
         // This consumes the interner
         let vec: Vec<String> = interner.into_iter().collect();
-        // Sort to ensure deterministic comparison, though IndexSet preserves insertion order
+        // Sort to ensure deterministic comparison, though the interner preserves insertion order
         // so it should be ["a", "b"]
         assert_eq!(vec, alloc::vec!["a".to_string(), "b".to_string()]);
     }
 
+    #[test]
+    fn test_from_arena_round_trip() {
+        let mut interner = create_string_interner();
+        let handles = [
+            interner.intern_ref("alpha").unwrap(),
+            interner.intern_ref("").unwrap(),
+            interner.intern_ref("γάμμα").unwrap(),
+        ];
+        let original = interner.clone();
+
+        let (arena, offsets) = interner.export_arena().unwrap();
+        let restored =
+            Interner::<String, RandomState>::from_arena(RandomState::new(), &arena, &offsets)
+                .unwrap();
+
+        assert_eq!(restored.len(), original.len());
+        for h in handles {
+            assert_eq!(restored.resolve(h), original.resolve(h));
+            let item = original.resolve(h).unwrap();
+            assert_eq!(restored.lookup_handle(item.as_str()).unwrap(), Some(h));
+        }
+    }
+
+    #[test]
+    fn test_from_arena_rejects_invalid_offsets() {
+        let load = |arena: &str, offsets: &[u32]| {
+            Interner::<String, RandomState>::from_arena(RandomState::new(), arena, offsets)
+        };
+
+        assert!(matches!(load("ab", &[]), Err(InternerError::InvalidRange)));
+        assert!(matches!(
+            load("ab", &[0, 3]),
+            Err(InternerError::InvalidRange)
+        ));
+        assert!(matches!(
+            load("ab", &[0, 2, 1]),
+            Err(InternerError::InvalidRange)
+        ));
+        assert!(matches!(
+            load("ab", &[1, 2]),
+            Err(InternerError::InvalidRange)
+        ));
+        assert!(matches!(
+            load("ab", &[0, 1]),
+            Err(InternerError::InvalidRange)
+        ));
+        // 'é' occupies bytes 0..2, so offset 1 splits it.
+        assert!(matches!(
+            load("é", &[0, 1, 2]),
+            Err(InternerError::InvalidRange)
+        ));
+        assert!(matches!(
+            load("abab", &[0, 2, 4]),
+            Err(InternerError::Duplicate)
+        ));
+        assert_eq!(load("", &[0]).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_arena_size_matches_export() {
+        let mut interner = create_string_interner();
+        for s in ["hello", "wörld", "", "hello"] {
+            interner.intern_ref(s).unwrap();
+        }
+        let size = interner.arena_size();
+        let entries = interner.arena_entry_count();
+
+        let (arena, offsets) = interner.export_arena().unwrap();
+        assert_eq!(size, arena.len());
+        assert_eq!(entries, offsets.len());
+    }
+
     #[test]
     fn test_export_arena_empty() {
         let interner = create_string_interner();
@@ -984,6 +3825,79 @@ mod tests {
         assert_eq!(offsets, alloc::vec![0]); // Should just contain the initial 0
     }
 
+    #[test]
+    fn test_export_arena_ref_snapshots() {
+        let mut interner = create_string_interner();
+        for s in ["alpha", "", "βeta"] {
+            interner.intern_ref(s).unwrap();
+        }
+        let (first, first_offsets) = interner.export_arena_ref();
+        let first_resolved = interner.export_resolved_ref();
+
+        for s in ["gamma", "alpha", "🦀"] {
+            interner.intern_ref(s).unwrap();
+        }
+        let (second, second_offsets) = interner.export_arena_ref();
+        assert_eq!(interner.len(), 5);
+
+        assert!(second.len() > first.len() && second.starts_with(&first));
+        assert!(
+            second_offsets.len() > first_offsets.len()
+                && second_offsets.starts_with(&first_offsets)
+        );
+        assert_eq!(first_resolved.as_parts(), (&*first, &*first_offsets));
+        assert_eq!(
+            interner.export_resolved_ref().as_parts(),
+            (&*second, &*second_offsets)
+        );
+
+        // The snapshot matches what the consuming exports produce.
+        let (bytes, byte_offsets) = interner.export_arena_bytes_ref();
+        assert_eq!(
+            (bytes.as_slice(), byte_offsets.as_slice()),
+            (second.as_bytes(), &*second_offsets)
+        );
+        let (consumed, offsets) = interner.export_arena().unwrap();
+        assert_eq!(consumed, second);
+        assert!(offsets.iter().map(|&o| o as usize).eq(second_offsets));
+    }
+
+    #[test]
+    fn test_export_arena_bytes() {
+        let blobs: [&[u8]; 5] = [b"\0\0", b"a\0b", b"", b"\xff\0", b"\0"];
+        let mut interner = Interner::<Box<[u8]>, RandomState>::new(RandomState::new());
+        let handles: Vec<u32> = blobs
+            .iter()
+            .map(|b| interner.intern_owned(Box::from(*b)).unwrap())
+            .collect();
+        interner.intern_owned(Box::from(&b"a\0b"[..])).unwrap();
+
+        let (arena, offsets) = interner.export_arena_bytes();
+        assert_eq!(offsets.len(), blobs.len() + 1);
+        assert_eq!(offsets[0], 0);
+        assert_eq!(*offsets.last().unwrap(), arena.len());
+        for (blob, &h) in blobs.iter().zip(&handles) {
+            let h = h as usize;
+            assert_eq!(&arena[offsets[h]..offsets[h + 1]], *blob);
+        }
+
+        let empty = Interner::<Vec<u8>, RandomState>::new(RandomState::new());
+        assert_eq!(empty.export_arena_bytes(), (Vec::new(), alloc::vec![0]));
+
+        // Strings flatten to the same bytes and offsets either way.
+        let mut strings = create_string_interner();
+        for s in ["x", "", "yz"] {
+            strings.intern_ref(s).unwrap();
+        }
+        let (text, text_offsets) = strings.clone().export_arena().unwrap();
+        let (bytes, byte_offsets) = strings.export_arena_bytes();
+        assert_eq!(text.as_bytes(), bytes);
+        assert_eq!(
+            text_offsets.iter().map(|&o| o as usize).collect::<Vec<_>>(),
+            byte_offsets
+        );
+    }
+
     #[test]
     fn test_lookup_handle_non_existent() {
         let interner = create_string_interner();
@@ -1011,6 +3925,161 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn test_iter_rev() {
+        fn assert_double_ended<I: DoubleEndedIterator + ExactSizeIterator>(iter: I) -> I {
+            iter
+        }
+
+        let mut interner = create_string_interner();
+        let words = ["a", "b", "a", "c", "d"];
+        for w in words {
+            interner.intern_ref(w).unwrap();
+        }
+        let newest_first: Vec<&str> = assert_double_ended(interner.iter())
+            .rev()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(newest_first, ["d", "c", "b", "a"]);
+        let via_ref: Vec<&String> = assert_double_ended((&interner).into_iter()).rev().collect();
+        assert_eq!(via_ref.len(), 4);
+        assert_eq!(via_ref[0], "d");
+    }
+
+    #[test]
+    fn test_ord_compares_items_in_order() {
+        let build = |words: &[&str]| {
+            let mut interner = create_string_interner();
+            for w in words {
+                interner.intern_ref(*w).unwrap();
+            }
+            interner
+        };
+        let abc = build(&["a", "b", "c"]);
+        let acb = build(&["a", "c", "b"]);
+        assert!(abc < acb);
+        assert_eq!(acb.cmp(&abc), Ordering::Greater);
+        assert!(build(&["a"]) < abc);
+
+        // Equality ignores hashers and capacity, but not order.
+        let mut reserved = create_string_interner();
+        reserved.reserve(100);
+        for w in ["a", "b", "c"] {
+            reserved.intern_ref(w).unwrap();
+        }
+        assert_eq!(reserved, abc);
+        assert_eq!(reserved.cmp(&abc), Ordering::Equal);
+        assert_ne!(abc, acb);
+        assert_ne!(build(&["a"]), abc);
+
+        let mut sorted = alloc::vec![acb, abc];
+        sorted.sort();
+        assert_eq!(sorted[0].resolve(1).unwrap(), "b");
+    }
+
+    #[test]
+    fn test_from_refs_clones_uniques() {
+        let words = ["a", "b", "a", "c"];
+        let interner =
+            Interner::<String, RandomState>::from_refs(RandomState::new(), words).unwrap();
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.as_values_slice(), ["a", "b", "c"]);
+
+        // Repeats do not count towards the handle space.
+        let values: Vec<u16> = (0..1000).map(|i| i % 256).collect();
+        let bytes = Interner::<u16, RandomState, u8>::from_refs(RandomState::new(), &values);
+        assert_eq!(bytes.unwrap().len(), 256);
+        let values: Vec<u16> = (0..257).collect();
+        assert!(matches!(
+            Interner::<u16, RandomState, u8>::from_refs(RandomState::new(), &values),
+            Err(InternerError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_incremental_rehash() {
+        let mut interner =
+            Interner::<u32, RandomState>::new(RandomState::new()).with_incremental_rehash(true);
+        let mut rehashes = 0;
+        for i in 0..20_000 {
+            let remaining = interner.items.rehash_remaining();
+            let was_in_progress = interner.rehash_in_progress();
+            assert_eq!(interner.intern_owned(i).unwrap(), i);
+
+            // One insertion moves at most one step of items.
+            let now = interner.items.rehash_remaining();
+            if was_in_progress {
+                assert!(remaining - now <= crate::store::Store::<u32, RandomState>::REHASH_STEP);
+            } else if now > 0 {
+                rehashes += 1;
+            }
+
+            // Lookups see old and new items alike during the move.
+            if i % 97 == 0 {
+                for j in (0..=i).step_by(13) {
+                    assert_eq!(interner.lookup_handle(&j).unwrap(), Some(j));
+                }
+                assert_eq!(interner.lookup_handle(&(i + 1)).unwrap(), None);
+                assert_eq!(interner.intern_owned(i / 2).unwrap(), i / 2);
+            }
+        }
+        assert!(rehashes > 5);
+
+        // Force a growth, then finish it explicitly.
+        let before = interner.capacity();
+        let mut i = 20_000;
+        while !interner.rehash_in_progress() {
+            interner.intern_owned(i).unwrap();
+            i += 1;
+        }
+        assert!(interner.items.rehash_remaining() >= before / 2);
+        interner.finish_rehash();
+        assert!(!interner.rehash_in_progress());
+        assert_eq!(interner.len(), usize::try_from(i).unwrap());
+        assert!((0..i).all(|j| interner.lookup_handle(&j).unwrap() == Some(j)));
+
+        // Structural changes finish a rehash first.
+        while !interner.rehash_in_progress() {
+            interner.intern_owned(i).unwrap();
+            i += 1;
+        }
+        interner.remove(&0).unwrap();
+        assert!(!interner.rehash_in_progress());
+        assert_eq!(interner.lookup_handle(&1).unwrap(), Some(0));
+    }
+
+    #[test]
+    fn test_remaining_handle_capacity() {
+        let mut interner = Interner::<u32, RandomState, u8>::new(RandomState::new());
+        assert_eq!(interner.remaining_handle_capacity(), Some(256));
+        for i in 0..200 {
+            interner.intern_owned(i).unwrap();
+        }
+        interner.intern_owned(0).unwrap();
+        assert_eq!(interner.remaining_handle_capacity(), Some(56));
+        for i in 200..256 {
+            interner.intern_owned(i).unwrap();
+        }
+        assert_eq!(interner.remaining_handle_capacity(), Some(0));
+        assert!(matches!(
+            interner.intern_owned(256),
+            Err(InternerError::Overflow)
+        ));
+
+        let wide = Interner::<u32, RandomState, usize>::new(RandomState::new());
+        assert_eq!(wide.remaining_handle_capacity(), None);
+    }
+
+    #[test]
+    fn test_display_summary() {
+        let mut interner = Interner::<u32, RandomState>::with_capacity(RandomState::new(), 8);
+        interner.intern_copied(&[1, 2, 3]).unwrap();
+
+        let summary = alloc::format!("{interner}");
+        assert!(summary.starts_with("Interner(len=3, cap="));
+        assert!(summary.contains(&alloc::format!("cap={})", interner.capacity())));
+    }
+
     #[test]
     fn test_error_debug_impl() {
         // Covers: #[derive(Debug)] for InternerError
@@ -1060,6 +4129,60 @@ mod tests {
         assert_eq!(interner.resolve(h_b), Some(&"C".to_string()));
     }
 
+    #[test]
+    fn test_split_off_rebases_tail_handles() {
+        let mut interner = create_string_interner();
+        for s in ["a", "b", "c", "d", "e"] {
+            interner.intern_ref(s).unwrap();
+        }
+
+        let tail = interner.split_off(2);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(tail.len(), 3);
+
+        assert_eq!(interner.resolve(0).map(String::as_str), Some("a"));
+        assert_eq!(interner.resolve(1).map(String::as_str), Some("b"));
+        assert_eq!(interner.resolve(2), None);
+        assert_eq!(interner.lookup_handle("c").unwrap(), None);
+
+        // Old handle `h` in the tail is now `h - 2`.
+        for (old, s) in [(2, "c"), (3, "d"), (4, "e")] {
+            assert_eq!(tail.resolve(old - 2).map(String::as_str), Some(s));
+            assert_eq!(tail.lookup_handle(s).unwrap(), Some(old - 2));
+        }
+
+        // Both halves keep interning independently.
+        assert_eq!(interner.intern_ref("c").unwrap(), 2);
+        let mut tail = tail;
+        assert_eq!(tail.intern_ref("a").unwrap(), 3);
+
+        // Splitting at the end yields an empty interner.
+        assert!(interner.split_off(3).is_empty());
+    }
+
+    #[test]
+    fn test_split_off_keeps_settings() {
+        let mut interner =
+            Interner::<String, RandomState, u8>::new_with_overflow_handle(RandomState::new(), 0);
+        interner.max_items = 1_000;
+        for i in 0..100 {
+            interner.intern_owned(i.to_string()).unwrap();
+        }
+
+        let mut tail = interner.split_off(50);
+        assert_eq!(tail.max_items, 1_000);
+        // Once the 256 `u8` handles run out, new items saturate to the
+        // overflow handle instead of failing.
+        for i in 50..256 {
+            assert_eq!(
+                tail.intern_owned(alloc::format!("new {i}")).unwrap(),
+                i as u8
+            );
+        }
+        assert_eq!(tail.intern_owned("one too many".to_string()).unwrap(), 0);
+        assert_eq!(tail.len(), 256);
+    }
+
     #[test]
     fn test_remove_and_recover_handles() {
         let mut interner = create_string_interner();
@@ -1186,4 +4309,23 @@ mod tests {
             Some(&"C".to_string())
         );
     }
+
+    #[test]
+    fn test_export_sorted_by_remaps_handles() {
+        let mut interner = create_string_interner();
+        let h_pear = interner.intern_ref("pear").unwrap(); // 0
+        let h_apple = interner.intern_ref("apple").unwrap(); // 1
+        let h_fig = interner.intern_ref("fig").unwrap(); // 2
+
+        let (items, remap) = interner.export_sorted_by(Ord::cmp);
+
+        assert_eq!(items, alloc::vec!["apple", "fig", "pear"]);
+        assert_eq!(remap, alloc::vec![2, 0, 1]);
+
+        // Every old handle maps to the position of its item in the sorted output.
+        for (old, expected) in [(h_pear, "pear"), (h_apple, "apple"), (h_fig, "fig")] {
+            let new = remap[old as usize] as usize;
+            assert_eq!(items[new], expected);
+        }
+    }
 }
@@ -0,0 +1,434 @@
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use hashbrown::HashTable;
+
+use crate::FromRef;
+
+/// Marks the end of the recency list.
+const NIL: usize = usize::MAX;
+
+/// The most slots an [`LruInterner`] can have, limited by the `u32` slot
+/// numbers in its handles.
+const MAX_CAPACITY: usize = (u32::MAX as usize).saturating_add(1);
+
+/// A handle into an [`LruInterner`]: a slot number plus the generation of
+/// the value in that slot.
+///
+/// Evicting a value bumps the generation of its slot, so handles to evicted
+/// values stop resolving instead of aliasing whatever value reuses the slot.
+/// Generations are `u32` and wrap around, so a handle could only alias again
+/// after its slot has been reused 2^32 times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LruHandle {
+    slot: u32,
+    generation: u32,
+}
+
+impl LruHandle {
+    /// Returns the slot number.
+    #[must_use]
+    #[inline]
+    pub const fn slot(self) -> u32 {
+        self.slot
+    }
+
+    /// Returns the generation of the slot this handle was issued for.
+    #[must_use]
+    #[inline]
+    pub const fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+/// A stored value with its links in the recency list.
+#[derive(Clone)]
+struct Slot<T> {
+    value: T,
+    generation: u32,
+    /// The next more recently used slot, or `NIL`.
+    prev: usize,
+    /// The next less recently used slot, or `NIL`.
+    next: usize,
+}
+
+/// An interner bounded to a fixed number of values, evicting the least
+/// recently used one to make room.
+///
+/// This gives interning semantics to a hot-key cache: equal values share a
+/// handle while they are cached, and memory stays bounded. Interning a value
+/// marks it as most recently used; once `capacity` values are stored, a new
+/// value replaces the least recently used one.
+///
+/// # Handles
+///
+/// Handles are [`LruHandle`]s carrying a generation, so a handle to an
+/// evicted value resolves to `None` rather than to the value now in its
+/// slot. Interning an evicted value again stores it anew, under a fresh
+/// handle.
+///
+/// # Eviction Hook
+///
+/// [`with_on_evict`](Self::with_on_evict) registers a callback that receives
+/// the handle and the value of every evicted entry, e.g. to drop derived
+/// data.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::LruInterner;
+///
+/// let mut cache = LruInterner::<String, RandomState>::new(RandomState::new(), 2);
+/// let a = cache.intern_ref("a");
+/// let b = cache.intern_ref("b");
+/// cache.intern_ref("a"); // `b` is now the least recently used.
+///
+/// let c = cache.intern_ref("c");
+/// assert_eq!(cache.resolve(b), None);
+/// assert_eq!(cache.resolve(a).map(String::as_str), Some("a"));
+/// assert_eq!(cache.resolve(c).map(String::as_str), Some("c"));
+/// ```
+#[derive(Clone)]
+pub struct LruInterner<T, S, F = fn(LruHandle, T)>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    F: FnMut(LruHandle, T),
+{
+    slots: Vec<Slot<T>>,
+    table: HashTable<usize>,
+    hasher: S,
+    capacity: usize,
+    /// The most recently used slot, or `NIL`.
+    head: usize,
+    /// The least recently used slot, or `NIL`.
+    tail: usize,
+    on_evict: F,
+}
+
+impl<T, S, F> fmt::Debug for LruInterner<T, S, F>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    F: FnMut(LruHandle, T),
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruInterner")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, S> LruInterner<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Creates a new, empty interner holding at most `capacity` values.
+    ///
+    /// A capacity of `0` is treated as `1`, and capacities beyond 2^32 are
+    /// capped to it. Slots are allocated as they are first used.
+    #[must_use]
+    pub fn new(hasher: S, capacity: usize) -> Self {
+        Self {
+            slots: Vec::new(),
+            table: HashTable::new(),
+            hasher,
+            capacity: capacity.clamp(1, MAX_CAPACITY),
+            head: NIL,
+            tail: NIL,
+            on_evict: |_, _| {},
+        }
+    }
+}
+
+impl<T, S, F> LruInterner<T, S, F>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    F: FnMut(LruHandle, T),
+{
+    /// Replaces the eviction callback, which is called with the handle and
+    /// the value of every evicted entry.
+    ///
+    /// The callback runs after the new value has been stored, so it cannot
+    /// observe a half-updated interner; it has no access to the interner.
+    #[must_use]
+    pub fn with_on_evict<G>(self, on_evict: G) -> LruInterner<T, S, G>
+    where
+        G: FnMut(LruHandle, T),
+    {
+        LruInterner {
+            slots: self.slots,
+            table: self.table,
+            hasher: self.hasher,
+            capacity: self.capacity,
+            head: self.head,
+            tail: self.tail,
+            on_evict,
+        }
+    }
+
+    /// Returns the maximum number of values.
+    #[must_use]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of stored values.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if no value is stored.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Interns an owned value and marks it as most recently used, evicting
+    /// the least recently used value if the interner is full.
+    pub fn intern_owned(&mut self, item: T) -> LruHandle {
+        let hash = self.hasher.hash_one(&item);
+        if let Some(slot) = self.find(hash, &item) {
+            self.touch_slot(slot);
+            return self.handle(slot);
+        }
+        self.insert(hash, item)
+    }
+
+    /// Interns a borrowed value and marks it as most recently used, cloning
+    /// it only if it must be inserted.
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> LruHandle
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(item);
+        if let Some(slot) = self.find(hash, item) {
+            self.touch_slot(slot);
+            return self.handle(slot);
+        }
+        self.insert(hash, T::from_ref(item))
+    }
+
+    /// Returns the handle of `item` if it is cached, without changing its
+    /// recency.
+    #[must_use]
+    pub fn lookup_handle<Q>(&self, item: &Q) -> Option<LruHandle>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(self.hasher.hash_one(item), item)
+            .map(|slot| self.handle(slot))
+    }
+
+    /// Resolves a handle without changing the value's recency.
+    ///
+    /// Returns `None` if the value was evicted.
+    #[must_use]
+    pub fn resolve(&self, handle: LruHandle) -> Option<&T> {
+        self.live_slot(handle).map(|slot| &self.slots[slot].value)
+    }
+
+    /// Resolves a handle and marks the value as most recently used.
+    ///
+    /// Returns `None` if the value was evicted.
+    pub fn resolve_touch(&mut self, handle: LruHandle) -> Option<&T> {
+        let slot = self.live_slot(handle)?;
+        self.touch_slot(slot);
+        Some(&self.slots[slot].value)
+    }
+
+    /// Iterates over the stored values from most to least recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (LruHandle, &T)> {
+        let mut slot = self.head;
+        core::iter::from_fn(move || {
+            let entry = self.slots.get(slot)?;
+            let handle = self.handle(slot);
+            slot = entry.next;
+            Some((handle, &entry.value))
+        })
+    }
+
+    /// Returns the slot holding a value equal to `item`.
+    fn find<Q>(&self, hash: u64, item: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.table
+            .find(hash, |&slot| self.slots[slot].value.borrow() == item)
+            .copied()
+    }
+
+    /// Returns the slot of `handle` if it still holds the same value.
+    fn live_slot(&self, handle: LruHandle) -> Option<usize> {
+        let slot = usize::try_from(handle.slot).ok()?;
+        (self.slots.get(slot)?.generation == handle.generation).then_some(slot)
+    }
+
+    /// Returns the handle of the value in `slot`.
+    fn handle(&self, slot: usize) -> LruHandle {
+        LruHandle {
+            // `capacity` keeps every slot within `u32`.
+            slot: slot as u32,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// Stores a new value with the given hash, evicting if full.
+    fn insert(&mut self, hash: u64, item: T) -> LruHandle {
+        let (slot, evicted) = if self.slots.len() < self.capacity {
+            self.slots.push(Slot {
+                value: item,
+                generation: 0,
+                prev: NIL,
+                next: NIL,
+            });
+            (self.slots.len() - 1, None)
+        } else {
+            let slot = self.tail;
+            let old_hash = self.hasher.hash_one(&self.slots[slot].value);
+            if let Ok(entry) = self.table.find_entry(old_hash, |&s| s == slot) {
+                entry.remove();
+            }
+            self.unlink(slot);
+            let old = self.handle(slot);
+            let entry = &mut self.slots[slot];
+            entry.generation = entry.generation.wrapping_add(1);
+            (
+                slot,
+                Some((old, core::mem::replace(&mut entry.value, item))),
+            )
+        };
+
+        let Self {
+            slots,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.insert_unique(hash, slot, |&s| hasher.hash_one(&slots[s].value));
+        self.push_front(slot);
+
+        if let Some((handle, value)) = evicted {
+            (self.on_evict)(handle, value);
+        }
+        self.handle(slot)
+    }
+
+    /// Moves `slot` to the front of the recency list.
+    fn touch_slot(&mut self, slot: usize) {
+        if self.head != slot {
+            self.unlink(slot);
+            self.push_front(slot);
+        }
+    }
+
+    /// Removes `slot` from the recency list.
+    fn unlink(&mut self, slot: usize) {
+        let Slot { prev, next, .. } = self.slots[slot];
+        match prev {
+            NIL => self.head = next,
+            prev => self.slots[prev].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.slots[next].prev = prev,
+        }
+    }
+
+    /// Inserts an unlinked `slot` at the front of the recency list.
+    fn push_front(&mut self, slot: usize) {
+        let head = self.head;
+        self.slots[slot].prev = NIL;
+        self.slots[slot].next = head;
+        match head {
+            NIL => self.tail = slot,
+            head => self.slots[head].prev = slot,
+        }
+        self.head = slot;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::LruInterner;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut evicted = Vec::new();
+        let mut cache = LruInterner::<String, RandomState>::new(RandomState::new(), 3)
+            .with_on_evict(|_, value: String| evicted.push(value));
+        let a = cache.intern_ref("a");
+        let b = cache.intern_ref("b");
+        let c = cache.intern_ref("c");
+        assert_eq!(cache.len(), 3);
+
+        // Touch `a` by interning and `b` by resolving, leaving `c` oldest.
+        assert_eq!(cache.intern_ref("a"), a);
+        assert_eq!(cache.resolve_touch(b).map(String::as_str), Some("b"));
+        let d = cache.intern_ref("d");
+        assert_eq!(cache.resolve(c), None);
+        assert_eq!(cache.lookup_handle("c"), None);
+
+        // A plain `resolve` does not touch, so `a` goes next.
+        assert!(cache.resolve(a).is_some());
+        let e = cache.intern_ref("e");
+        assert_eq!(cache.resolve(a), None);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.capacity(), 3);
+
+        let order: Vec<&str> = cache.iter().map(|(_, v)| v.as_str()).collect();
+        assert_eq!(order, ["e", "d", "b"]);
+        assert_eq!(cache.lookup_handle("d"), Some(d));
+        assert_eq!(cache.lookup_handle("e"), Some(e));
+        drop(cache);
+        assert_eq!(evicted, ["c", "a"]);
+    }
+
+    #[test]
+    fn test_stale_handles_do_not_alias() {
+        let mut cache = LruInterner::<u32, RandomState>::new(RandomState::new(), 1);
+        let one = cache.intern_owned(1);
+        let two = cache.intern_owned(2);
+
+        // Both values used the same slot, in different generations.
+        assert_eq!(one.slot(), two.slot());
+        assert_ne!(one.generation(), two.generation());
+        assert_eq!(cache.resolve(one), None);
+        assert_eq!(cache.resolve(two), Some(&2));
+
+        // Re-interning an evicted value yields a fresh handle.
+        let again = cache.intern_owned(1);
+        assert_ne!(again, one);
+        assert_eq!(cache.resolve(again), Some(&1));
+        assert_eq!(cache.resolve(two), None);
+    }
+
+    #[test]
+    fn test_zero_capacity_holds_one() {
+        let mut cache = LruInterner::<u32, RandomState>::new(RandomState::new(), 0);
+        assert_eq!(cache.capacity(), 1);
+        let h = cache.intern_owned(5);
+        assert_eq!(cache.intern_owned(5), h);
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,169 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use core::{
+    hash::{BuildHasher, Hash},
+    mem::{size_of, size_of_val},
+};
+
+use malloc_size_of::{MallocConditionalSizeOf, MallocSizeOf, MallocSizeOfOps};
+
+use crate::{FrozenInterner, Interner, store::Store};
+
+/// Measures the storage and index of an interner, plus the heap data of every
+/// value through its own `MallocSizeOf` impl.
+impl<T, S, H> MallocSizeOf for Interner<T, S, H>
+where
+    T: Eq + Hash + MallocSizeOf,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        deep_size_of(&self.items, ops)
+    }
+}
+
+impl<T, S, H> MallocSizeOf for FrozenInterner<T, S, H>
+where
+    T: Eq + Hash + MallocSizeOf,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        deep_size_of(&self.items, ops)
+    }
+}
+
+/// Measures an interner of `Arc`s, counting each shared value only if `ops`
+/// has not seen it yet.
+///
+/// `Arc` deliberately has no `MallocSizeOf` impl, since the values are shared
+/// with every `Arc` handed out by [`resolve_arc`](Interner::resolve_arc).
+/// Measuring other structures holding those `Arc`s with the same `ops` then
+/// counts each value once overall. The `Arc` allocation itself is estimated
+/// as the value plus its two reference counts.
+///
+/// # Panics
+///
+/// Panics if `ops` was created without a `have_seen_ptr` operation.
+impl<T, S, H> MallocConditionalSizeOf for Interner<Arc<T>, S, H>
+where
+    T: Eq + Hash + MallocSizeOf + ?Sized,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn conditional_size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        shared_size_of(&self.items, ops)
+    }
+}
+
+/// Measures a frozen interner of `Arc`s like the `Interner` impl, counting
+/// each shared value only if `ops` has not seen it yet.
+impl<T, S, H> MallocConditionalSizeOf for FrozenInterner<Arc<T>, S, H>
+where
+    T: Eq + Hash + MallocSizeOf + ?Sized,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn conditional_size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        shared_size_of(&self.items, ops)
+    }
+}
+
+fn deep_size_of<T, S>(items: &Store<T, S>, ops: &mut MallocSizeOfOps) -> usize
+where
+    T: MallocSizeOf,
+{
+    items.shallow_size_of(ops) + items.iter().map(|item| item.size_of(ops)).sum::<usize>()
+}
+
+fn shared_size_of<T, S>(items: &Store<Arc<T>, S>, ops: &mut MallocSizeOfOps) -> usize
+where
+    T: MallocSizeOf + ?Sized,
+{
+    let mut size = items.shallow_size_of(ops);
+    for item in items {
+        if !ops.have_seen_ptr(Arc::as_ptr(item).cast::<u8>()) {
+            // The two reference counts precede the value in the allocation.
+            size += 2 * size_of::<usize>() + size_of_val(&**item) + (**item).size_of(ops);
+        }
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, collections::BTreeSet, string::String, sync::Arc};
+    use core::{ffi::c_void, mem::size_of};
+
+    use ahash::RandomState;
+    use malloc_size_of::{MallocConditionalSizeOf as _, MallocSizeOf as _, MallocSizeOfOps};
+
+    use crate::{ArcInterner, Interner};
+
+    /// Pretends that every allocation is 100 bytes.
+    extern "C" fn mock_size_of(_: *const c_void) -> usize {
+        100
+    }
+
+    fn mock_ops() -> MallocSizeOfOps {
+        let mut seen = BTreeSet::new();
+        MallocSizeOfOps::new(
+            mock_size_of,
+            None,
+            Some(Box::new(move |ptr: *const c_void| !seen.insert(ptr.addr()))),
+        )
+    }
+
+    #[test]
+    fn test_counts_storage_and_values() {
+        let mut ops = mock_ops();
+        let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+        assert_eq!(interner.size_of(&mut ops), 0);
+
+        interner.intern_ref("a").unwrap();
+        interner.intern_ref("bb").unwrap();
+        interner.intern_ref("a").unwrap();
+
+        // The values are one allocation.
+        let shallow = interner.items.shallow_size_of(&mut ops);
+        assert!(shallow > 100);
+        assert_eq!(interner.size_of(&mut ops), shallow + 2 * 100);
+
+        let frozen = interner.freeze();
+        assert_eq!(frozen.size_of(&mut ops), shallow + 2 * 100);
+    }
+
+    #[test]
+    fn test_counts_shared_arcs_once() {
+        let mut ops = mock_ops();
+        let mut interner = ArcInterner::<str, RandomState>::new(RandomState::new());
+        interner.intern_ref("a").unwrap();
+        let h = interner.intern_ref("bb").unwrap();
+        let arc_overhead = 2 * size_of::<usize>();
+
+        let shallow = interner.items.shallow_size_of(&mut ops);
+        let first = interner.conditional_size_of(&mut ops);
+        assert_eq!(first, shallow + 2 * arc_overhead + "a".len() + "bb".len());
+
+        // Every value was seen, so measuring again, or measuring a frozen
+        // copy sharing the same `Arc`s, only counts the storage.
+        assert_eq!(interner.conditional_size_of(&mut ops), shallow);
+        let frozen = interner.clone().freeze();
+        let frozen_shallow = frozen.items.shallow_size_of(&mut ops);
+        assert_eq!(frozen.conditional_size_of(&mut ops), frozen_shallow);
+
+        // An `Arc` seen elsewhere first is not counted by the interner.
+        let mut ops = mock_ops();
+        let resolved = interner.resolve_arc(h).unwrap();
+        assert!(!ops.have_seen_ptr(Arc::as_ptr(&resolved).cast::<u8>()));
+        assert_eq!(
+            interner.conditional_size_of(&mut ops),
+            shallow + arc_overhead + "a".len()
+        );
+    }
+}
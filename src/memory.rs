@@ -0,0 +1,219 @@
+extern crate alloc;
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{
+    hash::{BuildHasher, Hash},
+    mem,
+};
+#[cfg(feature = "std")]
+use std::{ffi::OsString, path::PathBuf};
+
+use crate::{HashableF32, HashableF64, HashableOrderedF32, HashableOrderedF64, Interner};
+
+/// Values that can report the heap memory they own.
+///
+/// Only memory owned through pointers counts, not the inline size of the
+/// value itself. Implement it for custom item types to use
+/// [`Interner::memory_usage`]; types without heap data return `0`.
+pub trait HeapSize {
+    /// Returns the number of heap bytes owned by this value.
+    fn heap_size(&self) -> usize;
+}
+
+/// Implements `HeapSize` as zero for types stored entirely inline.
+macro_rules! impl_inline_heap_size {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl HeapSize for $ty {
+                #[inline]
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_inline_heap_size!(
+    (),
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    bool,
+    char,
+    HashableF32,
+    HashableF64,
+    HashableOrderedF32,
+    HashableOrderedF64,
+);
+
+impl HeapSize for String {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl HeapSize for Box<str> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Box<[T]> {
+    fn heap_size(&self) -> usize {
+        mem::size_of_val::<[T]>(self) + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+#[cfg(feature = "std")]
+impl HeapSize for PathBuf {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+#[cfg(feature = "std")]
+impl HeapSize for OsString {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+/// An estimate of the memory used by an interner, from
+/// [`Interner::memory_usage`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Bytes allocated by the interner itself: the inline parts of all value
+    /// slots, including unused capacity, and the hash index.
+    pub table_bytes: usize,
+    /// Heap bytes owned by the stored values, as reported by [`HeapSize`].
+    pub value_bytes: usize,
+    /// Number of stored items.
+    pub len: usize,
+    /// Number of items that fit without growing.
+    pub capacity: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the total number of bytes, `table_bytes + value_bytes`.
+    #[must_use]
+    #[inline]
+    pub const fn total_bytes(&self) -> usize {
+        self.table_bytes + self.value_bytes
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + HeapSize,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Estimates the heap memory used by the interner and its values.
+    ///
+    /// This is an O(n) scan over all values. Allocator overhead and the size
+    /// of the `Interner` struct itself are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// interner.intern_owned(String::from("hello")).unwrap();
+    ///
+    /// let usage = interner.memory_usage();
+    /// assert_eq!(usage.value_bytes, 5);
+    /// assert_eq!(usage.len, 1);
+    /// ```
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            table_bytes: self.items.allocation_size(),
+            value_bytes: self.items.iter().map(T::heap_size).sum(),
+            len: self.items.len(),
+            capacity: self.items.capacity(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        boxed::Box,
+        string::{String, ToString as _},
+        vec,
+    };
+
+    use ahash::RandomState;
+
+    use super::HeapSize;
+    use crate::{HashableF64, Interner};
+
+    #[test]
+    fn test_memory_usage_grows_and_clears() {
+        let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+        let empty = interner.memory_usage();
+        assert_eq!(empty.total_bytes(), 0);
+
+        let mut last = empty;
+        for i in 0..200 {
+            interner.intern_owned(i.to_string()).unwrap();
+            let usage = interner.memory_usage();
+            assert!(usage.value_bytes > last.value_bytes);
+            assert!(usage.table_bytes >= last.table_bytes);
+            assert!(usage.capacity >= usage.len);
+            assert_eq!(usage.len, i + 1);
+            last = usage;
+        }
+        assert!(last.table_bytes >= 200 * core::mem::size_of::<String>());
+
+        interner.clear();
+        let cleared = interner.memory_usage();
+        assert_eq!(cleared.value_bytes, 0);
+        assert_eq!(cleared.len, 0);
+    }
+
+    #[test]
+    fn test_heap_sizes() {
+        assert_eq!(42u32.heap_size(), 0);
+        assert_eq!(HashableF64(1.5).heap_size(), 0);
+        assert_eq!(Box::<str>::from("abc").heap_size(), 3);
+
+        let mut s = String::with_capacity(16);
+        s.push('x');
+        assert_eq!(s.heap_size(), 16);
+
+        let nested = vec![String::from("ab"), String::from("cde")];
+        assert_eq!(
+            nested.heap_size(),
+            2 * core::mem::size_of::<String>() + nested[0].capacity() + nested[1].capacity()
+        );
+        assert_eq!(
+            Box::<[u16]>::from(&[1, 2, 3][..]).heap_size(),
+            3 * core::mem::size_of::<u16>()
+        );
+    }
+}
@@ -0,0 +1,211 @@
+extern crate alloc;
+
+use alloc::{format, string::ToString as _, vec::Vec};
+use core::hash::{BuildHasher, Hash};
+
+use metrics::{Counter, Gauge, Histogram, Label};
+
+use crate::{HeapSize, Interner};
+
+/// The metric handles of an [`Interner`] configured with
+/// [`Interner::with_metrics`].
+#[derive(Clone)]
+pub(crate) struct Metrics<T> {
+    hits: Counter,
+    misses: Counter,
+    len: Gauge,
+    value_bytes: Gauge,
+    value_lengths: Histogram,
+    /// `HeapSize::heap_size` of `T`, captured where the bound is known.
+    heap_size: fn(&T) -> usize,
+}
+
+impl<T> Metrics<T> {
+    /// Counts an intern call that found an existing item.
+    #[inline]
+    pub(crate) fn record_hit(&self) {
+        self.hits.increment(1);
+    }
+
+    /// Counts the insertion of `value`, after which the interner holds `len`
+    /// items.
+    #[inline]
+    pub(crate) fn record_insert(&self, value: &T, len: usize) {
+        let size = (self.heap_size)(value);
+        self.misses.increment(1);
+        self.len.set(len as f64);
+        self.value_bytes.increment(size as f64);
+        self.value_lengths.record(size as f64);
+    }
+
+    /// Sets the gauges from scratch, after items were removed.
+    pub(crate) fn refresh(&self, values: &[T]) {
+        self.len.set(values.len() as f64);
+        let bytes: usize = values.iter().map(self.heap_size).sum();
+        self.value_bytes.set(bytes as f64);
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + HeapSize,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Reports this interner through the `metrics` facade, with every metric
+    /// name starting with `prefix`.
+    ///
+    /// The following metrics are registered with the installed recorder:
+    ///
+    /// - `{prefix}_intern_hits_total`: counter of intern calls that found an
+    ///   existing item.
+    /// - `{prefix}_intern_misses_total`: counter of inserted items.
+    /// - `{prefix}_interner_len`: gauge of the number of items.
+    /// - `{prefix}_interner_value_bytes`: gauge of the heap bytes of all
+    ///   items, as reported by [`HeapSize`].
+    /// - `{prefix}_interned_value_length`: histogram of the heap bytes of each
+    ///   inserted item.
+    ///
+    /// Updates go through the registered handles, so they cost a few atomic
+    /// operations per call. Removing items recomputes the byte gauge, an O(n)
+    /// scan. Interners derived from this one, e.g. by `split_off`,
+    /// `into_boxed`, or a freeze and thaw, do not report.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner =
+    ///     Interner::<String, RandomState>::new(RandomState::new()).with_metrics("symbols");
+    /// interner.intern_ref("hello").unwrap();
+    /// ```
+    #[must_use]
+    pub fn with_metrics(self, prefix: &str) -> Self {
+        self.with_metrics_labels(prefix, &[])
+    }
+
+    /// Like [`with_metrics`](Self::with_metrics), but attaches `labels` as
+    /// key-value pairs to every metric, e.g. to tell several interners with
+    /// the same prefix apart.
+    #[must_use]
+    pub fn with_metrics_labels(mut self, prefix: &str, labels: &[(&str, &str)]) -> Self {
+        let labels: Vec<Label> = labels
+            .iter()
+            .map(|&(key, value)| Label::new(key.to_string(), value.to_string()))
+            .collect();
+        let metrics = Metrics {
+            hits: metrics::counter!(format!("{prefix}_intern_hits_total"), labels.clone()),
+            misses: metrics::counter!(format!("{prefix}_intern_misses_total"), labels.clone()),
+            len: metrics::gauge!(format!("{prefix}_interner_len"), labels.clone()),
+            value_bytes: metrics::gauge!(format!("{prefix}_interner_value_bytes"), labels.clone()),
+            value_lengths: metrics::histogram!(format!("{prefix}_interned_value_length"), labels),
+            heap_size: T::heap_size,
+        };
+        metrics.refresh(self.items.as_slice());
+        self.metrics = Some(metrics);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::{
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+    use std::collections::HashMap;
+
+    use ahash::RandomState;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+
+    use crate::Interner;
+
+    /// Metric labels and values, keyed by metric name.
+    type Metrics = HashMap<String, (Vec<(String, String)>, DebugValue)>;
+
+    /// Takes a snapshot, keyed by metric name. Counters and histograms report
+    /// the changes since the previous snapshot.
+    fn snapshot(snapshotter: &Snapshotter) -> Metrics {
+        snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let key = key.key();
+                let labels = key
+                    .labels()
+                    .map(|label| (label.key().to_string(), label.value().to_string()))
+                    .collect();
+                (key.name().to_string(), (labels, value))
+            })
+            .collect()
+    }
+
+    fn value<'a>(snapshot: &'a Metrics, name: &str) -> &'a DebugValue {
+        &snapshot[name].1
+    }
+
+    #[test]
+    fn test_metrics_track_interning() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        metrics::with_local_recorder(&recorder, || {
+            let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+            interner.intern_ref("ab").unwrap();
+            let mut interner = interner.with_metrics_labels("test", &[("interner", "symbols")]);
+
+            interner.intern_ref("cde").unwrap();
+            interner.intern_owned("fghij".to_string()).unwrap();
+            interner.intern_ref("ab").unwrap();
+            interner.intern_ref("cde").unwrap();
+
+            let first = snapshot(&snapshotter);
+            assert_eq!(
+                first["test_intern_hits_total"].0,
+                [("interner".to_string(), "symbols".to_string())]
+            );
+            assert_eq!(
+                value(&first, "test_intern_hits_total"),
+                &DebugValue::Counter(2)
+            );
+            assert_eq!(
+                value(&first, "test_intern_misses_total"),
+                &DebugValue::Counter(2)
+            );
+            assert_eq!(
+                value(&first, "test_interner_len"),
+                &DebugValue::Gauge(3.0.into())
+            );
+            assert_eq!(
+                value(&first, "test_interner_value_bytes"),
+                &DebugValue::Gauge(10.0.into())
+            );
+            assert_eq!(
+                value(&first, "test_interned_value_length"),
+                &DebugValue::Histogram(alloc::vec![3.0.into(), 5.0.into()])
+            );
+
+            interner.remove("fghij");
+            let second = snapshot(&snapshotter);
+            assert_eq!(
+                value(&second, "test_interner_len"),
+                &DebugValue::Gauge(2.0.into())
+            );
+            assert_eq!(
+                value(&second, "test_interner_value_bytes"),
+                &DebugValue::Gauge(5.0.into())
+            );
+            assert_eq!(
+                value(&second, "test_intern_misses_total"),
+                &DebugValue::Counter(0)
+            );
+        });
+    }
+}
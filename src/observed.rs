@@ -0,0 +1,246 @@
+use alloc::borrow::{Cow, ToOwned};
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{FromRef, Interner, InternerError, IntoInternable};
+
+/// An interner that calls an observer for every newly inserted item.
+///
+/// Derived indexes (a sorted view, a trie, metrics) that mirror an interner
+/// need to learn about each new item exactly once. An `ObservedInterner`
+/// calls its `on_insert` callback with the handle and the stored value
+/// right after an item is inserted, and never for an item that was already
+/// present. Errors call nothing.
+///
+/// Reading the interner goes through [`interner`](Self::interner); all
+/// insertions go through the wrapper, so the callback sees every new item.
+///
+/// # Reentrancy
+///
+/// The callback runs while the interner is borrowed mutably, so it cannot
+/// intern or even read through the wrapper; the borrow checker rejects
+/// closures that try. Everything it needs about the new item is passed in.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::{Interner, ObservedInterner};
+///
+/// let mut seen = Vec::new();
+/// let mut interner = ObservedInterner::new(
+///     Interner::<String, RandomState>::new(RandomState::new()),
+///     |handle: u32, value: &String| seen.push((handle, value.clone())),
+/// );
+///
+/// interner.intern_ref("a").unwrap();
+/// interner.intern_ref("a").unwrap();
+/// interner.intern_ref("b").unwrap();
+/// drop(interner);
+///
+/// assert_eq!(seen, [(0, "a".to_string()), (1, "b".to_string())]);
+/// ```
+pub struct ObservedInterner<T, S, F, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    F: FnMut(H, &T),
+{
+    inner: Interner<T, S, H>,
+    on_insert: F,
+}
+
+impl<T, S, F, H> fmt::Debug for ObservedInterner<T, S, F, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    F: FnMut(H, &T),
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservedInterner")
+            .field("len", &self.inner.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, S, F, H> ObservedInterner<T, S, F, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    F: FnMut(H, &T),
+{
+    /// Wraps `interner`, calling `on_insert` for each item inserted from now
+    /// on. Items already in `interner` are not reported.
+    #[must_use]
+    pub const fn new(interner: Interner<T, S, H>, on_insert: F) -> Self {
+        Self {
+            inner: interner,
+            on_insert,
+        }
+    }
+
+    /// Replaces the callback, returning the previous one.
+    pub const fn set_on_insert(&mut self, on_insert: F) -> F {
+        core::mem::replace(&mut self.on_insert, on_insert)
+    }
+
+    /// Returns the wrapped interner, for resolving and other reads.
+    #[must_use]
+    #[inline]
+    pub const fn interner(&self) -> &Interner<T, S, H> {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the inner [`Interner`] and the
+    /// callback.
+    #[must_use]
+    #[inline]
+    pub fn into_parts(self) -> (Interner<T, S, H>, F) {
+        (self.inner, self.on_insert)
+    }
+
+    /// Interns an owned value; see [`Interner::intern_owned`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `Interner::intern_owned`.
+    #[track_caller]
+    pub fn intern_owned(&mut self, item: T) -> Result<H, InternerError> {
+        self.observe(|inner| inner.intern_owned(item))
+    }
+
+    /// Interns a borrowed value; see [`Interner::intern_ref`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `Interner::intern_ref`.
+    #[track_caller]
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.observe(|inner| inner.intern_ref(item))
+    }
+
+    /// Interns a `Cow`; see [`Interner::intern_cow`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `Interner::intern_cow`.
+    #[track_caller]
+    pub fn intern_cow<Q>(&mut self, item: Cow<'_, Q>) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + Clone,
+        Q: ToOwned<Owned = T> + Hash + Eq + ?Sized,
+    {
+        self.observe(|inner| inner.intern_cow(item))
+    }
+
+    /// Interns an owned value, a reference, or a `Cow`; see
+    /// [`Interner::intern`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the `intern_*` method used.
+    #[track_caller]
+    pub fn intern<V, M>(&mut self, value: V) -> Result<H, InternerError>
+    where
+        V: IntoInternable<T, M>,
+    {
+        self.observe(|inner| inner.intern(value))
+    }
+
+    /// Runs an intern operation and reports the item if it was inserted.
+    #[inline]
+    fn observe(
+        &mut self,
+        op: impl FnOnce(&mut Interner<T, S, H>) -> Result<H, InternerError>,
+    ) -> Result<H, InternerError> {
+        let len = self.inner.len();
+        let handle = op(&mut self.inner)?;
+        if self.inner.len() > len
+            && let Some(item) = self.inner.resolve(handle)
+        {
+            (self.on_insert)(handle, item);
+        }
+        Ok(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        borrow::Cow,
+        boxed::Box,
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+    use core::cell::Cell;
+
+    use ahash::RandomState;
+
+    use super::ObservedInterner;
+    use crate::{Interner, InternerError};
+
+    #[test]
+    fn test_reports_unique_items_in_order() {
+        let mut seen = Vec::new();
+        let mut interner = ObservedInterner::new(
+            Interner::<String, RandomState>::new(RandomState::new()),
+            |handle: u32, value: &String| seen.push((handle, value.clone())),
+        );
+
+        let stream = ["x", "y", "x", "z", "y", "x", "w"];
+        for (i, s) in stream.into_iter().enumerate() {
+            match i % 3 {
+                0 => interner.intern_ref(s).unwrap(),
+                1 => interner.intern_owned(s.to_string()).unwrap(),
+                _ => interner.intern_cow(Cow::Borrowed(s)).unwrap(),
+            };
+        }
+        interner.intern("v").unwrap();
+        let (inner, _) = interner.into_parts();
+
+        let expected: Vec<(u32, String)> = inner
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (u32::try_from(i).unwrap(), s.clone()))
+            .collect();
+        assert_eq!(seen, expected);
+        assert_eq!(
+            inner.as_values_slice(),
+            ["x", "y", "z", "w", "v"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn test_errors_and_replaced_callback() {
+        let (first, second) = (Cell::new(0), Cell::new(0));
+        let mut interner = ObservedInterner::new(
+            Interner::<u32, RandomState>::new_with_limit(RandomState::new(), 2),
+            Box::new(|_, _: &u32| first.set(first.get() + 1)) as Box<dyn FnMut(u32, &u32)>,
+        );
+        interner.intern_owned(1).unwrap();
+        interner.intern_owned(1).unwrap();
+
+        let _ = interner.set_on_insert(Box::new(|_, _| second.set(second.get() + 1)));
+        interner.intern_owned(2).unwrap();
+        assert!(matches!(
+            interner.intern_owned(3),
+            Err(InternerError::LimitReached)
+        ));
+        assert_eq!(interner.interner().len(), 2);
+        assert_eq!((first.get(), second.get()), (1, 1));
+    }
+}
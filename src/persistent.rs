@@ -0,0 +1,446 @@
+use alloc::boxed::Box;
+use core::{fmt, hash::BuildHasher};
+use std::{
+    collections::hash_map::RandomState,
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use crate::Interner;
+
+/// The bytes every log file starts with: a magic tag and the format version.
+const HEADER: &[u8; 12] = b"XGXINTRN\x01\0\0\0";
+
+/// The size of a record header: the length and the checksum of the string.
+const RECORD_HEADER_LEN: usize = 8;
+
+/// A string interner backed by an append-only log file, so interned strings
+/// and their handles survive restarts.
+///
+/// Opening a log replays it into memory, which is much faster than
+/// rebuilding a large dictionary from its source. Every newly interned
+/// string is appended to the log; handles are assigned in log order, so a
+/// string keeps its handle across every reopen.
+///
+/// All strings are also held in memory, so resolving and lookups never touch
+/// the file.
+///
+/// # File Format
+///
+/// The file is a 12-byte header followed by one record per string: its
+/// length as a little-endian `u32`, an FNV-1a checksum of its bytes as a
+/// little-endian `u32`, and the UTF-8 bytes themselves.
+///
+/// # Crash Safety
+///
+/// Appends are buffered; [`flush`](Self::flush) writes them out and syncs
+/// the file. After a crash, the log holds every string interned before the
+/// last successful `flush`, and possibly some interned after it, each
+/// either fully written or absent. A record cut short by the crash (or
+/// damaged otherwise) fails its length or checksum check when the log is
+/// opened; it is dropped together with everything after it, and the file is
+/// truncated to the last intact record. A file holding only the start of
+/// the header, left by a crash while the log was created, is opened as a
+/// new, empty log.
+///
+/// Dropping the interner writes out the buffer, but ignores errors and does
+/// not sync; call `flush` to know that everything is stored.
+///
+/// # Examples
+///
+/// ```
+/// use xgx_intern::PersistentInterner;
+///
+/// let path = std::env::temp_dir().join(format!("xgx-doc-{}.log", std::process::id()));
+/// # let _ = std::fs::remove_file(&path);
+///
+/// let mut interner = PersistentInterner::open(&path).unwrap();
+/// let h = interner.intern("hello").unwrap();
+/// interner.flush().unwrap();
+/// drop(interner);
+///
+/// let interner = PersistentInterner::open(&path).unwrap();
+/// assert_eq!(interner.resolve(h), Some("hello"));
+/// assert_eq!(interner.lookup("hello"), Some(h));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct PersistentInterner<S = RandomState>
+where
+    S: BuildHasher,
+{
+    interner: Interner<Box<str>, S>,
+    log: BufWriter<File>,
+    /// Set once an append fails, as the log may then end in a partial record.
+    failed: bool,
+}
+
+impl<S> fmt::Debug for PersistentInterner<S>
+where
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentInterner")
+            .field("len", &self.interner.len())
+            .field("failed", &self.failed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PersistentInterner {
+    /// Opens the log at `path`, creating it if it does not exist, and loads
+    /// the strings it holds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened, read, or truncated,
+    /// or with `io::ErrorKind::InvalidData` if it is not a log of this
+    /// format or shrinks while it is read.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with_hasher(path, RandomState::new())
+    }
+}
+
+impl<S> PersistentInterner<S>
+where
+    S: BuildHasher,
+{
+    /// Opens the log at `path` like [`open`](PersistentInterner::open),
+    /// hashing with `hasher`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `open`.
+    pub fn open_with_hasher(path: impl AsRef<Path>, hasher: S) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let mut interner = Interner::new(hasher);
+        let file_len = file.metadata()?.len();
+
+        let mut reader = BufReader::new(&mut file);
+        let mut header = [0; HEADER.len()];
+        let valid_len = match read_full(&mut reader, &mut header)? {
+            // A crash while creating the log can leave part of the header.
+            n if n < HEADER.len() && header[..n] == HEADER[..n] => 0,
+            n if n == HEADER.len() && header == *HEADER => {
+                let records_len = file_len - HEADER.len() as u64;
+                HEADER.len() as u64 + replay(&mut reader, records_len, &mut interner)?
+            }
+            _ => return Err(invalid_data("not an interner log")),
+        };
+        drop(reader);
+
+        // Drop a damaged tail so new records follow the last intact one.
+        file.set_len(valid_len)?;
+        file.seek(SeekFrom::Start(valid_len))?;
+        if valid_len == 0 {
+            file.write_all(HEADER)?;
+        }
+        Ok(Self {
+            interner,
+            log: BufWriter::new(file),
+            failed: false,
+        })
+    }
+
+    /// Interns a string, appending it to the log if it is new.
+    ///
+    /// The record is buffered; call [`flush`](Self::flush) to store it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if appending fails, or if the `u32` handle space is
+    /// exhausted. After a failed append, interning new strings keeps failing,
+    /// as the log may end in a partial record; reopen the log to recover.
+    pub fn intern(&mut self, value: &str) -> io::Result<u32> {
+        if let Some(handle) = self.lookup(value) {
+            return Ok(handle);
+        }
+        if self.failed {
+            return Err(io::Error::other("an earlier append to the log failed"));
+        }
+        let len = u32::try_from(value.len()).map_err(|_| invalid_data("string too long"))?;
+        // Fail before appending, so the log never holds a record without a
+        // handle.
+        self.interner.next_handle().map_err(io::Error::other)?;
+        let mut record = [0; RECORD_HEADER_LEN];
+        record[..4].copy_from_slice(&len.to_le_bytes());
+        record[4..].copy_from_slice(&checksum(value.as_bytes()).to_le_bytes());
+
+        let appended = self
+            .log
+            .write_all(&record)
+            .and_then(|()| self.log.write_all(value.as_bytes()));
+        if let Err(e) = appended {
+            self.failed = true;
+            return Err(e);
+        }
+        self.interner.intern_ref(value).map_err(io::Error::other)
+    }
+
+    /// Resolves a handle to its string.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: u32) -> Option<&str> {
+        self.interner.resolve(handle).map(|s| &**s)
+    }
+
+    /// Returns the handle of `value` if it has been interned.
+    #[must_use]
+    #[inline]
+    pub fn lookup(&self, value: &str) -> Option<u32> {
+        self.interner.lookup_handle(value).ok().flatten()
+    }
+
+    /// Writes out all buffered records and syncs the file to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing or syncing fails.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.log.flush()?;
+        self.log.get_ref().sync_data()
+    }
+
+    /// Returns the number of interned strings.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interner.len()
+    }
+
+    /// Returns `true` if no string has been interned.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interner.is_empty()
+    }
+
+    /// Returns the in-memory interner, for everything else that reads it.
+    #[must_use]
+    #[inline]
+    pub const fn interner(&self) -> &Interner<Box<str>, S> {
+        &self.interner
+    }
+}
+
+/// Interns every intact record among the `records_len` bytes left in the
+/// file, returning the number of bytes they span.
+fn replay<S>(
+    reader: &mut impl Read,
+    records_len: u64,
+    interner: &mut Interner<Box<str>, S>,
+) -> io::Result<u64>
+where
+    S: BuildHasher,
+{
+    let mut valid_len = 0;
+    let mut header = [0; RECORD_HEADER_LEN];
+    let mut bytes = alloc::vec::Vec::new();
+    loop {
+        if read_full(reader, &mut header)? < RECORD_HEADER_LEN {
+            return Ok(valid_len);
+        }
+        let [l0, l1, l2, l3, c0, c1, c2, c3] = header;
+        let len = u32::from_le_bytes([l0, l1, l2, l3]) as usize;
+        // A damaged length field must not make us allocate more than the
+        // file holds.
+        let remaining = records_len
+            .checked_sub(valid_len + RECORD_HEADER_LEN as u64)
+            .ok_or_else(|| invalid_data("interner log changed while opening"))?;
+        if len as u64 > remaining {
+            return Ok(valid_len);
+        }
+        bytes.resize(len, 0);
+        if read_full(reader, &mut bytes)? < len
+            || checksum(&bytes) != u32::from_le_bytes([c0, c1, c2, c3])
+        {
+            return Ok(valid_len);
+        }
+        let Ok(value) = core::str::from_utf8(&bytes) else {
+            return Ok(valid_len);
+        };
+        let expected = interner.len();
+        let handle = interner.intern_ref(value).map_err(io::Error::other)?;
+        if handle as usize != expected {
+            return Err(invalid_data("duplicate string in interner log"));
+        }
+        valid_len += (RECORD_HEADER_LEN + len) as u64;
+    }
+}
+
+/// Reads until `buf` is full or the input ends, returning the bytes read.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Computes the 32-bit FNV-1a hash of `bytes`.
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &b| {
+        (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+    })
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+    use std::{
+        fs::{self, OpenOptions},
+        path::PathBuf,
+    };
+
+    use ahash::RandomState;
+
+    use super::PersistentInterner;
+
+    /// Returns a fresh path in the temporary directory.
+    fn temp_log(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("xgx-intern-{}-{name}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_reopen_keeps_handles() {
+        let path = temp_log("reopen");
+        let words = ["alpha", "beta", "", "gamma", "beta", "δέλτα"];
+
+        let mut interner = PersistentInterner::open(&path).unwrap();
+        let handles: Vec<u32> = words.iter().map(|w| interner.intern(w).unwrap()).collect();
+        assert_eq!(handles, [0, 1, 2, 3, 1, 4]);
+        interner.flush().unwrap();
+        drop(interner);
+
+        let mut interner = PersistentInterner::open_with_hasher(&path, RandomState::new()).unwrap();
+        assert_eq!(interner.len(), 5);
+        for (word, &h) in words.iter().zip(&handles) {
+            assert_eq!(interner.resolve(h), Some(*word));
+            assert_eq!(interner.lookup(word), Some(h));
+        }
+        assert_eq!(interner.lookup("epsilon"), None);
+        assert_eq!(interner.intern("epsilon").unwrap(), 5);
+        // Dropping writes out the buffer.
+        drop(interner);
+
+        let interner = PersistentInterner::open(&path).unwrap();
+        assert_eq!(interner.lookup("epsilon"), Some(5));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_truncated_tail_is_dropped() {
+        let path = temp_log("truncated");
+        let mut interner = PersistentInterner::open(&path).unwrap();
+        interner.intern("kept").unwrap();
+        interner.intern("torn").unwrap();
+        interner.flush().unwrap();
+        drop(interner);
+
+        // Cut the last record short, as a crash mid-write would.
+        let len = fs::metadata(&path).unwrap().len();
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(len - 2).unwrap();
+        drop(file);
+
+        let mut interner = PersistentInterner::open(&path).unwrap();
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.resolve(0), Some("kept"));
+        assert_eq!(interner.lookup("torn"), None);
+
+        // New records follow the last intact one.
+        assert_eq!(interner.intern("next").unwrap(), 1);
+        interner.flush().unwrap();
+        drop(interner);
+        let interner = PersistentInterner::open(&path).unwrap();
+        assert_eq!(interner.resolve(1), Some("next"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_oversized_length_is_dropped() {
+        let path = temp_log("oversized");
+        let mut interner = PersistentInterner::open(&path).unwrap();
+        interner.intern("kept").unwrap();
+        interner.intern("damaged").unwrap();
+        interner.flush().unwrap();
+        drop(interner);
+
+        // Claim the last record is 4 GiB long.
+        let mut bytes = fs::read(&path).unwrap();
+        let record = bytes.len() - "damaged".len() - 8;
+        bytes[record..record + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+
+        let interner = PersistentInterner::open(&path).unwrap();
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.lookup("damaged"), None);
+        assert_eq!(
+            fs::metadata(&path).unwrap().len(),
+            (bytes.len() - "damaged".len() - 8) as u64
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_full_interner_appends_nothing() {
+        let path = temp_log("full");
+        let mut interner = PersistentInterner::open(&path).unwrap();
+        interner.intern("kept").unwrap();
+        interner.interner.max_items = 1;
+        interner.flush().unwrap();
+        let len = fs::metadata(&path).unwrap().len();
+
+        assert!(interner.intern("rejected").is_err());
+        assert_eq!(interner.intern("kept").unwrap(), 0);
+        interner.flush().unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), len);
+        drop(interner);
+
+        let interner = PersistentInterner::open(&path).unwrap();
+        assert_eq!(interner.len(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_partial_header_is_a_fresh_log() {
+        let path = temp_log("partial-header");
+        fs::write(&path, &super::HEADER[..5]).unwrap();
+
+        let mut interner = PersistentInterner::open(&path).unwrap();
+        assert!(interner.is_empty());
+        assert_eq!(interner.intern("first").unwrap(), 0);
+        interner.flush().unwrap();
+        drop(interner);
+
+        let interner = PersistentInterner::open(&path).unwrap();
+        assert_eq!(interner.resolve(0), Some("first"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_foreign_file() {
+        let path = temp_log("foreign");
+        fs::write(&path, b"definitely not a log").unwrap();
+        let err = PersistentInterner::open(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+}
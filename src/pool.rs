@@ -0,0 +1,440 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{FromRef, Interner, InternerError};
+
+/// The merged interner and the per-local remaps returned by
+/// [`InternerPool::merge`].
+type Merged<T, S, H> = (Interner<T, S, H>, Vec<Remap<H>>);
+
+/// A handle translation table from one [`LocalInterner`] into the merged
+/// interner produced by [`InternerPool::merge`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Remap<H> {
+    map: Vec<H>,
+}
+
+impl<H> Remap<H>
+where
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Translates a handle issued by the local interner into the merged
+    /// interner's handle.
+    ///
+    /// Returns `None` if `local` was not issued by that local interner.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, local: H) -> Option<H> {
+        let idx = usize::try_from(local).ok()?;
+        self.map.get(idx).copied()
+    }
+
+    /// Rewrites every handle in `handles` in place.
+    ///
+    /// Handles that were not issued by the local interner are left unchanged.
+    pub fn apply(&self, handles: &mut [H]) {
+        for handle in handles {
+            if let Some(merged) = self.get(*handle) {
+                *handle = merged;
+            }
+        }
+    }
+
+    /// Returns the table as a slice, indexed by the local handle's index.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[H] {
+        &self.map
+    }
+
+    /// Returns the number of local handles covered by the table.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the local interner was empty.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+/// A worker-local interner handed out by an [`InternerPool`].
+///
+/// Interning into it needs no synchronization. Its handles are only
+/// meaningful locally until translated with the [`Remap`] returned by
+/// [`InternerPool::merge`].
+pub struct LocalInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    index: usize,
+    interner: Interner<T, S, H>,
+}
+
+impl<T, S, H> fmt::Debug for LocalInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalInterner")
+            .field("index", &self.index)
+            .field("len", &self.interner.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> LocalInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Returns the position of this local in the pool, which determines the
+    /// merge order.
+    #[must_use]
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Interns an owned value, returning a local handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the local handle capacity is exhausted.
+    #[inline]
+    pub fn intern_owned(&mut self, item: T) -> Result<H, InternerError> {
+        self.interner.intern_owned(item)
+    }
+
+    /// Interns a borrowed value, returning a local handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the local handle capacity is exhausted.
+    #[inline]
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.interner.intern_ref(item)
+    }
+
+    /// Resolves a local handle.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        self.interner.resolve(handle)
+    }
+
+    /// Returns the number of unique items interned locally.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interner.len()
+    }
+
+    /// Returns `true` if nothing was interned locally.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interner.is_empty()
+    }
+}
+
+/// A set of worker-local interners merged into one interner at the end.
+///
+/// For fork/join workloads, each worker interns into its own
+/// [`LocalInterner`] without any contention. [`merge`](Self::merge) then
+/// combines all locals into a single [`Interner`] and returns one [`Remap`]
+/// per local to translate the handles the workers recorded.
+///
+/// Locals are obtained with [`create_local`](Self::create_local) and handed
+/// back with [`return_local`](Self::return_local), or managed automatically
+/// by [`scope`](Self::scope) when the `std` feature is enabled.
+///
+/// # Merge Order
+///
+/// The merge is deterministic: locals are visited in the order they were
+/// created, and the items of each local in their local handle order. Each item
+/// not seen before gets the next merged handle. The result depends only on
+/// what each local interned, not on thread timing.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::InternerPool;
+///
+/// let mut pool = InternerPool::<String, RandomState>::new(RandomState::new());
+///
+/// let mut first = pool.create_local();
+/// let mut second = pool.create_local();
+/// let a = first.intern_ref("a").unwrap();
+/// let b = second.intern_ref("b").unwrap();
+/// let a2 = second.intern_ref("a").unwrap();
+/// pool.return_local(first);
+/// pool.return_local(second);
+///
+/// let (merged, remaps) = pool.merge().unwrap();
+/// assert_eq!(merged.resolve(remaps[0].get(a).unwrap()).unwrap(), "a");
+/// assert_eq!(merged.resolve(remaps[1].get(b).unwrap()).unwrap(), "b");
+/// assert_eq!(remaps[1].get(a2), remaps[0].get(a));
+/// ```
+pub struct InternerPool<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    hasher: S,
+    locals: Vec<Option<Interner<T, S, H>>>,
+}
+
+impl<T, S, H> Default for InternerPool<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for InternerPool<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InternerPool")
+            .field("locals", &self.locals.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> InternerPool<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new pool. Every local and the merged interner use a clone of
+    /// `hasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            hasher,
+            locals: Vec::new(),
+        }
+    }
+
+    /// Hands back a local created by this pool, so its items are included in
+    /// [`merge`](Self::merge).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `local` was not created by this pool.
+    pub fn return_local(&mut self, local: LocalInterner<T, S, H>) {
+        let slot = self
+            .locals
+            .get_mut(local.index)
+            .expect("local interner was not created by this pool");
+        *slot = Some(local.interner);
+    }
+
+    /// Merges all returned locals into one interner.
+    ///
+    /// Returns the merged interner and one [`Remap`] per created local, in
+    /// creation order. Locals that were never returned contribute nothing and
+    /// get an empty remap. See the type-level docs for the merge order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the merged interner's handle
+    /// capacity is exhausted.
+    pub fn merge(self) -> Result<Merged<T, S, H>, InternerError> {
+        let mut merged = Interner::new(self.hasher);
+        let mut remaps = Vec::with_capacity(self.locals.len());
+        for local in self.locals {
+            let mut map = Vec::new();
+            if let Some(local) = local {
+                map.reserve(local.len());
+                for item in local {
+                    map.push(merged.intern_owned(item)?);
+                }
+            }
+            remaps.push(Remap { map });
+        }
+        Ok((merged, remaps))
+    }
+}
+
+impl<T, S, H> InternerPool<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Clone,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new local interner, to be handed to a worker.
+    ///
+    /// Locals are merged in the order they are created. Hand the local back
+    /// with [`return_local`](Self::return_local) once the worker is done.
+    #[must_use]
+    pub fn create_local(&mut self) -> LocalInterner<T, S, H> {
+        let index = self.locals.len();
+        self.locals.push(None);
+        LocalInterner {
+            index,
+            interner: Interner::new(self.hasher.clone()),
+        }
+    }
+
+    /// Runs `f` on `workers` scoped threads, each with its own local, and
+    /// returns the locals to the pool afterwards.
+    ///
+    /// Returns the results of `f` in local order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a worker panics.
+    #[cfg(feature = "std")]
+    pub fn scope<R, F>(&mut self, workers: usize, f: F) -> Vec<R>
+    where
+        T: Send,
+        S: Send,
+        H: Send,
+        R: Send,
+        F: Fn(&mut LocalInterner<T, S, H>) -> R + Sync,
+    {
+        extern crate std;
+
+        let locals: Vec<_> = (0..workers).map(|_| self.create_local()).collect();
+        let f = &f;
+        let finished: Vec<_> = std::thread::scope(|scope| {
+            let running: Vec<_> = locals
+                .into_iter()
+                .map(|mut local| {
+                    scope.spawn(move || {
+                        let result = f(&mut local);
+                        (local, result)
+                    })
+                })
+                .collect();
+            running
+                .into_iter()
+                .map(|worker| worker.join().expect("interner pool worker panicked"))
+                .collect()
+        });
+
+        finished
+            .into_iter()
+            .map(|(local, result)| {
+                self.return_local(local);
+                result
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::InternerPool;
+
+    #[test]
+    fn test_merge_order_is_deterministic() {
+        let mut pool = InternerPool::<String, RandomState>::new(RandomState::new());
+        let mut first = pool.create_local();
+        let mut second = pool.create_local();
+        let never_returned = pool.create_local();
+
+        // Return in reverse order; the merge still follows creation order.
+        let handles_2: Vec<u32> = ["c", "a", "d"]
+            .into_iter()
+            .map(|s| second.intern_ref(s).unwrap())
+            .collect();
+        pool.return_local(second);
+        let handles_1: Vec<u32> = ["a", "b"]
+            .into_iter()
+            .map(|s| first.intern_ref(s).unwrap())
+            .collect();
+        pool.return_local(first);
+        drop(never_returned);
+
+        let (merged, remaps) = pool.merge().unwrap();
+        assert_eq!(
+            merged.iter().map(String::as_str).collect::<Vec<_>>(),
+            ["a", "b", "c", "d"]
+        );
+        assert_eq!(remaps.len(), 3);
+        assert_eq!(remaps[0].as_slice(), [0, 1]);
+        assert_eq!(remaps[1].as_slice(), [2, 0, 3]);
+        assert!(remaps[2].is_empty());
+
+        let mut rewritten = handles_2.clone();
+        remaps[1].apply(&mut rewritten);
+        assert_eq!(rewritten, [2, 0, 3]);
+        assert_eq!(remaps[0].get(handles_1[1]), Some(1));
+        assert_eq!(remaps[0].get(7), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_scoped_workers_with_overlap() {
+        use alloc::string::ToString as _;
+
+        let mut pool = InternerPool::<String, RandomState>::new(RandomState::new());
+        // Worker `i` interns "0" ..= "i + 9", so all workers overlap on "0" ..= "9".
+        let recorded = pool.scope(4, |local| {
+            let n = local.index() + 10;
+            (0..n)
+                .map(|v| {
+                    let s = v.to_string();
+                    let h = local.intern_ref(s.as_str()).unwrap();
+                    (s, h)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let (merged, remaps) = pool.merge().unwrap();
+        assert_eq!(merged.len(), 13);
+        for (records, remap) in recorded.iter().zip(&remaps) {
+            for (s, local) in records {
+                let global = remap.get(*local).unwrap();
+                assert_eq!(merged.resolve(global), Some(s));
+            }
+        }
+    }
+}
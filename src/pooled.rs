@@ -0,0 +1,116 @@
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::hash::BuildHasher;
+
+use crate::{Interner, InternerError};
+
+impl<S, H> Interner<String, S, H>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Interns a string slice, reusing a buffer from `pool` if it has to be
+    /// stored.
+    ///
+    /// On a hit, the pool is left untouched. On a miss, a `String` is popped
+    /// from the pool, cleared, and filled with `s`, so no allocation happens
+    /// as long as the popped buffer is large enough; only an empty pool
+    /// allocates a new `String`. This suits loops that free scratch strings
+    /// as fast as they intern new ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if a new item is inserted and the
+    /// interner's handle capacity is exhausted, or `InternerError::LimitReached`
+    /// if the configured item limit is reached. The pool is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// let mut pool = vec![String::with_capacity(64)];
+    ///
+    /// let h = interner.intern_with_pool(&mut pool, "hello").unwrap();
+    /// assert!(pool.is_empty());
+    /// assert_eq!(interner.resolve(h).unwrap().capacity(), 64);
+    ///
+    /// // A hit does not take from the pool.
+    /// pool.push(String::new());
+    /// assert_eq!(interner.intern_with_pool(&mut pool, "hello").unwrap(), h);
+    /// assert_eq!(pool.len(), 1);
+    /// ```
+    #[track_caller]
+    pub fn intern_with_pool(
+        &mut self,
+        pool: &mut Vec<String>,
+        s: &str,
+    ) -> Result<H, InternerError> {
+        self.track_overflow(|this| {
+            let hash = match this.items.find_or_hash(s) {
+                Ok(idx) => {
+                    this.record_hit(idx);
+                    return Self::idx_to_handle(idx);
+                }
+                Err(hash) => hash,
+            };
+            let h = this.next_handle()?;
+            let mut buffer = pool.pop().unwrap_or_default();
+            buffer.clear();
+            buffer.push_str(s);
+            this.insert_hashed(hash, buffer);
+            this.record_miss();
+            Ok(h)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use crate::{Interner, InternerError};
+
+    #[test]
+    fn test_pool_buffers_are_reused() {
+        let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+        let mut pool: Vec<String> = (0..3)
+            .map(|i| {
+                let mut scratch = String::with_capacity(32);
+                scratch.push_str(["x", "y", "z"][i]);
+                scratch
+            })
+            .collect();
+        let buffers: Vec<*const u8> = pool.iter().map(|s| s.as_ptr()).collect();
+
+        // Misses take buffers from the back of the pool, without allocating.
+        let a = interner.intern_with_pool(&mut pool, "alpha").unwrap();
+        let b = interner.intern_with_pool(&mut pool, "beta").unwrap();
+        assert_eq!(interner.intern_with_pool(&mut pool, "alpha").unwrap(), a);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(interner.resolve(a).unwrap().as_ptr(), buffers[2]);
+        assert_eq!(interner.resolve(b).unwrap().as_ptr(), buffers[1]);
+        assert_eq!(interner.resolve(a).map(String::as_str), Some("alpha"));
+        assert_eq!(interner.resolve(b).unwrap().capacity(), 32);
+
+        // An empty pool falls back to a fresh string.
+        pool.clear();
+        let c = interner.intern_with_pool(&mut pool, "gamma").unwrap();
+        assert_eq!(interner.resolve(c).map(String::as_str), Some("gamma"));
+
+        let mut limited = Interner::<String, RandomState>::new_with_limit(RandomState::new(), 0);
+        let mut pool = Vec::from([String::new()]);
+        assert!(matches!(
+            limited.intern_with_pool(&mut pool, "full"),
+            Err(InternerError::LimitReached)
+        ));
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -0,0 +1,202 @@
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Deref,
+};
+
+/// A value stored together with its precomputed hash.
+///
+/// Hashing a `PrehashedValue` only feeds the cached `u64` to the hasher, and
+/// equality compares the cached hashes before comparing the values. For large
+/// keys such as multi-kilobyte `Vec<u8>` blobs, the value is hashed exactly
+/// once, when the wrapper is created: duplicate probes hash a single `u64`
+/// and almost never compare the full bytes of unequal values.
+///
+/// # Consistency
+///
+/// All wrappers interned into the same interner must hash their values the
+/// same way, e.g. with the same `BuildHasher` passed to [`new`](Self::new).
+/// Two equal values with different cached hashes compare unequal and are
+/// interned twice. The interner's own hasher may be a different one.
+///
+/// # Examples
+///
+/// ```
+/// use std::{collections::hash_map::RandomState, hash::BuildHasher};
+///
+/// use xgx_intern::{Interner, PrehashedValue};
+///
+/// let key_hasher = RandomState::new();
+/// let mut interner = Interner::<PrehashedValue<Vec<u8>>, RandomState>::new(RandomState::new());
+///
+/// let blob = vec![7u8; 4096];
+/// let h = interner
+///     .intern_owned(PrehashedValue::new(blob.clone(), &key_hasher))
+///     .unwrap();
+///
+/// // A hash computed elsewhere, e.g. while streaming the blob in, works too.
+/// let hash = key_hasher.hash_one(&blob);
+/// let probe = PrehashedValue::with_hash(blob, hash);
+/// assert_eq!(interner.lookup_handle(&probe).unwrap(), Some(h));
+/// ```
+#[derive(Clone)]
+pub struct PrehashedValue<T> {
+    hash: u64,
+    value: T,
+}
+
+impl<T> PrehashedValue<T> {
+    /// Wraps `value`, hashing it once with `hasher`.
+    #[must_use]
+    pub fn new<S>(value: T, hasher: &S) -> Self
+    where
+        T: Hash,
+        S: BuildHasher,
+    {
+        Self {
+            hash: hasher.hash_one(&value),
+            value,
+        }
+    }
+
+    /// Wraps `value` with a hash computed by the caller.
+    ///
+    /// The hash must be consistent with every other wrapper in the same
+    /// interner; see the type-level docs.
+    #[must_use]
+    #[inline]
+    pub const fn with_hash(value: T, hash: u64) -> Self {
+        Self { hash, value }
+    }
+
+    /// Returns the cached hash.
+    #[must_use]
+    #[inline]
+    pub const fn cached_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns a reference to the wrapped value.
+    #[must_use]
+    #[inline]
+    pub const fn as_inner(&self) -> &T {
+        &self.value
+    }
+
+    /// Unwraps the value, discarding the cached hash.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for PrehashedValue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // Unequal hashes rule out equality without touching the values.
+        self.hash == other.hash && self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for PrehashedValue<T> {}
+
+impl<T> Hash for PrehashedValue<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PrehashedValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrehashedValue")
+            .field("hash", &self.hash)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T> Deref for PrehashedValue<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::{
+        hash::{Hash, Hasher},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use ahash::RandomState;
+
+    use super::PrehashedValue;
+    use crate::Interner;
+
+    /// Number of times a `Blob` has been hashed.
+    static BLOB_HASHES: AtomicUsize = AtomicUsize::new(0);
+
+    /// A large key counting how often its bytes are hashed.
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    struct Blob(Vec<u8>);
+
+    impl Hash for Blob {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            BLOB_HASHES.fetch_add(1, Ordering::Relaxed);
+            self.0.hash(state);
+        }
+    }
+
+    fn blob(seed: u32) -> Blob {
+        let mut bytes = vec![0u8; 4096];
+        bytes[..4].copy_from_slice(&seed.to_le_bytes());
+        Blob(bytes)
+    }
+
+    #[test]
+    fn test_large_keys_hash_once() {
+        let key_hasher = RandomState::new();
+        let mut interner = Interner::<PrehashedValue<Blob>, RandomState>::new(RandomState::new());
+
+        // 500 distinct blobs, each interned twice, across many table growths.
+        let mut handles = Vec::new();
+        for round in 0..2 {
+            for seed in 0..500 {
+                let key = PrehashedValue::new(blob(seed), &key_hasher);
+                let h = interner.intern_owned(key).unwrap();
+                if round == 0 {
+                    handles.push(h);
+                } else {
+                    assert_eq!(h, handles[seed as usize]);
+                }
+            }
+        }
+        assert_eq!(interner.len(), 500);
+
+        // One hash per wrapper created, none for growth or probes.
+        assert_eq!(BLOB_HASHES.load(Ordering::Relaxed), 1000);
+
+        // A caller-provided hash finds the same entry.
+        let hash = key_hasher.hash_one(blob(42));
+        let probe = PrehashedValue::with_hash(blob(42), hash);
+        assert_eq!(probe.cached_hash(), hash);
+        assert_eq!(interner.lookup_handle(&probe).unwrap(), Some(handles[42]));
+        assert_eq!(interner.intern_ref(&probe).unwrap(), handles[42]);
+        assert_eq!(interner.resolve(handles[42]).unwrap().as_inner(), &blob(42));
+    }
+
+    #[test]
+    fn test_equality_compares_hash_first() {
+        let a = PrehashedValue::with_hash(1u32, 10);
+        assert_eq!(a, PrehashedValue::with_hash(1, 10));
+        // Inconsistent hashes make equal values distinct.
+        assert_ne!(a, PrehashedValue::with_hash(1, 11));
+        assert_ne!(a, PrehashedValue::with_hash(2, 10));
+        assert_eq!(*a, 1);
+        assert_eq!(a.into_inner(), 1);
+    }
+}
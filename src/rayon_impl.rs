@@ -0,0 +1,311 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+
+use rayon::prelude::*;
+
+use crate::{FrozenInterner, Interner, InternerError, iter::ParIter, store::Store};
+
+/// A chunk of the input deduplicated on its own: the distinct items in order
+/// of first occurrence, and each input item's index into them.
+type Partial<'a, T, S> = (Store<&'a T, S>, Vec<usize>);
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + Sync,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Iterates over all unique items in parallel.
+    ///
+    /// The iterator is indexed: the item at position `i` is the one behind the
+    /// handle with index `i`, so `enumerate`, `zip`, and `collect` into a
+    /// `Vec` keep the handle order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use rayon::prelude::*;
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// interner.intern_ref("hello").unwrap();
+    /// interner.intern_ref("world").unwrap();
+    ///
+    /// let total: usize = interner.par_iter().map(String::len).sum();
+    /// assert_eq!(total, 10);
+    /// ```
+    #[inline]
+    pub fn par_iter(&self) -> ParIter<'_, T> {
+        ParIter::new(self.items.as_slice())
+    }
+
+    /// Iterates over all unique items in parallel, together with their handles.
+    pub fn par_iter_with_handles(&self) -> impl ParallelIterator<Item = (H, &T)>
+    where
+        H: Send,
+    {
+        // Every stored index was a valid handle when its item was interned.
+        self.items
+            .as_slice()
+            .par_iter()
+            .enumerate()
+            .filter_map(|(idx, item)| Some((H::try_from(idx).ok()?, item)))
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + Clone + Sync,
+    S: BuildHasher + Clone + Send + Sync,
+    H: Copy + TryFrom<usize> + Send + Sync,
+    usize: TryFrom<H>,
+{
+    /// Builds an interner from `items` in parallel, also returning the handle
+    /// of every input item.
+    ///
+    /// The input is split into one chunk per rayon thread, and each chunk is
+    /// deduplicated on its own. The partial sets are then merged in input
+    /// order, and finally the codes are rewritten to the merged handles in
+    /// parallel.
+    ///
+    /// # Handle Order
+    ///
+    /// Handles are assigned by first occurrence in `items`, exactly as if every
+    /// item had been interned in order with
+    /// [`intern_owned`](Self::intern_owned). The result does not depend on the
+    /// number of threads, and `codes[i]` is the handle of `items[i]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the distinct items do not fit into
+    /// the handle space of `H`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let words = ["b", "a", "b", "c", "a"].map(String::from);
+    /// let (interner, codes) =
+    ///     Interner::<String, RandomState>::par_intern_all(RandomState::new(), &words).unwrap();
+    ///
+    /// assert_eq!(codes, [0, 1, 0, 2, 1]);
+    /// assert_eq!(interner.resolve(2).map(String::as_str), Some("c"));
+    /// ```
+    pub fn par_intern_all(hasher: S, items: &[T]) -> Result<(Self, Vec<H>), InternerError> {
+        Self::par_build(hasher, items, true)
+    }
+
+    /// Builds an interner from `items` in parallel.
+    ///
+    /// Like [`par_intern_all`](Self::par_intern_all), including the handle
+    /// order, but skips producing the codes for the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the distinct items do not fit into
+    /// the handle space of `H`.
+    pub fn from_par_slice(hasher: S, items: &[T]) -> Result<Self, InternerError> {
+        Self::par_build(hasher, items, false).map(|(interner, _)| interner)
+    }
+
+    /// Shared implementation of the parallel constructors.
+    fn par_build(
+        hasher: S,
+        items: &[T],
+        with_codes: bool,
+    ) -> Result<(Self, Vec<H>), InternerError> {
+        let chunk_size = items.len().div_ceil(rayon::current_num_threads()).max(1);
+        // The statistics need to know how often each item occurred.
+        let local_codes = with_codes || cfg!(feature = "stats");
+
+        let partials: Vec<Partial<'_, T, S>> = items
+            .par_chunks(chunk_size)
+            .map(|chunk| {
+                let mut set = Store::with_hasher(hasher.clone());
+                let mut codes = Vec::with_capacity(if local_codes { chunk.len() } else { 0 });
+                for item in chunk {
+                    let (idx, _) = set.insert_full(item);
+                    if local_codes {
+                        codes.push(idx);
+                    }
+                }
+                (set, codes)
+            })
+            .collect();
+
+        // Merging the chunks in input order keeps first occurrences first.
+        let mut interner = Self::new(hasher);
+        let remaps = partials
+            .iter()
+            .map(|(set, _)| {
+                set.iter()
+                    .map(|&item| match interner.items.find_or_hash(item) {
+                        Ok(idx) => Self::idx_to_handle(idx),
+                        Err(hash) => {
+                            let handle = interner.next_handle()?;
+                            interner.insert_hashed(hash, item.clone());
+                            Ok(handle)
+                        }
+                    })
+                    .collect::<Result<Vec<H>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        #[cfg(feature = "stats")]
+        {
+            let mut seen = alloc::vec![false; interner.len()];
+            for ((_, codes), remap) in partials.iter().zip(&remaps) {
+                for &local in codes {
+                    let Ok(idx) = usize::try_from(remap[local]) else {
+                        continue;
+                    };
+                    if core::mem::replace(&mut seen[idx], true) {
+                        interner.record_hit(idx);
+                    } else {
+                        interner.record_miss();
+                    }
+                }
+            }
+        }
+
+        let codes = if with_codes {
+            partials
+                .par_iter()
+                .zip(remaps.par_iter())
+                .flat_map_iter(|((_, codes), remap)| codes.iter().map(|&idx| remap[idx]))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Ok((interner, codes))
+    }
+}
+
+impl<T, S, H> FrozenInterner<T, S, H>
+where
+    T: Eq + Hash + Sync,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Iterates over all unique items in parallel, in handle order.
+    ///
+    /// See [`Interner::par_iter`].
+    #[inline]
+    pub fn par_values(&self) -> ParIter<'_, T> {
+        ParIter::new(self.items.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+    use rayon::prelude::*;
+
+    use crate::{Interner, InternerError};
+
+    fn create_interner(n: u32) -> Interner<String, RandomState> {
+        let mut interner = Interner::new(RandomState::new());
+        for i in 0..n {
+            interner.intern_owned(i.to_string()).unwrap();
+        }
+        interner
+    }
+
+    #[test]
+    fn test_par_fold_matches_sequential() {
+        // Large enough for rayon to split the work across threads.
+        let interner = create_interner(100_000);
+
+        let sequential: usize = interner.iter().map(String::len).sum();
+        let parallel: usize = interner.par_iter().fold(|| 0, |acc, s| acc + s.len()).sum();
+        assert_eq!(parallel, sequential);
+
+        let collected: Vec<&String> = interner.par_iter().collect();
+        assert!(collected.iter().copied().eq(interner.iter()));
+
+        let frozen = interner.freeze();
+        let frozen_sum: usize = frozen.par_values().map(String::len).sum();
+        assert_eq!(frozen_sum, sequential);
+    }
+
+    #[test]
+    fn test_par_intern_all_matches_sequential() {
+        // Many duplicates, spread so that chunks share items.
+        let items: Vec<String> = (0..200_000u32)
+            .map(|i| (i * 7 % 30_011).to_string())
+            .collect();
+
+        let mut sequential = Interner::<String, RandomState, u32>::new(RandomState::new());
+        let expected: Vec<u32> = items
+            .iter()
+            .map(|item| sequential.intern_owned(item.clone()).unwrap())
+            .collect();
+
+        for threads in [1, 2, 3, 8] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let (interner, codes) = pool
+                .install(|| {
+                    Interner::<String, RandomState>::par_intern_all(RandomState::new(), &items)
+                })
+                .unwrap();
+
+            assert_eq!(codes, expected, "threads = {threads}");
+            assert!(interner.iter().eq(sequential.iter()), "threads = {threads}");
+
+            let without_codes: Interner<String, RandomState> = pool
+                .install(|| Interner::from_par_slice(RandomState::new(), &items))
+                .unwrap();
+            assert!(without_codes.iter().eq(sequential.iter()));
+            #[cfg(feature = "stats")]
+            {
+                assert_eq!(interner.stats(), sequential.stats());
+                assert_eq!(without_codes.stats(), sequential.stats());
+                assert_eq!(interner.dedup_savings(), sequential.dedup_savings());
+            }
+        }
+    }
+
+    #[test]
+    fn test_par_intern_all_edge_cases() {
+        let (empty, codes) =
+            Interner::<String, RandomState>::par_intern_all(RandomState::new(), &[]).unwrap();
+        assert!(empty.is_empty());
+        assert!(codes.is_empty());
+
+        let bytes: Vec<u32> = (0..1000).collect();
+        let result = Interner::<u32, RandomState, u8>::par_intern_all(RandomState::new(), &bytes);
+        assert!(matches!(result, Err(InternerError::Overflow)));
+    }
+
+    #[test]
+    fn test_par_iter_with_handles_maps_to_items() {
+        let interner = create_interner(50_000);
+
+        let mismatches = interner
+            .par_iter_with_handles()
+            .filter(|&(h, item)| interner.resolve(h) != Some(item))
+            .count();
+        assert_eq!(mismatches, 0);
+
+        let mut handles: Vec<u32> = interner.par_iter_with_handles().map(|(h, _)| h).collect();
+        handles.sort_unstable();
+        assert!(handles.into_iter().eq(0..50_000));
+    }
+}
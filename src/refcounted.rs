@@ -0,0 +1,573 @@
+extern crate alloc;
+
+use alloc::vec::{self, Vec};
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    slice,
+};
+
+use hashbrown::HashTable;
+
+use crate::{FromRef, InternerError};
+
+/// A live entry of an [`RcInterner`].
+struct Entry<T> {
+    value: T,
+    /// Number of `intern` calls not yet matched by a `release`; never zero.
+    count: usize,
+}
+
+/// An interner counting references to each entry, removing an entry once
+/// every `intern` has been matched by a [`release`](Self::release).
+///
+/// This gives deterministic removal, e.g. for a symbol table whose symbols go
+/// away when the last scope using them is closed. It is not thread-safe: all
+/// mutation goes through `&mut self`.
+///
+/// # Contract
+///
+/// - Every [`intern`](Self::intern) or [`intern_ref`](Self::intern_ref) call
+///   increments the entry's count, and every `release` decrements it.
+/// - When the count reaches zero, the entry is removed and its handle becomes
+///   a tombstone: it never resolves again and is not reused until
+///   [`compact`](Self::compact) renumbers the live entries. Interning an equal
+///   value afterwards returns a fresh handle.
+/// - Releasing a removed or unknown handle returns
+///   `InternerError::InvalidHandle` and changes nothing.
+/// - [`len`](Self::len), [`iter`](Self::iter), and both `IntoIterator`
+///   impls only cover live entries, yielding each with its own handle, so
+///   holes left by removed entries are skipped rather than renumbered.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::RcInterner;
+///
+/// let mut symbols = RcInterner::<String, RandomState>::new(RandomState::new());
+/// let x = symbols.intern_ref("x").unwrap();
+/// assert_eq!(symbols.intern_ref("x").unwrap(), x);
+/// assert_eq!(symbols.refcount(x), Some(2));
+///
+/// assert_eq!(symbols.release(x).unwrap(), None);
+/// assert_eq!(symbols.release(x).unwrap().as_deref(), Some("x"));
+/// assert!(symbols.release(x).is_err());
+/// assert!(symbols.is_empty());
+/// ```
+pub struct RcInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    entries: Vec<Option<Entry<T>>>,
+    table: HashTable<usize>,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<T, S, H> Default for RcInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for RcInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RcInterner")
+            .field("len", &self.len())
+            .field("handles_issued", &self.entries.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> RcInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner with the given `BuildHasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            entries: Vec::new(),
+            table: HashTable::new(),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Interns an owned value and increments its reference count.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity or
+    /// the entry's reference count is exhausted.
+    pub fn intern(&mut self, item: T) -> Result<H, InternerError> {
+        let hash = self.hasher.hash_one(&item);
+        if let Some(idx) = self.find(hash, &item) {
+            return self.acquire(idx);
+        }
+        self.insert(hash, item)
+    }
+
+    /// Interns a borrowed value, cloning it only if it is not live, and
+    /// increments its reference count.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity or
+    /// the entry's reference count is exhausted.
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(item);
+        if let Some(idx) = self.find(hash, item) {
+            return self.acquire(idx);
+        }
+        self.insert(hash, T::from_ref(item))
+    }
+
+    /// Decrements the reference count of `handle`, removing the entry when it
+    /// reaches zero.
+    ///
+    /// Returns the removed value if this was the last reference, or `None` if
+    /// the entry is still referenced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::InvalidHandle` if `handle` was never issued or
+    /// its entry has already been removed. The interner is left unchanged.
+    pub fn release(&mut self, handle: H) -> Result<Option<T>, InternerError> {
+        let idx = usize::try_from(handle).map_err(|_| InternerError::InvalidHandle)?;
+        let entry = self
+            .entries
+            .get_mut(idx)
+            .and_then(Option::as_mut)
+            .ok_or(InternerError::InvalidHandle)?;
+
+        if entry.count > 1 {
+            entry.count -= 1;
+            return Ok(None);
+        }
+
+        let hash = self.hasher.hash_one(&entry.value);
+        if let Ok(found) = self.table.find_entry(hash, |&i| i == idx) {
+            found.remove();
+        }
+        Ok(self.entries[idx].take().map(|entry| entry.value))
+    }
+
+    /// Returns the current reference count of `handle`, or `None` if the
+    /// handle is invalid or its entry was removed.
+    #[must_use]
+    pub fn refcount(&self, handle: H) -> Option<usize> {
+        self.entry(handle).map(|entry| entry.count)
+    }
+
+    /// Resolves a handle to its value, if the entry is still live.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        self.entry(handle).map(|entry| &entry.value)
+    }
+
+    /// Returns the handle of a live value equal to `item`, without changing
+    /// its reference count.
+    #[must_use]
+    pub fn lookup_handle<Q>(&self, item: &Q) -> Option<H>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(self.hasher.hash_one(item), item)
+            .and_then(|idx| H::try_from(idx).ok())
+    }
+
+    /// Returns the number of live entries.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns `true` if there are no live entries.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Returns `true` if `handle` belongs to a live entry.
+    #[must_use]
+    #[inline]
+    pub fn contains_handle(&self, handle: H) -> bool {
+        self.entry(handle).is_some()
+    }
+
+    /// Iterates over the live entries in handle order.
+    ///
+    /// Each entry is yielded with its handle, which is its slot: tombstones
+    /// are skipped, not counted.
+    pub fn iter(&self) -> Iter<'_, T, H> {
+        Iter {
+            inner: self.entries.iter().enumerate(),
+            live: self.len(),
+            _handle: PhantomData,
+        }
+    }
+
+    /// Removes the tombstones of released entries, renumbering the live
+    /// entries contiguously in their current order.
+    ///
+    /// Returns a remap indexed by old handle: the new handle of each live
+    /// entry, or `None` for a tombstone. Every handle held outside the
+    /// interner must be translated through it, since old handles may now
+    /// resolve to different entries. Reference counts are kept.
+    pub fn compact(&mut self) -> Vec<Option<H>> {
+        let mut positions = Vec::with_capacity(self.entries.len());
+        let mut remap = Vec::with_capacity(self.entries.len());
+        let mut next = 0;
+        for entry in &self.entries {
+            positions.push(next);
+            if entry.is_some() {
+                // A new position never exceeds the old, valid one.
+                remap.push(H::try_from(next).ok());
+                next += 1;
+            } else {
+                remap.push(None);
+            }
+        }
+
+        self.entries.retain(Option::is_some);
+        for idx in self.table.iter_mut() {
+            *idx = positions[*idx];
+        }
+        remap
+    }
+
+    /// Returns the live entry behind `handle`.
+    fn entry(&self, handle: H) -> Option<&Entry<T>> {
+        let idx = usize::try_from(handle).ok()?;
+        self.entries.get(idx)?.as_ref()
+    }
+
+    /// Finds the index of the live entry equal to `item`.
+    fn find<Q>(&self, hash: u64, item: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.table
+            .find(hash, |&idx| {
+                self.entries[idx]
+                    .as_ref()
+                    .is_some_and(|entry| entry.value.borrow() == item)
+            })
+            .copied()
+    }
+
+    /// Increments the count of the live entry at `idx`.
+    fn acquire(&mut self, idx: usize) -> Result<H, InternerError> {
+        let handle = H::try_from(idx).map_err(|_| InternerError::Overflow)?;
+        if let Some(entry) = self.entries[idx].as_mut() {
+            entry.count = entry.count.checked_add(1).ok_or(InternerError::Overflow)?;
+        }
+        Ok(handle)
+    }
+
+    /// Appends a new entry with a count of one.
+    fn insert(&mut self, hash: u64, value: T) -> Result<H, InternerError> {
+        let idx = self.entries.len();
+        let handle = H::try_from(idx).map_err(|_| InternerError::Overflow)?;
+        self.entries.push(Some(Entry { value, count: 1 }));
+
+        let Self {
+            entries,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.insert_unique(hash, idx, |&i| {
+            entries[i]
+                .as_ref()
+                .map_or(0, |entry| hasher.hash_one(&entry.value))
+        });
+        Ok(handle)
+    }
+}
+
+impl<'a, T, S, H> IntoIterator for &'a RcInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    type Item = (H, &'a T);
+    type IntoIter = Iter<'a, T, H>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, S, H> IntoIterator for RcInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    type Item = (H, T);
+    type IntoIter = IntoIter<T, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            live: self.len(),
+            inner: self.entries.into_iter().enumerate(),
+            _handle: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the live entries of an [`RcInterner`] and their
+/// handles, in handle order.
+///
+/// Returned by [`RcInterner::iter`].
+pub struct Iter<'a, T, H> {
+    inner: Enumerate<slice::Iter<'a, Option<Entry<T>>>>,
+    /// Live entries not yet yielded.
+    live: usize,
+    _handle: PhantomData<H>,
+}
+
+impl<'a, T, H> Iterator for Iter<'a, T, H>
+where
+    H: TryFrom<usize>,
+{
+    type Item = (H, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (idx, entry) in self.inner.by_ref() {
+            // Every slot was issued as a handle, so its index fits in `H`.
+            if let (Some(entry), Ok(handle)) = (entry, H::try_from(idx)) {
+                self.live -= 1;
+                return Some((handle, &entry.value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.live, Some(self.live))
+    }
+}
+
+impl<T, H> ExactSizeIterator for Iter<'_, T, H> where H: TryFrom<usize> {}
+
+impl<T, H> FusedIterator for Iter<'_, T, H> where H: TryFrom<usize> {}
+
+/// An owning iterator over the live entries of an [`RcInterner`] and their
+/// handles, in handle order.
+///
+/// Returned by the `IntoIterator` impl of `RcInterner`.
+pub struct IntoIter<T, H> {
+    inner: Enumerate<vec::IntoIter<Option<Entry<T>>>>,
+    /// Live entries not yet yielded.
+    live: usize,
+    _handle: PhantomData<H>,
+}
+
+impl<T, H> Iterator for IntoIter<T, H>
+where
+    H: TryFrom<usize>,
+{
+    type Item = (H, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (idx, entry) in self.inner.by_ref() {
+            if let (Some(entry), Ok(handle)) = (entry, H::try_from(idx)) {
+                self.live -= 1;
+                return Some((handle, entry.value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.live, Some(self.live))
+    }
+}
+
+impl<T, H> ExactSizeIterator for IntoIter<T, H> where H: TryFrom<usize> {}
+
+impl<T, H> FusedIterator for IntoIter<T, H> where H: TryFrom<usize> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::RcInterner;
+    use crate::InternerError;
+
+    fn create_interner() -> RcInterner<String, RandomState> {
+        RcInterner::new(RandomState::new())
+    }
+
+    #[test]
+    fn test_balanced_intern_release() {
+        let mut interner = create_interner();
+        let a = interner.intern_ref("a").unwrap();
+        let b = interner.intern_ref("b").unwrap();
+        for _ in 0..3 {
+            assert_eq!(interner.intern_ref("a").unwrap(), a);
+        }
+        assert_eq!(interner.refcount(a), Some(4));
+        assert_eq!(interner.refcount(b), Some(1));
+
+        for _ in 0..3 {
+            assert_eq!(interner.release(a).unwrap(), None);
+        }
+        assert_eq!(interner.resolve(a).map(String::as_str), Some("a"));
+        assert_eq!(interner.release(a).unwrap().as_deref(), Some("a"));
+
+        assert_eq!(interner.refcount(a), None);
+        assert_eq!(interner.resolve(a), None);
+        assert_eq!(interner.lookup_handle("a"), None);
+        assert_eq!(interner.len(), 1);
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            [(b, &String::from("b"))]
+        );
+    }
+
+    #[test]
+    fn test_reintern_after_release_gets_fresh_handle() {
+        let mut interner = create_interner();
+        let a = interner.intern_ref("a").unwrap();
+        interner.release(a).unwrap();
+
+        let again = interner.intern_ref("a").unwrap();
+        assert_ne!(again, a);
+        assert_eq!(interner.refcount(again), Some(1));
+        assert_eq!(interner.lookup_handle("a"), Some(again));
+        assert_eq!(interner.resolve(a), None);
+    }
+
+    #[test]
+    fn test_over_release_is_an_error() {
+        let mut interner = create_interner();
+        let a = interner.intern_ref("a").unwrap();
+        let b = interner.intern_ref("b").unwrap();
+        interner.release(a).unwrap();
+
+        assert!(matches!(
+            interner.release(a),
+            Err(InternerError::InvalidHandle)
+        ));
+        assert!(matches!(
+            interner.release(99),
+            Err(InternerError::InvalidHandle)
+        ));
+
+        // Nothing else was disturbed.
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.refcount(b), Some(1));
+        assert_eq!(interner.intern_ref("b").unwrap(), b);
+        assert_eq!(interner.refcount(b), Some(2));
+    }
+
+    #[test]
+    fn test_iteration_skips_tombstones() {
+        let mut interner = create_interner();
+        let handles: Vec<_> = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|s| interner.intern_ref(s).unwrap())
+            .collect();
+        interner.release(handles[1]).unwrap();
+
+        assert!(interner.contains_handle(handles[0]));
+        assert!(!interner.contains_handle(handles[1]));
+        assert!(!interner.contains_handle(4));
+
+        let expected = [(0, "a"), (2, "c"), (3, "d")];
+        let iter = interner.iter();
+        assert_eq!(iter.len(), 3);
+        assert!(iter.map(|(h, s)| (h, s.as_str())).eq(expected));
+        assert!(
+            (&interner)
+                .into_iter()
+                .map(|(h, s)| (h, s.as_str()))
+                .eq(expected)
+        );
+        for (h, s) in &interner {
+            assert_eq!(interner.resolve(h), Some(s));
+        }
+
+        // A re-interned value gets a fresh slot and is yielded last.
+        interner.release(handles[0]).unwrap();
+        interner.intern_ref("a").unwrap();
+        let owned: Vec<(u32, String)> = interner.into_iter().collect();
+        assert_eq!(owned, [(2, "c".into()), (3, "d".into()), (4, "a".into())]);
+    }
+
+    #[test]
+    fn test_compact_renumbers_live_entries() {
+        let mut interner = create_interner();
+        let handles: Vec<_> = ["a", "b", "c", "d", "e"]
+            .into_iter()
+            .map(|s| interner.intern_ref(s).unwrap())
+            .collect();
+        interner.intern_ref("d").unwrap();
+        for &h in &[handles[0], handles[2], handles[3]] {
+            interner.release(h).unwrap();
+        }
+
+        let remap = interner.compact();
+        assert_eq!(remap, [None, Some(0), None, Some(1), Some(2)]);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(
+            interner
+                .iter()
+                .map(|(h, s)| (h, s.as_str()))
+                .collect::<Vec<_>>(),
+            [(0, "b"), (1, "d"), (2, "e")]
+        );
+        assert_eq!(interner.lookup_handle("d"), Some(1));
+        assert_eq!(interner.refcount(1), Some(1));
+
+        // New entries continue after the compacted ones.
+        assert_eq!(interner.intern_ref("f").unwrap(), 3);
+        assert_eq!(interner.release(1).unwrap().as_deref(), Some("d"));
+        assert_eq!(interner.compact(), [Some(0), None, Some(1), Some(2)]);
+        assert_eq!(interner.compact(), [Some(0), Some(1), Some(2)]);
+    }
+}
@@ -0,0 +1,293 @@
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::Interner;
+
+/// The error returned when an arena is too long for its offset type.
+///
+/// Returned by [`Interner::export_arena_with_offsets`] and
+/// [`Interner::export_resolved_with_offsets`] before anything is copied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("arena of {len} bytes does not fit in the offset type")]
+pub struct ArenaOverflow {
+    /// The length of the arena in bytes.
+    pub len: usize,
+}
+
+/// A flattened string arena with its offsets, resolving handles like the
+/// interner it was exported from.
+///
+/// Returned by [`Interner::export_resolved`]. It holds the same arena and
+/// offsets as [`export_arena`](Interner::export_arena), but does the slicing
+/// itself: [`resolve`](Self::resolve) returns the string for a handle, and
+/// every access is bounds-checked, so an invalid handle yields `None`
+/// rather than a wrong slice or a panic.
+///
+/// Offsets are `usize` by default. With
+/// [`export_resolved_with_offsets`](Interner::export_resolved_with_offsets)
+/// they are stored as a narrower `O`, such as `u32`, which halves the
+/// offsets table on 64-bit targets.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::Interner;
+///
+/// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+/// let h = interner.intern_ref("hello").unwrap();
+/// interner.intern_ref("world").unwrap();
+///
+/// let arena = interner.export_resolved();
+/// assert_eq!(arena.resolve(h), Some("hello"));
+/// assert_eq!(arena.resolve(2), None);
+/// assert_eq!(arena.as_parts(), ("helloworld", &[0, 5, 10][..]));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ResolvedArena<H = u32, O = usize> {
+    arena: String,
+    /// `len() + 1` offsets into `arena`, starting at `0`.
+    offsets: Vec<O>,
+    _handle: PhantomData<H>,
+}
+
+impl<H, O> fmt::Debug for ResolvedArena<H, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedArena")
+            .field("len", &(self.offsets.len() - 1))
+            .field("arena_len", &self.arena.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H, O> ResolvedArena<H, O>
+where
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+    O: Copy,
+    usize: TryFrom<O>,
+{
+    /// Wraps an arena and its offsets, which must start at `0`, never
+    /// decrease, end at `arena.len()`, and fall on character boundaries.
+    pub(crate) const fn from_parts(arena: String, offsets: Vec<O>) -> Self {
+        Self {
+            arena,
+            offsets,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Resolves a handle to its string.
+    #[must_use]
+    #[inline]
+    pub fn resolve(&self, handle: H) -> Option<&str> {
+        self.get_unvalidated(usize::try_from(handle).ok()?)
+    }
+
+    /// Returns the string at a raw index, without going through the handle
+    /// type.
+    ///
+    /// The index is still bounds-checked: anything past the last string
+    /// returns `None`.
+    #[must_use]
+    pub fn get_unvalidated(&self, idx: usize) -> Option<&str> {
+        let end = *self.offsets.get(idx.checked_add(1)?)?;
+        slice(&self.arena, self.offsets[idx], end)
+    }
+
+    /// Returns the number of strings.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns `true` if the arena holds no strings.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the strings in handle order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> + DoubleEndedIterator {
+        self.offsets
+            .windows(2)
+            .map(|w| slice(&self.arena, w[0], w[1]).unwrap_or_default())
+    }
+
+    /// Returns the arena and its offsets, laid out like the output of
+    /// [`write_arena`](Interner::write_arena).
+    #[must_use]
+    #[inline]
+    pub fn as_parts(&self) -> (&str, &[O]) {
+        (&self.arena, &self.offsets)
+    }
+
+    /// Returns the arena and its offsets by value.
+    #[must_use]
+    #[inline]
+    pub fn into_parts(self) -> (String, Vec<O>) {
+        (self.arena, self.offsets)
+    }
+}
+
+/// Returns `arena[start..end]`, or `None` if the offsets are invalid.
+fn slice<O>(arena: &str, start: O, end: O) -> Option<&str>
+where
+    usize: TryFrom<O>,
+{
+    let start = usize::try_from(start).ok()?;
+    let end = usize::try_from(end).ok()?;
+    arena.get(start..end)
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + AsRef<str>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Consumes the interner and flattens all strings into a
+    /// [`ResolvedArena`], which resolves handles without slicing arithmetic
+    /// on the caller's side.
+    ///
+    /// The arena is the same as that of
+    /// [`export_arena`](Self::export_arena). Offsets are `usize`, so unlike
+    /// `export_arena` this cannot fail.
+    #[must_use]
+    pub fn export_resolved(self) -> ResolvedArena<H> {
+        let mut arena = String::with_capacity(self.arena_size());
+        let mut offsets = Vec::with_capacity(self.arena_entry_count());
+        offsets.push(0);
+        for item in self {
+            arena.push_str(item.as_ref());
+            offsets.push(arena.len());
+        }
+        ResolvedArena::<H>::from_parts(arena, offsets)
+    }
+
+    /// Like [`export_resolved`](Self::export_resolved), but stores the
+    /// offsets as `O`, such as `u32`, to shrink the offsets table.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArenaOverflow`] if the arena is too long for `O`; see
+    /// [`export_arena_with_offsets`](Self::export_arena_with_offsets).
+    pub fn export_resolved_with_offsets<O>(self) -> Result<ResolvedArena<H, O>, ArenaOverflow>
+    where
+        O: Copy + TryFrom<usize>,
+        usize: TryFrom<O>,
+    {
+        let (arena, offsets) = self.export_arena_with_offsets()?;
+        Ok(ResolvedArena::from_parts(arena, offsets))
+    }
+
+    /// Copies all strings into a [`ResolvedArena`], leaving the interner
+    /// usable.
+    ///
+    /// This is the non-consuming [`export_resolved`](Self::export_resolved),
+    /// built from [`export_arena_ref`](Self::export_arena_ref).
+    #[must_use]
+    pub fn export_resolved_ref(&self) -> ResolvedArena<H> {
+        let (arena, offsets) = self.export_arena_ref();
+        ResolvedArena::<H>::from_parts(arena, offsets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::ArenaOverflow;
+    use crate::{Interner, InternerError};
+
+    #[test]
+    fn test_resolves_like_interner() {
+        let mut interner = Interner::<Box<str>, RandomState, u16>::new(RandomState::new());
+        for w in ["alpha", "", "βeta", "alpha", "🦀", ""] {
+            interner.intern_ref(w).unwrap();
+        }
+        let original = interner.clone();
+        let (exported, _) = interner.clone().export_arena().unwrap();
+        let arena = interner.export_resolved();
+
+        assert_eq!(arena.len(), original.len());
+        for h in 0..u16::try_from(original.len()).unwrap() {
+            assert_eq!(arena.resolve(h), original.resolve(h).map(|s| &**s));
+        }
+        assert!(arena.iter().eq(original.iter().map(|s| &**s)));
+        assert_eq!(arena.iter().next_back(), Some("🦀"));
+        assert_eq!(arena.as_parts().0, exported);
+
+        // Invalid handles and indices resolve to nothing.
+        assert_eq!(arena.resolve(4), None);
+        assert_eq!(arena.resolve(u16::MAX), None);
+        assert_eq!(arena.get_unvalidated(usize::MAX), None);
+        assert_eq!(arena.get_unvalidated(3), Some("🦀"));
+
+        let (text, offsets) = arena.into_parts();
+        assert_eq!(offsets, [0, 5, 5, 10, 14]);
+        assert_eq!(text.len(), 14);
+
+        let empty = Interner::<String, RandomState>::new(RandomState::new()).export_resolved();
+        assert!(empty.is_empty());
+        assert_eq!(empty.resolve(0), None);
+        assert_eq!(empty.as_parts(), ("", &[0][..]));
+        assert_eq!(empty.iter().collect::<Vec<_>>(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_narrow_offsets() {
+        let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+        for w in ["alpha", "", "βeta", "🦀"] {
+            interner.intern_ref(w).unwrap();
+        }
+        let (arena, offsets) = interner.clone().export_arena_with_offsets::<u32>().unwrap();
+        assert_eq!(arena, "alphaβeta🦀");
+        assert_eq!(offsets, [0u32, 5, 5, 10, 14]);
+
+        let narrow = interner
+            .clone()
+            .export_resolved_with_offsets::<u32>()
+            .unwrap();
+        assert!(narrow.iter().eq(interner.iter().map(String::as_str)));
+        assert_eq!(narrow.resolve(2), Some("βeta"));
+        assert_eq!(narrow.as_parts(), (arena.as_str(), offsets.as_slice()));
+        let wide = interner.export_resolved();
+        assert!(narrow.iter().eq(wide.iter()));
+
+        // 300 bytes do not fit in `u8` offsets, and nothing is exported.
+        let mut long = Interner::<String, RandomState>::new(RandomState::new());
+        for i in 0..100 {
+            long.intern_ref(&alloc::format!("{i:03}")).unwrap();
+        }
+        assert_eq!(
+            long.clone().export_arena_with_offsets::<u8>().unwrap_err(),
+            ArenaOverflow { len: 300 }
+        );
+        assert_eq!(
+            long.clone()
+                .export_resolved_with_offsets::<u8>()
+                .unwrap_err(),
+            ArenaOverflow { len: 300 }
+        );
+        let short = long.clone().export_arena_with_offsets::<u16>().unwrap();
+        assert_eq!(short.1.last(), Some(&300));
+
+        // `export_arena` reports the same overflow for its handle type.
+        let mut tiny = Interner::<String, RandomState, u8>::new(RandomState::new());
+        for i in 0..100 {
+            tiny.intern_ref(&alloc::format!("{i:03}")).unwrap();
+        }
+        assert_eq!(tiny.export_arena().unwrap_err(), InternerError::Overflow);
+    }
+}
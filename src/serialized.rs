@@ -0,0 +1,282 @@
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash, Hasher};
+
+use crate::{DeterministicState, Interner, InternerError};
+
+/// The format version written by [`SerializedHandles::new`].
+pub const HANDLES_FORMAT_VERSION: u32 = 1;
+
+/// The size of the header of [`SerializedHandles::to_bytes`]: the version,
+/// the fingerprint, and the handle count.
+const HEADER_LEN: usize = 4 + 8 + 8;
+
+/// Handles stored together with a fingerprint of the interner that issued
+/// them, so they are never applied to a different one.
+///
+/// A handle is only an index; loaded into an interner built differently
+/// (other items, or the same items in another order), it silently resolves
+/// to the wrong item. `SerializedHandles` records the
+/// [`fingerprint`](Interner::fingerprint) of the interner's contents and
+/// [`load`](Self::load) refuses handles whose fingerprint does not match.
+///
+/// The fields are public for storing them in any format;
+/// [`to_bytes`](Self::to_bytes) and [`from_bytes`](Self::from_bytes) provide
+/// a compact binary one.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::{Interner, InternerError, SerializedHandles};
+///
+/// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+/// let handles = vec![interner.intern_ref("a").unwrap(), interner.intern_ref("b").unwrap()];
+///
+/// let bytes = SerializedHandles::new(&interner, handles.clone()).to_bytes();
+/// let loaded = SerializedHandles::<u32>::from_bytes(&bytes).unwrap();
+/// assert_eq!(loaded.clone().load(&interner).unwrap(), handles);
+///
+/// interner.intern_ref("c").unwrap();
+/// assert!(matches!(loaded.load(&interner), Err(InternerError::FingerprintMismatch)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SerializedHandles<H> {
+    /// The format version; [`HANDLES_FORMAT_VERSION`] when created.
+    pub version: u32,
+    /// The fingerprint of the interner that issued the handles.
+    pub interner_fingerprint: u64,
+    /// The handles.
+    pub handles: Vec<H>,
+}
+
+impl<H> SerializedHandles<H>
+where
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Wraps handles issued by `interner`, recording its fingerprint.
+    #[must_use]
+    pub fn new<T, S>(interner: &Interner<T, S, H>, handles: Vec<H>) -> Self
+    where
+        T: Eq + Hash,
+        S: BuildHasher,
+    {
+        Self {
+            version: HANDLES_FORMAT_VERSION,
+            interner_fingerprint: interner.fingerprint(),
+            handles,
+        }
+    }
+
+    /// Returns the handles if they were issued by an interner with the same
+    /// contents as `interner`.
+    ///
+    /// # Errors
+    ///
+    /// - `InternerError::UnsupportedFormat` if the version is unknown.
+    /// - `InternerError::FingerprintMismatch` if `interner` holds different
+    ///   items, or the same items in a different order, than the interner
+    ///   that issued the handles.
+    /// - `InternerError::InvalidHandle` if a handle does not resolve in
+    ///   `interner`, which only happens if the data was altered.
+    pub fn load<T, S>(self, interner: &Interner<T, S, H>) -> Result<Vec<H>, InternerError>
+    where
+        T: Eq + Hash,
+        S: BuildHasher,
+    {
+        if self.version != HANDLES_FORMAT_VERSION {
+            return Err(InternerError::UnsupportedFormat);
+        }
+        if self.interner_fingerprint != interner.fingerprint() {
+            return Err(InternerError::FingerprintMismatch);
+        }
+        if self.handles.iter().any(|&h| interner.resolve(h).is_none()) {
+            return Err(InternerError::InvalidHandle);
+        }
+        Ok(self.handles)
+    }
+
+    /// Encodes the handles in a compact binary format.
+    ///
+    /// The layout is the version as a little-endian `u32`, the fingerprint
+    /// and the number of handles as little-endian `u64`s, and then each
+    /// handle as a little-endian `u64`.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.handles.len() * 8);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend_from_slice(&self.interner_fingerprint.to_le_bytes());
+        bytes.extend_from_slice(&(self.handles.len() as u64).to_le_bytes());
+        for &h in &self.handles {
+            // Handles are indices, which always fit in a `usize`.
+            let idx = usize::try_from(h).unwrap_or(usize::MAX);
+            bytes.extend_from_slice(&(idx as u64).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes handles written by [`to_bytes`](Self::to_bytes).
+    ///
+    /// The fingerprint is not checked until [`load`](Self::load).
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::UnsupportedFormat` if the version is unknown
+    /// or `bytes` is truncated or has trailing data, and
+    /// `InternerError::Overflow` if a handle does not fit in `H`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InternerError> {
+        let (header, body) = bytes
+            .split_first_chunk::<HEADER_LEN>()
+            .ok_or(InternerError::UnsupportedFormat)?;
+        let version = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        if version != HANDLES_FORMAT_VERSION {
+            return Err(InternerError::UnsupportedFormat);
+        }
+        let fingerprint = le_u64(&header[4..12]);
+        let count = le_u64(&header[12..]);
+        let body_len = <usize as TryFrom<u64>>::try_from(count)
+            .ok()
+            .and_then(|n| n.checked_mul(8));
+        if body_len != Some(body.len()) {
+            return Err(InternerError::UnsupportedFormat);
+        }
+        let handles = body
+            .chunks_exact(8)
+            .map(|chunk| {
+                let idx = le_u64(chunk);
+                <usize as TryFrom<u64>>::try_from(idx)
+                    .ok()
+                    .and_then(|idx| H::try_from(idx).ok())
+                    .ok_or(InternerError::Overflow)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            version,
+            interner_fingerprint: fingerprint,
+            handles,
+        })
+    }
+}
+
+/// Reads a little-endian `u64` from exactly 8 bytes.
+fn le_u64(bytes: &[u8]) -> u64 {
+    let mut word = [0; 8];
+    word.copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Returns a 64-bit fingerprint of the items in insertion order.
+    ///
+    /// Interners with equal items in the same order, and so identical
+    /// handles, have equal fingerprints, whatever their hasher. Any other
+    /// difference changes the fingerprint with overwhelming probability.
+    /// It is computed with a fixed [`DeterministicState`], so it is stable
+    /// across runs of the same build; items whose `Hash` writes a `usize`
+    /// (such as slices and `Vec`s) fingerprint differently on 32-bit and
+    /// 64-bit targets.
+    ///
+    /// This is an O(n) scan over all items.
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DeterministicState::default().build_hasher();
+        hasher.write_u64(self.len() as u64);
+        for item in &self.items {
+            item.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::{HANDLES_FORMAT_VERSION, SerializedHandles};
+    use crate::{Interner, InternerError};
+
+    fn build(words: &[&str]) -> Interner<String, RandomState> {
+        let mut interner = Interner::new(RandomState::new());
+        for w in words {
+            interner.intern_ref(*w).unwrap();
+        }
+        interner
+    }
+
+    #[test]
+    fn test_round_trip_and_stale_rejection() {
+        let interner = build(&["x", "y", "z"]);
+        let serialized = SerializedHandles::new(&interner, vec![2, 0, 0, 1]);
+        let bytes = serialized.to_bytes();
+        let decoded = SerializedHandles::<u32>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, serialized);
+
+        // A rebuilt interner with the same contents accepts the handles.
+        assert_eq!(
+            decoded.clone().load(&build(&["x", "y", "z"])).unwrap(),
+            [2, 0, 0, 1]
+        );
+
+        // Reordered, changed, and extended interners all reject them.
+        for words in [
+            &["y", "x", "z"][..],
+            &["x", "y", "w"],
+            &["x", "y", "z", "w"],
+            &[],
+        ] {
+            assert!(matches!(
+                decoded.clone().load(&build(words)),
+                Err(InternerError::FingerprintMismatch)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_data() {
+        let interner = build(&["x"]);
+        let bytes = SerializedHandles::new(&interner, vec![0u32]).to_bytes();
+        for bad in [
+            &bytes[..bytes.len() - 1],
+            &bytes[..3],
+            &[bytes.as_slice(), &[0]].concat(),
+        ] {
+            assert!(matches!(
+                SerializedHandles::<u32>::from_bytes(bad),
+                Err(InternerError::UnsupportedFormat)
+            ));
+        }
+
+        let mut future = bytes.clone();
+        future[..4].copy_from_slice(&(HANDLES_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            SerializedHandles::<u32>::from_bytes(&future),
+            Err(InternerError::UnsupportedFormat)
+        ));
+
+        let tampered = SerializedHandles {
+            handles: vec![5],
+            ..SerializedHandles::new(&interner, Vec::new())
+        };
+        assert!(matches!(
+            tampered.load(&interner),
+            Err(InternerError::InvalidHandle)
+        ));
+
+        let wide = SerializedHandles::new(&build(&[]), vec![]).to_bytes();
+        assert!(
+            SerializedHandles::<u8>::from_bytes(&wide)
+                .unwrap()
+                .handles
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,380 @@
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use crate::{FromRef, Interner, InternerError, ThreadedInterner};
+
+/// A concurrent interner that partitions items by hash into independently
+/// locked shards.
+///
+/// A single lock around an interner becomes a bottleneck when many threads
+/// insert new items at once. A `ShardedInterner` spreads items over `N` shards,
+/// each a [`ThreadedInterner`] with its own lock, so inserts into different
+/// shards never contend.
+///
+/// # Handle Layout
+///
+/// The shard id is stored in the high bits of the handle and the index within
+/// the shard in the low bits, so resolving a handle routes straight to its
+/// shard. This reduces the handle space available to each shard: with `u32`
+/// handles and 16 shards, 4 bits are used for the shard id and each shard can
+/// hold at most 2^28 unique items. Signed handles leave out the sign bit, so
+/// with `i32` handles each of 16 shards holds 2^27 items. Interning into a full shard returns
+/// `InternerError::Overflow`, even if other shards still have room.
+///
+/// Handles are unique across shards, but they are not dense: they do not
+/// correspond to positions in [`export`](Self::export).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::ShardedInterner;
+///
+/// let interner = ShardedInterner::<String, RandomState>::with_shards(8, RandomState::new());
+///
+/// let a = interner.intern_ref("a").unwrap();
+/// let b = interner.intern_ref("b").unwrap();
+///
+/// assert_ne!(a, b);
+/// assert_eq!(interner.intern_ref("a").unwrap(), a);
+/// assert_eq!(interner.resolve_cloned(b).as_deref(), Some("b"));
+/// ```
+pub struct ShardedInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    shards: Box<[ThreadedInterner<T, S, usize>]>,
+    hasher: S,
+    shard_bits: u32,
+    local_bits: u32,
+    _handle: PhantomData<H>,
+}
+
+impl<T, S, H> fmt::Debug for ShardedInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedInterner")
+            .field("shards", &self.shards.len())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> ShardedInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Clone,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new sharded interner with `shards` shards.
+    ///
+    /// The shard count is rounded up to the next power of two (and at least
+    /// one). Every shard uses a clone of `hasher`, which is also used to pick
+    /// the shard of an item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shard count needs more bits than the handle type `H` has.
+    #[must_use]
+    pub fn with_shards(shards: usize, hasher: S) -> Self {
+        let shards = shards.max(1).next_power_of_two();
+        let shard_bits = shards.trailing_zeros();
+        // Count the bits of the largest handle `H` accepts rather than those
+        // of its type, as signed handles cannot use their sign bit.
+        let handle_bits = Interner::<T, S, H>::handle_space()
+            .map_or(usize::BITS, |space| space.checked_ilog2().unwrap_or(0));
+        assert!(
+            shard_bits < handle_bits,
+            "{shards} shards do not fit in a {handle_bits}-bit handle"
+        );
+        let local_bits = handle_bits - shard_bits;
+        let per_shard = 1usize.checked_shl(local_bits).unwrap_or(usize::MAX);
+
+        let shards = (0..shards)
+            .map(|_| new_shard(hasher.clone(), per_shard))
+            .collect();
+
+        Self {
+            shards,
+            hasher,
+            shard_bits,
+            local_bits,
+            _handle: PhantomData,
+        }
+    }
+}
+
+impl<T, S, H> ShardedInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Interns an owned value into its shard.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle space of the item's
+    /// shard is exhausted.
+    pub fn intern(&self, item: T) -> Result<H, InternerError> {
+        let shard = self.shard_of(&item);
+        let local = self.shards[shard].intern(item).map_err(Self::shard_error)?;
+        self.encode(shard, local)
+    }
+
+    /// Interns a borrowed value into its shard, cloning it only if it must be
+    /// inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle space of the item's
+    /// shard is exhausted.
+    pub fn intern_ref<Q>(&self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let shard = self.shard_of(item);
+        let local = self.shards[shard]
+            .intern_ref(item)
+            .map_err(Self::shard_error)?;
+        self.encode(shard, local)
+    }
+
+    /// Returns the handle for `item` if present, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub fn get<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let shard = self.shard_of(item);
+        self.shards[shard]
+            .get(item)?
+            .map_or(Ok(None), |local| Ok(Some(self.encode(shard, local)?)))
+    }
+
+    /// Resolves `handle` in its shard and passes the value to `f` while that
+    /// shard's read lock is held.
+    ///
+    /// Returns `None` if the handle is invalid.
+    pub fn resolve_with<R, F>(&self, handle: H, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let (shard, local) = self.decode(handle)?;
+        self.shards.get(shard)?.resolve_with(local, f)
+    }
+
+    /// Resolves `handle` and returns a clone of the value.
+    #[must_use]
+    pub fn resolve_cloned(&self, handle: H) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.resolve_with(handle, T::clone)
+    }
+
+    /// Returns the total number of unique items across all shards.
+    ///
+    /// Shards are visited one after the other, so concurrent inserts may or
+    /// may not be counted.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.len()).sum()
+    }
+
+    /// Returns `true` if no shard contains any item.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.is_empty())
+    }
+
+    /// Returns the number of shards.
+    #[must_use]
+    #[inline]
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Consumes the interner and returns all unique items.
+    ///
+    /// Items are ordered by shard, then by insertion order within each shard,
+    /// so the result is deterministic for a given set of inserts per shard.
+    #[must_use]
+    pub fn export(self) -> Vec<T> {
+        let mut items = Vec::with_capacity(self.len());
+        for shard in self.shards {
+            items.extend(shard.into_inner());
+        }
+        items
+    }
+
+    /// Returns the shard an item belongs to.
+    #[inline]
+    fn shard_of<Q: Hash + ?Sized>(&self, item: &Q) -> usize {
+        // Use the high bits of the hash, which are independent from the low bits
+        // the shard's own hash table uses to pick a bucket.
+        if self.shard_bits == 0 {
+            return 0;
+        }
+        let hash = self.hasher.hash_one(item);
+        <usize as TryFrom<u64>>::try_from(hash >> (u64::BITS - self.shard_bits)).unwrap_or(0)
+    }
+
+    /// Combines a shard id and a shard-local index into a handle.
+    #[inline]
+    fn encode(&self, shard: usize, local: usize) -> Result<H, InternerError> {
+        let raw = shard
+            .checked_shl(self.local_bits)
+            .unwrap_or(0)
+            .checked_add(local)
+            .ok_or(InternerError::Overflow)?;
+        H::try_from(raw).map_err(|_| InternerError::Overflow)
+    }
+
+    /// Splits a handle into its shard id and shard-local index.
+    #[inline]
+    fn decode(&self, handle: H) -> Option<(usize, usize)> {
+        let raw = usize::try_from(handle).ok()?;
+        let shard = raw.checked_shr(self.local_bits).unwrap_or(0);
+        let mask = 1usize
+            .checked_shl(self.local_bits)
+            .map_or(usize::MAX, |bit| bit - 1);
+        Some((shard, raw & mask))
+    }
+
+    /// A full shard reports its limit as a handle space overflow.
+    #[inline]
+    fn shard_error(err: InternerError) -> InternerError {
+        match err {
+            InternerError::LimitReached => InternerError::Overflow,
+            other => other,
+        }
+    }
+}
+
+/// Creates one shard holding at most `limit` items.
+///
+/// This lives outside the `ShardedInterner` impls so the `usize: TryFrom<H>`
+/// bound there does not get in the way of inferring `usize` handles.
+fn new_shard<T, S>(hasher: S, limit: usize) -> ThreadedInterner<T, S, usize>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    ThreadedInterner::from(Interner::new_with_limit(hasher, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
+    use std::thread;
+
+    use ahash::RandomState;
+
+    use super::ShardedInterner;
+    use crate::InternerError;
+
+    #[test]
+    fn test_handles_unique_and_routed() {
+        let interner = ShardedInterner::<u32, RandomState>::with_shards(6, RandomState::new());
+        assert_eq!(interner.shard_count(), 8);
+
+        let handles: Vec<_> = (0..1000).map(|i| interner.intern(i).unwrap()).collect();
+        let unique: BTreeSet<_> = handles.iter().copied().collect();
+        assert_eq!(unique.len(), 1000);
+
+        // Items landed in more than one shard (the shard id is in the top 3 bits).
+        let shards: BTreeSet<_> = handles.iter().map(|h| h >> 29).collect();
+        assert!(shards.len() > 1);
+
+        for (i, h) in (0..1000).zip(&handles) {
+            assert_eq!(interner.resolve_cloned(*h), Some(i));
+            assert_eq!(interner.get(&i).unwrap(), Some(*h));
+        }
+        assert_eq!(interner.get(&5000).unwrap(), None);
+        assert_eq!(interner.resolve_cloned(u32::MAX), None);
+
+        let mut exported = interner.export();
+        exported.sort_unstable();
+        assert_eq!(exported, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shard_overflow() {
+        // u8 handles with 64 shards leave 2 bits, i.e. 4 items, per shard.
+        let interner = ShardedInterner::<u32, RandomState, u8>::with_shards(64, RandomState::new());
+        let overflowed =
+            (0..10_000).any(|i| matches!(interner.intern(i), Err(InternerError::Overflow)));
+        assert!(overflowed);
+        assert!(interner.len() <= 256);
+    }
+
+    #[test]
+    fn test_signed_handles_stay_positive() {
+        // `i16` handles have 15 usable bits, leaving 14 per shard.
+        let interner = ShardedInterner::<u32, RandomState, i16>::with_shards(2, RandomState::new());
+        let handles: Vec<i16> = (0..1000).map(|i| interner.intern(i).unwrap()).collect();
+        assert!(handles.iter().any(|&h| h >= 1 << 14));
+        for (i, &h) in (0..1000).zip(&handles) {
+            assert!(h >= 0);
+            assert_eq!(interner.resolve_cloned(h), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_concurrent_duplicate_heavy_stress() {
+        let interner = Arc::new(ShardedInterner::<u64, RandomState>::with_shards(
+            16,
+            RandomState::new(),
+        ));
+
+        let workers: Vec<_> = (0..16u64)
+            .map(|t| {
+                let interner = Arc::clone(&interner);
+                thread::spawn(move || {
+                    (0..5000u64)
+                        .map(|i| {
+                            let value = (i * 7 + t) % 300;
+                            (value, interner.intern(value).unwrap())
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect();
+
+        assert_eq!(interner.len(), 300);
+        for (value, handle) in results {
+            assert_eq!(interner.resolve_with(handle, |v| *v), Some(value));
+        }
+    }
+}
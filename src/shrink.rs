@@ -0,0 +1,121 @@
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::{ffi::OsString, path::PathBuf};
+
+use crate::Interner;
+
+/// Values that can release unused heap capacity in place.
+///
+/// Implementations must not change the value's `Hash` or `Eq`, which holds
+/// for capacity-only changes such as `String::shrink_to_fit`. This lets
+/// [`Interner::shrink_values_to_fit`] trim stored values without disturbing
+/// the index or any handle.
+pub trait ShrinkToFit {
+    /// Shrinks the capacity of the value as much as possible.
+    fn shrink_to_fit(&mut self);
+}
+
+impl ShrinkToFit for String {
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        Self::shrink_to_fit(self);
+    }
+}
+
+impl<T> ShrinkToFit for Vec<T> {
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        Self::shrink_to_fit(self);
+    }
+}
+
+#[cfg(feature = "std")]
+impl ShrinkToFit for PathBuf {
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        Self::shrink_to_fit(self);
+    }
+}
+
+#[cfg(feature = "std")]
+impl ShrinkToFit for OsString {
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        Self::shrink_to_fit(self);
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + ShrinkToFit,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Shrinks the capacity of every stored value, e.g. the slack a parser
+    /// left in its `String`s.
+    ///
+    /// Values keep their contents, hashes, and handles.
+    pub fn shrink_values_to_fit(&mut self) {
+        self.items
+            .as_mut_slice()
+            .iter_mut()
+            .for_each(ShrinkToFit::shrink_to_fit);
+    }
+
+    /// Shrinks every stored value and then the interner's own storage.
+    ///
+    /// Combines [`shrink_values_to_fit`](Self::shrink_values_to_fit) and
+    /// [`shrink_to_fit`](Self::shrink_to_fit), e.g. once loading is done.
+    pub fn shrink_all(&mut self) {
+        self.shrink_values_to_fit();
+        self.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use crate::Interner;
+
+    #[test]
+    fn test_shrink_values_keeps_contents_and_handles() {
+        let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+        let handles: Vec<u32> = (0..100)
+            .map(|i| {
+                let mut s = String::with_capacity(256);
+                s.push_str(&alloc::format!("value-{i}"));
+                interner.intern_owned(s).unwrap()
+            })
+            .collect();
+        assert!(interner.iter().all(|s| s.capacity() == 256));
+
+        interner.shrink_all();
+        assert!(interner.iter().all(|s| s.capacity() == s.len()));
+        assert_eq!(interner.capacity(), 100);
+
+        for (i, &h) in handles.iter().enumerate() {
+            let expected = alloc::format!("value-{i}");
+            assert_eq!(interner.resolve(h), Some(&expected));
+            assert_eq!(interner.lookup_handle(expected.as_str()).unwrap(), Some(h));
+        }
+    }
+
+    #[test]
+    fn test_shrink_vec_values() {
+        let mut interner = Interner::<Vec<u8>, RandomState>::new(RandomState::new());
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(b"abc");
+        let h = interner.intern_owned(bytes).unwrap();
+
+        interner.shrink_values_to_fit();
+        assert_eq!(interner.resolve(h).map(Vec::capacity), Some(3));
+        assert_eq!(interner.lookup_handle(&b"abc"[..]).unwrap(), Some(h));
+    }
+}
@@ -0,0 +1,305 @@
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{FromRef, Interner, InternerError};
+
+/// An interner that stores its first `N` items inline and only allocates a
+/// hash table once it grows past them.
+///
+/// Many interners hold just a handful of items (e.g. per-node attribute
+/// sets), where allocating a hash table for each one costs more than it
+/// saves. A `SmallInterner` keeps up to `N` items in an array and finds them
+/// by linear search. Interning the `N + 1`-th distinct item spills every item
+/// into a regular [`Interner`], which is used from then on.
+///
+/// Handles are assigned in insertion order both before and after the spill,
+/// exactly like in an `Interner`, so handles issued while inline stay valid.
+/// The wrapped interner is created up front but does not allocate while it
+/// is empty.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::SmallInterner;
+///
+/// let mut interner = SmallInterner::<&str, 2, RandomState>::new(RandomState::new());
+///
+/// let a = interner.intern_owned("a").unwrap();
+/// let b = interner.intern_owned("b").unwrap();
+/// assert!(!interner.is_spilled());
+///
+/// let c = interner.intern_owned("c").unwrap();
+/// assert!(interner.is_spilled());
+/// assert_eq!((a, b, c), (0, 1, 2));
+/// assert_eq!(interner.resolve(a), Some(&"a"));
+/// ```
+#[derive(Clone)]
+pub struct SmallInterner<T, const N: usize, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// The inline items, filled front to back. Empty once spilled.
+    inline: [Option<T>; N],
+    /// How many inline slots are filled.
+    len: usize,
+    /// The spilled items; empty until the spill.
+    table: Interner<T, S, H>,
+}
+
+impl<T, const N: usize, S, H> fmt::Debug for SmallInterner<T, N, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmallInterner")
+            .field("len", &self.len())
+            .field("spilled", &self.is_spilled())
+            .finish()
+    }
+}
+
+impl<T, const N: usize, S, H> Default for SmallInterner<T, N, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, const N: usize, S, H> SmallInterner<T, N, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner. This does not allocate.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            inline: [const { None }; N],
+            len: 0,
+            table: Interner::new(hasher),
+        }
+    }
+
+    /// Returns `true` if the items have moved into a hash table.
+    #[must_use]
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        !self.table.is_empty()
+    }
+
+    /// Interns an owned value, taking ownership.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle capacity is exhausted.
+    pub fn intern_owned(&mut self, item: T) -> Result<H, InternerError> {
+        if self.is_spilled() {
+            return self.table.intern_owned(item);
+        }
+        if let Some(idx) = self.position(&item) {
+            return Self::to_handle(idx);
+        }
+        self.insert_inline(item)
+    }
+
+    /// Interns a value by reference, cloning it only if it is not yet present.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle capacity is exhausted.
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.is_spilled() {
+            return self.table.intern_ref(item);
+        }
+        if let Some(idx) = self.position(item) {
+            return Self::to_handle(idx);
+        }
+        self.insert_inline(T::from_ref(item))
+    }
+
+    /// Returns the handle for `item` if it was interned, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented
+    /// by `H`.
+    pub fn lookup_handle<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.is_spilled() {
+            return self.table.lookup_handle(item);
+        }
+        self.position(item).map(Self::to_handle).transpose()
+    }
+
+    /// Resolves a handle to a reference to its value.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<&T> {
+        if self.is_spilled() {
+            return self.table.resolve(handle);
+        }
+        let idx = usize::try_from(handle).ok()?;
+        self.inline[..self.len].get(idx)?.as_ref()
+    }
+
+    /// Returns the number of unique items.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len + self.table.len()
+    }
+
+    /// Returns `true` if the interner contains no items.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over all unique items in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.inline[..self.len]
+            .iter()
+            .flatten()
+            .chain(self.table.iter())
+    }
+
+    /// Returns the inline index of `item`, if it is stored inline.
+    fn position<Q>(&self, item: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.inline[..self.len]
+            .iter()
+            .position(|slot| slot.as_ref().is_some_and(|x| x.borrow() == item))
+    }
+
+    /// Stores a new item inline, or spills if every slot is taken.
+    fn insert_inline(&mut self, item: T) -> Result<H, InternerError> {
+        let handle = Self::to_handle(self.len)?;
+        if self.len < N {
+            self.inline[self.len] = Some(item);
+            self.len += 1;
+            return Ok(handle);
+        }
+
+        // Reinsert in order, so every item keeps its index as handle.
+        self.table.reserve(N + 1);
+        for slot in &mut self.inline {
+            if let Some(item) = slot.take() {
+                self.table.intern_owned(item)?;
+            }
+        }
+        self.len = 0;
+        self.table.intern_owned(item)
+    }
+
+    /// Converts an index to a handle.
+    #[inline]
+    fn to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use ahash::RandomState;
+
+    use super::SmallInterner;
+    use crate::InternerError;
+
+    type Small = SmallInterner<String, 4, RandomState>;
+
+    #[test]
+    fn test_exactly_n_items_stay_inline() {
+        let mut interner = Small::new(RandomState::new());
+        for (i, s) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            assert_eq!(interner.intern_ref(s).unwrap(), u32::try_from(i).unwrap());
+        }
+        assert_eq!(interner.intern_ref("c").unwrap(), 2);
+        assert_eq!(interner.len(), 4);
+        assert!(!interner.is_spilled());
+
+        assert_eq!(interner.lookup_handle("d").unwrap(), Some(3));
+        assert_eq!(interner.lookup_handle("e").unwrap(), None);
+        assert_eq!(interner.resolve(1).map(String::as_str), Some("b"));
+        assert_eq!(interner.resolve(4), None);
+    }
+
+    #[test]
+    fn test_spill_keeps_handles() {
+        let mut interner = Small::new(RandomState::new());
+        let before: Vec<u32> = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|s| interner.intern_owned(s.into()).unwrap())
+            .collect();
+
+        let e = interner.intern_owned("e".into()).unwrap();
+        assert!(interner.is_spilled());
+        assert_eq!(e, 4);
+        assert_eq!(interner.len(), 5);
+
+        for (handle, s) in before.iter().zip(["a", "b", "c", "d"]) {
+            assert_eq!(interner.resolve(*handle).map(String::as_str), Some(s));
+            assert_eq!(interner.lookup_handle(s).unwrap(), Some(*handle));
+            assert_eq!(interner.intern_ref(s).unwrap(), *handle);
+        }
+        assert_eq!(interner.lookup_handle("e").unwrap(), Some(e));
+        assert_eq!(interner.intern_ref("f").unwrap(), 5);
+        assert_eq!(
+            interner.iter().map(String::as_str).collect::<Vec<_>>(),
+            ["a", "b", "c", "d", "e", "f"]
+        );
+    }
+
+    #[test]
+    fn test_spill_overflow() {
+        // A `u8` handle cannot number a 257th item, so the spill is refused.
+        let mut interner = SmallInterner::<u16, 256, RandomState, u8>::new(RandomState::new());
+        for i in 0..256 {
+            interner.intern_owned(i).unwrap();
+        }
+        assert!(matches!(
+            interner.intern_owned(256),
+            Err(InternerError::Overflow)
+        ));
+        assert!(!interner.is_spilled());
+        assert_eq!(interner.intern_owned(255).unwrap(), 255);
+    }
+
+    #[test]
+    fn test_zero_capacity_spills_immediately() {
+        let mut interner = SmallInterner::<u32, 0, RandomState>::new(RandomState::new());
+        assert!(!interner.is_spilled());
+        assert_eq!(interner.intern_owned(7).unwrap(), 0);
+        assert!(interner.is_spilled());
+        assert_eq!(interner.resolve(0), Some(&7));
+    }
+}
@@ -0,0 +1,277 @@
+extern crate std;
+
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+#[cfg(not(feature = "parking_lot"))]
+use std::sync::{Mutex, PoisonError};
+
+use arc_swap::ArcSwap;
+#[cfg(feature = "parking_lot")]
+use parking_lot::Mutex;
+
+use crate::{FrozenInterner, Interner};
+
+/// A read-mostly interner publishing immutable snapshots through an
+/// [`ArcSwap`].
+///
+/// Readers call [`load`](Self::load) to get an `Arc` to the current
+/// [`FrozenInterner`] without taking any lock, and keep using that snapshot
+/// for as long as they like. Writers call [`update`](Self::update), which
+/// copies the current contents into a mutable [`Interner`], applies a closure,
+/// and atomically swaps in the new frozen version. Updates are serialized by
+/// a mutex, so no update is lost.
+///
+/// Snapshots are append-only: every handle of an older snapshot resolves to
+/// the same value in every newer one.
+///
+/// Each update copies the whole interner, so batch new items into as few
+/// updates as possible.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::SnapshotInterner;
+///
+/// let interner = SnapshotInterner::<String, RandomState>::new(RandomState::new());
+/// let hello = interner.update(|i| i.intern_ref("hello").unwrap());
+///
+/// let before = interner.load();
+/// let world = interner.update(|i| i.intern_ref("world").unwrap());
+///
+/// // The old snapshot is unchanged, the new one has both items.
+/// assert_eq!(before.resolve(world), None);
+/// let after = interner.load();
+/// assert_eq!(after.resolve(hello).unwrap(), "hello");
+/// assert_eq!(after.resolve(world).unwrap(), "world");
+/// ```
+pub struct SnapshotInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    current: ArcSwap<FrozenInterner<T, S, H>>,
+    update_lock: Mutex<()>,
+}
+
+impl<T, S, H> Default for SnapshotInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for SnapshotInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapshotInterner")
+            .field("len", &self.current.load().len())
+            .finish()
+    }
+}
+
+impl<T, S, H> From<FrozenInterner<T, S, H>> for SnapshotInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn from(frozen: FrozenInterner<T, S, H>) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(frozen),
+            update_lock: Mutex::new(()),
+        }
+    }
+}
+
+impl<T, S, H> From<Interner<T, S, H>> for SnapshotInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn from(interner: Interner<T, S, H>) -> Self {
+        Self::from(interner.freeze())
+    }
+}
+
+impl<T, S, H> SnapshotInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new interner whose first snapshot is empty.
+    #[must_use]
+    pub fn new(hasher: S) -> Self {
+        Self::from(Interner::new(hasher))
+    }
+
+    /// Returns the current snapshot, without taking any lock.
+    ///
+    /// The snapshot stays valid and unchanged while it is held, regardless of
+    /// later updates.
+    #[must_use]
+    #[inline]
+    pub fn load(&self) -> Arc<FrozenInterner<T, S, H>> {
+        self.current.load_full()
+    }
+}
+
+impl<T, S, H> SnapshotInterner<T, S, H>
+where
+    T: Eq + Hash + Clone,
+    S: BuildHasher + Clone,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Applies `f` to a mutable copy of the current snapshot and publishes the
+    /// result as the new snapshot.
+    ///
+    /// Concurrent updates are serialized, and readers keep seeing the
+    /// previous snapshot until the new one is swapped in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` removes or reorders existing items, as that would
+    /// invalidate handles of earlier snapshots. The current snapshot is left
+    /// untouched in that case.
+    pub fn update<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Interner<T, S, H>) -> R,
+    {
+        let _guard = self.lock();
+
+        let previous = self.current.load_full();
+        let mut interner = FrozenInterner::clone(&previous).thaw();
+        let result = f(&mut interner);
+
+        assert!(
+            interner.len() >= previous.len()
+                && previous.iter().eq(interner.iter().take(previous.len())),
+            "SnapshotInterner::update must only add items"
+        );
+        self.current.store(Arc::new(interner.freeze()));
+        result
+    }
+
+    /// Acquires the update mutex, ignoring poisoning.
+    ///
+    /// A panicking update never publishes its snapshot, so the current one
+    /// is always consistent.
+    #[cfg(not(feature = "parking_lot"))]
+    fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.update_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Acquires the update mutex.
+    #[cfg(feature = "parking_lot")]
+    fn lock(&self) -> parking_lot::MutexGuard<'_, ()> {
+        self.update_lock.lock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::{string::String, sync::Arc, vec::Vec};
+    use std::{sync::Barrier, thread};
+
+    use ahash::RandomState;
+
+    use super::SnapshotInterner;
+
+    #[test]
+    fn test_old_snapshots_survive_updates() {
+        let interner = Arc::new(SnapshotInterner::<String, RandomState>::new(
+            RandomState::new(),
+        ));
+        let old: Vec<u32> = interner.update(|i| {
+            ["a", "b", "c"]
+                .into_iter()
+                .map(|s| i.intern_ref(s).unwrap())
+                .collect()
+        });
+
+        // Readers grab a snapshot, then wait for the update to happen.
+        let barrier = Arc::new(Barrier::new(5));
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let interner = Arc::clone(&interner);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    let snapshot = interner.load();
+                    barrier.wait();
+                    barrier.wait();
+                    assert_eq!(snapshot.len(), 3);
+                    assert_eq!(snapshot.resolve(1).map(String::as_str), Some("b"));
+                    assert_eq!(snapshot.get("d"), None);
+                })
+            })
+            .collect();
+
+        barrier.wait();
+        let new = interner.update(|i| {
+            assert_eq!(i.intern_ref("a").unwrap(), old[0]);
+            i.intern_ref("d").unwrap()
+        });
+        barrier.wait();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        let snapshot = interner.load();
+        for (h, s) in old.iter().zip(["a", "b", "c"]) {
+            assert_eq!(snapshot.resolve(*h).map(String::as_str), Some(s));
+        }
+        assert_eq!(snapshot.resolve(new).map(String::as_str), Some("d"));
+    }
+
+    #[test]
+    fn test_update_rejects_removal() {
+        let interner = Arc::new(SnapshotInterner::<u32, RandomState>::new(RandomState::new()));
+        interner.update(|i| i.intern_copied(&[1, 2]).unwrap());
+
+        let shared = Arc::clone(&interner);
+        let result = thread::spawn(move || {
+            shared.update(|i| {
+                i.remove(&1);
+                i.intern_owned(3).unwrap();
+            });
+        })
+        .join();
+        assert!(result.is_err());
+
+        let snapshot = interner.load();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.resolve(0), Some(&1));
+
+        // The mutex is usable after the panic.
+        assert_eq!(interner.update(|i| i.intern_owned(3).unwrap()), 2);
+    }
+}
@@ -0,0 +1,421 @@
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::Interner;
+
+/// A snapshot of the hit/miss counters of an [`Interner`], from
+/// [`Interner::stats`].
+///
+/// Intern calls count as a hit if an equal item was already present and as a
+/// miss if the item was inserted; calls failing with an error count as
+/// neither. Lookups count the calls to `lookup_handle`, `contains`, and
+/// `resolve_existing_handle`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InternerStats {
+    /// Intern calls that returned the handle of an existing item.
+    pub hits: usize,
+    /// Intern calls that inserted a new item.
+    pub misses: usize,
+    /// Lookups that did not insert.
+    pub lookups: usize,
+    /// Lookups that found no equal item.
+    pub failed_lookups: usize,
+}
+
+impl InternerStats {
+    /// Returns the fraction of intern calls that were hits, or `None` if
+    /// nothing was interned yet.
+    #[must_use]
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let calls = self.hits + self.misses;
+        (calls > 0).then(|| self.hits as f64 / calls as f64)
+    }
+}
+
+impl fmt::Display for InternerStats {
+    /// Writes a summary such as
+    /// `hits=3 misses=1 (75.0% hits), lookups=2 (1 failed)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hits={} misses={} ", self.hits, self.misses)?;
+        match self.hit_ratio() {
+            Some(ratio) => write!(f, "({:.1}% hits)", ratio * 100.0)?,
+            None => f.write_str("(no interns)")?,
+        }
+        write!(
+            f,
+            ", lookups={} ({} failed)",
+            self.lookups, self.failed_lookups
+        )
+    }
+}
+
+/// The byte savings of deduplication, from [`Interner::dedup_savings`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DedupSavings {
+    /// Total length of all interned items, counting every intern call that
+    /// returned a stored item.
+    pub attempted_bytes: usize,
+    /// Total length of the stored items.
+    pub stored_bytes: usize,
+    /// Bytes not stored thanks to deduplication, `attempted_bytes -
+    /// stored_bytes`.
+    pub saved_bytes: usize,
+    /// The fraction of attempted bytes saved, `0.0` if nothing was interned.
+    pub ratio: f64,
+}
+
+/// The live counters stored in an [`Interner`].
+///
+/// Atomics let `&self` lookups count without making the interner `!Sync`.
+pub(crate) struct Counters<T> {
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    lookups: AtomicUsize,
+    failed_lookups: AtomicUsize,
+    /// Total length of the items found by hits; see `with_dedup_savings`.
+    hit_bytes: usize,
+    /// Returns the length of an item, once `with_dedup_savings` was called.
+    byte_len: Option<fn(&T) -> usize>,
+}
+
+impl<T> Counters<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            lookups: AtomicUsize::new(0),
+            failed_lookups: AtomicUsize::new(0),
+            hit_bytes: 0,
+            byte_len: None,
+        }
+    }
+
+    /// Returns zeroed counters that keep the savings tracking of `self`.
+    pub(crate) const fn fresh(&self) -> Self {
+        let mut counters = Self::new();
+        counters.byte_len = self.byte_len;
+        counters
+    }
+
+    /// Moves the counters to an interner of `U`, measuring its items with
+    /// `byte_len` if the bytes of hits were tracked so far.
+    pub(crate) fn map_items<U>(self, byte_len: fn(&U) -> usize) -> Counters<U> {
+        Counters {
+            hits: self.hits,
+            misses: self.misses,
+            lookups: self.lookups,
+            failed_lookups: self.failed_lookups,
+            hit_bytes: self.hit_bytes,
+            byte_len: self.byte_len.map(|_| byte_len),
+        }
+    }
+
+    /// Counts an intern call that found `item`.
+    #[inline]
+    pub(crate) fn add_hit(&mut self, item: &T) {
+        *self.hits.get_mut() += 1;
+        if let Some(byte_len) = self.byte_len {
+            self.hit_bytes += byte_len(item);
+        }
+    }
+
+    /// Counts an intern call that inserted a new item.
+    #[inline]
+    pub(crate) fn add_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Drops the bytes of earlier hits, once no item is left to attribute
+    /// them to.
+    pub(crate) fn forget_hit_bytes(&mut self) {
+        self.hit_bytes = 0;
+    }
+
+    /// Counts a lookup, failed unless `found`.
+    #[inline]
+    pub(crate) fn add_lookup(&self, found: bool) {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
+        if !found {
+            self.failed_lookups.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> InternerStats {
+        InternerStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            lookups: self.lookups.load(Ordering::Relaxed),
+            failed_lookups: self.failed_lookups.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<T> Clone for Counters<T> {
+    fn clone(&self) -> Self {
+        let stats = self.snapshot();
+        Self {
+            hits: AtomicUsize::new(stats.hits),
+            misses: AtomicUsize::new(stats.misses),
+            lookups: AtomicUsize::new(stats.lookups),
+            failed_lookups: AtomicUsize::new(stats.failed_lookups),
+            hit_bytes: self.hit_bytes,
+            byte_len: self.byte_len,
+        }
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Returns the hit/miss counters accumulated since creation or the last
+    /// [`reset_stats`](Self::reset_stats).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner = Interner::<String, RandomState>::new(RandomState::new());
+    /// interner.intern_ref("a").unwrap();
+    /// interner.intern_ref("a").unwrap();
+    ///
+    /// let stats = interner.stats();
+    /// assert_eq!((stats.hits, stats.misses), (1, 1));
+    /// assert_eq!(stats.hit_ratio(), Some(0.5));
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> InternerStats {
+        self.stats.snapshot()
+    }
+
+    /// Resets all counters to zero.
+    pub fn reset_stats(&mut self) {
+        self.stats = self.stats.fresh();
+    }
+}
+
+impl<T, S, H> Interner<T, S, H>
+where
+    T: Eq + Hash + AsRef<[u8]>,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Starts counting the bytes of hits for
+    /// [`dedup_savings`](Self::dedup_savings).
+    ///
+    /// Each later hit adds the length of the item it found to a single
+    /// counter, so recording neither resolves nor allocates. Hits before this
+    /// call are not counted.
+    #[must_use]
+    pub fn with_dedup_savings(mut self) -> Self {
+        self.stats.byte_len = Some(|item: &T| item.as_ref().len());
+        self
+    }
+
+    /// Returns how many bytes deduplication saved, for string and byte
+    /// interners configured with
+    /// [`with_dedup_savings`](Self::with_dedup_savings).
+    ///
+    /// Without it, no hit bytes are counted and `saved_bytes` is zero. The
+    /// stored bytes are summed by scanning the stored items. Hits on items
+    /// removed later still count until the interner is emptied, and
+    /// [`reset_stats`](Self::reset_stats) forgets all earlier hits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use xgx_intern::Interner;
+    ///
+    /// let mut interner =
+    ///     Interner::<String, RandomState>::new(RandomState::new()).with_dedup_savings();
+    /// for word in ["alpha", "beta", "alpha", "alpha"] {
+    ///     interner.intern_ref(word).unwrap();
+    /// }
+    ///
+    /// let savings = interner.dedup_savings();
+    /// assert_eq!(savings.attempted_bytes, 19);
+    /// assert_eq!(savings.stored_bytes, 9);
+    /// assert_eq!(savings.saved_bytes, 10);
+    /// ```
+    #[must_use]
+    pub fn dedup_savings(&self) -> DedupSavings {
+        let stored_bytes = self.items.iter().map(|item| item.as_ref().len()).sum();
+        let saved_bytes = self.stats.hit_bytes;
+        let attempted_bytes = stored_bytes + saved_bytes;
+        DedupSavings {
+            attempted_bytes,
+            stored_bytes,
+            saved_bytes,
+            ratio: if attempted_bytes == 0 {
+                0.0
+            } else {
+                saved_bytes as f64 / attempted_bytes as f64
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        borrow::Cow,
+        string::{String, ToString as _},
+        vec::Vec,
+    };
+
+    use ahash::RandomState;
+
+    use super::{DedupSavings, InternerStats};
+    use crate::Interner;
+
+    #[test]
+    fn test_counts_known_sequence() {
+        let mut interner = Interner::<String, RandomState>::new_with_limit(RandomState::new(), 3);
+
+        interner.intern_ref("a").unwrap(); // miss
+        interner.intern_ref("a").unwrap(); // hit
+        interner.intern_owned("b".to_string()).unwrap(); // miss
+        interner.intern_owned("b".to_string()).unwrap(); // hit
+        interner.intern_cow(Cow::Borrowed("a")).unwrap(); // hit
+        interner
+            .intern_cow(Cow::<str>::Owned("c".to_string()))
+            .unwrap(); // miss
+        interner
+            .intern_ref_or_insert_with("c", || unreachable!())
+            .unwrap(); // hit
+        // At the limit: a failed insert counts as nothing, a lookup hit still counts.
+        assert!(interner.intern_ref("d").is_err());
+        interner.intern_owned("c".to_string()).unwrap(); // hit
+
+        assert_eq!(interner.lookup_handle("a").unwrap(), Some(0));
+        assert!(!interner.contains("zzz"));
+        assert!(interner.resolve_existing_handle("zzz").is_err());
+
+        let stats = interner.stats();
+        assert_eq!(
+            stats,
+            InternerStats {
+                hits: 5,
+                misses: 3,
+                lookups: 3,
+                failed_lookups: 2,
+            }
+        );
+        assert_eq!(stats.hit_ratio(), Some(5.0 / 8.0));
+        assert_eq!(
+            stats.to_string(),
+            "hits=5 misses=3 (62.5% hits), lookups=3 (2 failed)"
+        );
+
+        interner.reset_stats();
+        assert_eq!(interner.stats(), InternerStats::default());
+        assert_eq!(interner.stats().hit_ratio(), None);
+        assert_eq!(
+            interner.stats().to_string(),
+            "hits=0 misses=0 (no interns), lookups=0 (0 failed)"
+        );
+    }
+
+    #[test]
+    fn test_bulk_entry_points() {
+        let mut interner = Interner::<u32, RandomState>::new(RandomState::new());
+        interner.intern_copied(&[1, 2, 1, 3, 2]).unwrap();
+        assert_eq!((interner.stats().hits, interner.stats().misses), (2, 3));
+
+        assert!(interner.intern_owned_all_distinct([4, 1]).is_err());
+        assert_eq!((interner.stats().hits, interner.stats().misses), (3, 4));
+
+        // Clones keep the counts, but count on their own afterwards.
+        let mut copy = interner.clone();
+        copy.intern_owned(9).unwrap();
+        assert_eq!(copy.stats().misses, 5);
+        assert_eq!(interner.stats().misses, 4);
+    }
+
+    #[test]
+    fn test_dedup_savings() {
+        let mut interner =
+            Interner::<String, RandomState>::new(RandomState::new()).with_dedup_savings();
+        assert_eq!(interner.dedup_savings(), DedupSavings::default());
+
+        // No duplication.
+        for word in ["ab", "cde", "f"] {
+            interner.intern_ref(word).unwrap();
+        }
+        let savings = interner.dedup_savings();
+        assert_eq!((savings.attempted_bytes, savings.stored_bytes), (6, 6));
+        assert_eq!((savings.saved_bytes, savings.ratio), (0, 0.0));
+
+        // Known duplication: "cde" twice more, "ab" once more.
+        interner.intern_ref("cde").unwrap();
+        interner.intern_owned("cde".to_string()).unwrap();
+        interner.intern_cow(Cow::Borrowed("ab")).unwrap();
+        let savings = interner.dedup_savings();
+        assert_eq!(
+            savings,
+            DedupSavings {
+                attempted_bytes: 14,
+                stored_bytes: 6,
+                saved_bytes: 8,
+                ratio: 8.0 / 14.0,
+            }
+        );
+
+        // Hits on a removed item still count, until the interner is empty.
+        interner.remove("ab");
+        assert_eq!(interner.dedup_savings().saved_bytes, 8);
+        interner.intern_ref("f").unwrap();
+        assert_eq!(interner.dedup_savings().saved_bytes, 9);
+
+        // Without `with_dedup_savings`, hits are counted without bytes.
+        let mut untracked = Interner::<String, RandomState>::new(RandomState::new());
+        untracked.intern_ref("ab").unwrap();
+        untracked.intern_ref("ab").unwrap();
+        assert_eq!(untracked.stats().hits, 1);
+        assert_eq!(untracked.dedup_savings().saved_bytes, 0);
+    }
+
+    #[test]
+    fn test_split_off_starts_fresh_but_keeps_tracking() {
+        let mut interner =
+            Interner::<String, RandomState>::new(RandomState::new()).with_dedup_savings();
+        for word in ["ab", "cde", "ab"] {
+            interner.intern_ref(word).unwrap();
+        }
+
+        let mut tail = interner.split_off(1);
+        assert_eq!(tail.stats(), InternerStats::default());
+        tail.intern_ref("cde").unwrap();
+        assert_eq!(tail.dedup_savings().saved_bytes, 3);
+        assert_eq!(interner.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_dedup_savings_all_duplicates() {
+        let mut interner =
+            Interner::<Vec<u8>, RandomState>::new(RandomState::new()).with_dedup_savings();
+        for _ in 0..100 {
+            interner.intern_ref(&b"0123456789"[..]).unwrap();
+        }
+        let savings = interner.dedup_savings();
+        assert_eq!(savings.attempted_bytes, 1_000);
+        assert_eq!(savings.stored_bytes, 10);
+        assert_eq!(savings.saved_bytes, 990);
+        assert!((savings.ratio - 0.99).abs() < 1e-12);
+
+        interner.clear();
+        assert_eq!(interner.dedup_savings(), DedupSavings::default());
+    }
+}
@@ -0,0 +1,877 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+
+use hashbrown::HashTable;
+
+use crate::{
+    InternerError,
+    filter::NegativeFilter,
+    iter::{IntoIter, Iter},
+};
+
+/// The insertion-ordered storage behind [`Interner`](crate::Interner) and
+/// [`FrozenInterner`](crate::FrozenInterner).
+///
+/// Values live in a plain `Vec`, so the index of a value is its position and
+/// resolving is a direct slice access. The hash index next to them stores
+/// only positions, as `u32` while they fit, so each entry costs four bytes
+/// plus the table's control byte. Hashes are not cached: growing, shrinking,
+/// or rebuilding the index hashes the values again.
+///
+/// An optional [`NegativeFilter`] over the hashes answers most lookups of
+/// absent values before the index is probed.
+///
+/// In incremental mode, growing the index allocates the larger table right
+/// away but moves the positions over from the old one a few at a time, on
+/// each later insertion; until then, lookups consult both tables.
+#[derive(Clone)]
+pub(crate) struct Store<T, S> {
+    values: Vec<T>,
+    table: Index,
+    hasher: S,
+    filter: Option<NegativeFilter>,
+    incremental: bool,
+    rehash: Option<Rehash>,
+}
+
+/// An incremental move of the index into a larger table.
+#[derive(Clone)]
+struct Rehash {
+    /// The outgrown table, holding the positions `0..end`.
+    old: Index,
+    /// The next position to move; those before it are in the new table.
+    next: usize,
+    end: usize,
+}
+
+/// A hash table of positions into the values.
+///
+/// Positions are stored as `u32` until one no longer fits, which widens the
+/// table to `usize` positions once, so there is no limit beyond `usize`.
+#[derive(Clone)]
+enum Index {
+    Narrow(HashTable<u32>),
+    Wide(HashTable<usize>),
+}
+
+impl Index {
+    const fn new() -> Self {
+        Self::Narrow(HashTable::new())
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::Narrow(HashTable::with_capacity(capacity))
+    }
+
+    /// Creates an empty table of the same width with room for `capacity`
+    /// positions.
+    fn with_capacity_like(&self, capacity: usize) -> Self {
+        match self {
+            Self::Narrow(_) => Self::Narrow(HashTable::with_capacity(capacity)),
+            Self::Wide(_) => Self::Wide(HashTable::with_capacity(capacity)),
+        }
+    }
+
+    /// Like [`with_capacity_like`](Self::with_capacity_like), but reports
+    /// allocation failures instead of aborting.
+    fn try_with_capacity_like(&self, capacity: usize) -> Result<Self, InternerError> {
+        let mut table = match self {
+            Self::Narrow(_) => Self::Narrow(HashTable::new()),
+            Self::Wide(_) => Self::Wide(HashTable::new()),
+        };
+        table.try_reserve(capacity, |_| 0)?;
+        Ok(table)
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Narrow(table) => table.len(),
+            Self::Wide(table) => table.len(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            Self::Narrow(table) => table.capacity(),
+            Self::Wide(table) => table.capacity(),
+        }
+    }
+
+    fn allocation_size(&self) -> usize {
+        match self {
+            Self::Narrow(table) => table.allocation_size(),
+            Self::Wide(table) => table.allocation_size(),
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Self::Narrow(table) => table.clear(),
+            Self::Wide(table) => table.clear(),
+        }
+    }
+
+    /// Returns the position with `hash` for which `is_match` holds.
+    fn find(&self, hash: u64, is_match: impl Fn(usize) -> bool) -> Option<usize> {
+        match self {
+            Self::Narrow(table) => table
+                .find(hash, |&slot| is_match(slot as usize))
+                .map(|&slot| slot as usize),
+            Self::Wide(table) => table.find(hash, |&slot| is_match(slot)).copied(),
+        }
+    }
+
+    /// Inserts a position known to be absent, widening the table first if it
+    /// does not fit in a `u32`. `hash_at` hashes the value at a position.
+    fn insert_unique(&mut self, hash: u64, idx: usize, hash_at: impl Fn(usize) -> u64) {
+        let narrow = u32::try_from(idx);
+        if narrow.is_err() {
+            self.widen(&hash_at);
+        }
+        match (self, narrow) {
+            (Self::Narrow(table), Ok(slot)) => {
+                table.insert_unique(hash, slot, |&slot| hash_at(slot as usize));
+            }
+            (Self::Narrow(_), Err(_)) => unreachable!("the table was widened"),
+            (Self::Wide(table), _) => {
+                table.insert_unique(hash, idx, |&slot| hash_at(slot));
+            }
+        }
+    }
+
+    /// Switches to `usize` positions.
+    fn widen(&mut self, hash_at: impl Fn(usize) -> u64) {
+        let Self::Narrow(narrow) = self else {
+            return;
+        };
+        let mut wide = HashTable::with_capacity(narrow.capacity());
+        for &slot in narrow.iter() {
+            let slot = slot as usize;
+            wide.insert_unique(hash_at(slot), slot, |&slot| hash_at(slot));
+        }
+        *self = Self::Wide(wide);
+    }
+
+    fn reserve(&mut self, additional: usize, hash_at: impl Fn(usize) -> u64) {
+        match self {
+            Self::Narrow(table) => table.reserve(additional, |&slot| hash_at(slot as usize)),
+            Self::Wide(table) => table.reserve(additional, |&slot| hash_at(slot)),
+        }
+    }
+
+    fn try_reserve(
+        &mut self,
+        additional: usize,
+        hash_at: impl Fn(usize) -> u64,
+    ) -> Result<(), InternerError> {
+        match self {
+            Self::Narrow(table) => table.try_reserve(additional, |&slot| hash_at(slot as usize)),
+            Self::Wide(table) => table.try_reserve(additional, |&slot| hash_at(slot)),
+        }
+        .map_err(|_| InternerError::AllocationFailed)
+    }
+
+    fn shrink_to_fit(&mut self, hash_at: impl Fn(usize) -> u64) {
+        match self {
+            Self::Narrow(table) => table.shrink_to_fit(|&slot| hash_at(slot as usize)),
+            Self::Wide(table) => table.shrink_to_fit(|&slot| hash_at(slot)),
+        }
+    }
+
+    /// Removes position `idx`, stored under `hash`, and shifts every later
+    /// position down by one.
+    fn shift_remove(&mut self, hash: u64, idx: usize) {
+        match self {
+            Self::Narrow(table) => {
+                if let Ok(entry) = table.find_entry(hash, |&slot| slot as usize == idx) {
+                    entry.remove();
+                }
+                for slot in table.iter_mut() {
+                    if *slot as usize > idx {
+                        *slot -= 1;
+                    }
+                }
+            }
+            Self::Wide(table) => {
+                if let Ok(entry) = table.find_entry(hash, |&slot| slot == idx) {
+                    entry.remove();
+                }
+                for slot in table.iter_mut() {
+                    if *slot > idx {
+                        *slot -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes the positions from `len` onwards.
+    fn truncate(&mut self, len: usize) {
+        match self {
+            Self::Narrow(table) => table.retain(|&mut slot| (slot as usize) < len),
+            Self::Wide(table) => table.retain(|&mut slot| slot < len),
+        }
+    }
+}
+
+impl<T, S> Store<T, S> {
+    /// How many positions each insertion moves during an incremental rehash.
+    ///
+    /// The new table is at least twice as large as the old one, so any step
+    /// above one finishes the move before the new table fills up.
+    pub(crate) const REHASH_STEP: usize = 64;
+
+    /// Creates an empty store.
+    pub(crate) const fn with_hasher(hasher: S) -> Self {
+        Self {
+            values: Vec::new(),
+            table: Index::new(),
+            hasher,
+            filter: None,
+            incremental: false,
+            rehash: None,
+        }
+    }
+
+    /// Creates an empty store with room for `capacity` values.
+    pub(crate) fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            table: Index::with_capacity(capacity),
+            hasher,
+            filter: None,
+            incremental: false,
+            rehash: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns how many values fit without growing either the values or the
+    /// index.
+    #[inline]
+    pub(crate) fn capacity(&self) -> usize {
+        self.values.capacity().min(self.table.capacity())
+    }
+
+    /// Returns the bytes allocated for the values' inline parts and the
+    /// index.
+    pub(crate) fn allocation_size(&self) -> usize {
+        self.values.capacity() * core::mem::size_of::<T>() + self.index_allocation_size()
+    }
+
+    /// Returns the bytes allocated for the index, including an old index
+    /// still being rehashed and the negative filter.
+    fn index_allocation_size(&self) -> usize {
+        self.table.allocation_size()
+            + self
+                .rehash
+                .as_ref()
+                .map_or(0, |rehash| rehash.old.allocation_size())
+            + self
+                .filter
+                .as_ref()
+                .map_or(0, NegativeFilter::allocation_size)
+    }
+
+    /// Measures the allocation of the values with `ops`, plus the bytes of
+    /// the index, but not the values' own heap data.
+    #[cfg(feature = "malloc_size_of")]
+    pub(crate) fn shallow_size_of(&self, ops: &mut malloc_size_of::MallocSizeOfOps) -> usize {
+        use malloc_size_of::MallocShallowSizeOf as _;
+
+        self.values.shallow_size_of(ops) + self.index_allocation_size()
+    }
+
+    /// Returns the bits per value of the negative filter, if there is one.
+    #[inline]
+    pub(crate) fn negative_filter_bits(&self) -> Option<usize> {
+        self.filter.as_ref().map(NegativeFilter::bits_per_entry)
+    }
+
+    /// Returns how many positions an incremental rehash has yet to move.
+    #[inline]
+    pub(crate) fn rehash_remaining(&self) -> usize {
+        self.rehash
+            .as_ref()
+            .map_or(0, |rehash| rehash.end - rehash.next)
+    }
+
+    /// Returns `true` if the negative filter rules out a value with `hash`.
+    #[inline]
+    fn filtered_out(&self, hash: u64) -> bool {
+        self.filter
+            .as_ref()
+            .is_some_and(|filter| !filter.may_contain(hash))
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    #[inline]
+    pub(crate) fn get_index(&self, idx: usize) -> Option<&T> {
+        self.values.get(idx)
+    }
+
+    /// Returns a mutable reference to a value. The caller must not change its
+    /// hash or equality.
+    #[inline]
+    pub(crate) fn get_index_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.values.get_mut(idx)
+    }
+
+    /// Returns mutable references to all values. The caller must not change
+    /// their hash or equality.
+    #[inline]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    #[inline]
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.values)
+    }
+
+    #[inline]
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        self.values
+    }
+
+    /// Converts every value with `f`, keeping the positions and the index.
+    ///
+    /// No value is rehashed, so `f` must preserve hashes and equality: the
+    /// converted values must hash and compare like the originals, as for
+    /// `String` and `Box<str>`.
+    pub(crate) fn map_values<U>(self, f: impl FnMut(T) -> U) -> Store<U, S> {
+        Store {
+            values: self.values.into_iter().map(f).collect(),
+            table: self.table,
+            hasher: self.hasher,
+            filter: self.filter,
+            incremental: self.incremental,
+            rehash: self.rehash,
+        }
+    }
+
+    /// Replaces the hasher with `f(hasher)`, keeping the index.
+    ///
+    /// Nothing is rehashed, so the new hasher must hash every value exactly
+    /// like the old one.
+    pub(crate) fn map_hasher<S2>(self, f: impl FnOnce(S) -> S2) -> Store<T, S2> {
+        Store {
+            values: self.values,
+            table: self.table,
+            hasher: f(self.hasher),
+            filter: self.filter,
+            incremental: self.incremental,
+            rehash: self.rehash,
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.values.clear();
+        self.table.clear();
+        self.rehash = None;
+        if let Some(filter) = &mut self.filter {
+            filter.clear();
+        }
+    }
+}
+
+impl<T, S> Store<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Hashes `item` with the store's hasher.
+    #[inline]
+    pub(crate) fn hash<Q>(&self, item: &Q) -> u64
+    where
+        Q: Hash + ?Sized,
+    {
+        self.hasher.hash_one(item)
+    }
+
+    /// Returns the position of the value equal to `item`.
+    pub(crate) fn get_index_of<Q>(&self, item: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find_hashed(self.hasher.hash_one(item), item)
+    }
+
+    /// Returns the position of the value equal to `item`, given its `hash`.
+    ///
+    /// A `hash` that does not match the hasher's only causes a miss.
+    pub(crate) fn find_hashed<Q>(&self, hash: u64, item: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if self.filtered_out(hash) {
+            return None;
+        }
+        self.find_slot(hash, |value| value.borrow() == item)
+    }
+
+    /// Returns the position of the value with `hash` matching `eq`, in the
+    /// index or, during an incremental rehash, in the old table.
+    fn find_slot(&self, hash: u64, eq: impl Fn(&T) -> bool) -> Option<usize> {
+        let values = &self.values;
+        let is_match = |slot: usize| eq(&values[slot]);
+        self.table
+            .find(hash, is_match)
+            .or_else(|| self.rehash.as_ref()?.old.find(hash, is_match))
+    }
+
+    /// Returns the position of the value equal to `item`, or on a miss the
+    /// hash of `item`, to insert it with [`push_with_hash`](Self::push_with_hash)
+    /// without hashing again.
+    pub(crate) fn find_or_hash<Q>(&self, item: &Q) -> Result<usize, u64>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(item);
+        self.find_hashed(hash, item).ok_or(hash)
+    }
+
+    #[inline]
+    pub(crate) fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_index_of(item).is_some()
+    }
+
+    /// Returns the position of the value equal to `item`, inserting it first
+    /// if absent, with a single hash of `item`.
+    ///
+    /// Returns `true` if the value was inserted.
+    pub(crate) fn insert_full(&mut self, item: T) -> (usize, bool) {
+        let hash = self.hasher.hash_one(&item);
+        if !self.filtered_out(hash)
+            && let Some(idx) = self.find_slot(hash, |value| *value == item)
+        {
+            return (idx, false);
+        }
+        (self.push_with_hash(hash, item), true)
+    }
+
+    /// Appends a value known to be absent, returning its position.
+    pub(crate) fn push_new(&mut self, item: T) -> usize {
+        let hash = self.hasher.hash_one(&item);
+        self.push_with_hash(hash, item)
+    }
+
+    /// Appends a value known to be absent, given its hash, returning its
+    /// position.
+    ///
+    /// The `Borrow` contract guarantees that a value hashes like the borrowed
+    /// form it was looked up with, so a hash from
+    /// [`find_or_hash`](Self::find_or_hash) can be reused for the owned value.
+    pub(crate) fn push_with_hash(&mut self, hash: u64, item: T) -> usize {
+        if self.incremental {
+            self.reserve_table(1);
+            self.continue_rehash(Self::REHASH_STEP);
+        }
+        let idx = self.values.len();
+        self.values.push(item);
+
+        let Self {
+            values,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.insert_unique(hash, idx, |slot| hasher.hash_one(&values[slot]));
+        if let Some(filter) = &mut self.filter {
+            if self.values.len() > filter.capacity() {
+                self.rebuild_filter();
+            } else {
+                filter.insert(hash);
+            }
+        }
+        idx
+    }
+
+    /// Adds a negative filter with `bits_per_entry` bits per value, or
+    /// removes it if `bits_per_entry` is zero.
+    pub(crate) fn set_negative_filter(&mut self, bits_per_entry: usize) {
+        self.filter = (bits_per_entry > 0).then(|| NegativeFilter::new(bits_per_entry, 0));
+        self.rebuild_filter();
+    }
+
+    /// Rebuilds the negative filter by hashing every value, sized for twice
+    /// as many values so it keeps up with growth.
+    fn rebuild_filter(&mut self) {
+        let Some(filter) = &mut self.filter else {
+            return;
+        };
+        *filter = NegativeFilter::new(
+            filter.bits_per_entry(),
+            self.values
+                .len()
+                .max(self.values.capacity())
+                .saturating_mul(2),
+        );
+        for value in &self.values {
+            filter.insert(self.hasher.hash_one(value));
+        }
+    }
+
+    /// Moves up to `limit` positions of an incremental rehash into the new
+    /// table, dropping the old one once it is empty.
+    fn continue_rehash(&mut self, limit: usize) {
+        let Self {
+            values,
+            table,
+            hasher,
+            rehash,
+            ..
+        } = self;
+        let Some(progress) = rehash else {
+            return;
+        };
+        let hash_at = |slot: usize| hasher.hash_one(&values[slot]);
+        let stop = progress.end.min(progress.next.saturating_add(limit));
+        for idx in progress.next..stop {
+            table.insert_unique(hash_at(idx), idx, hash_at);
+        }
+        progress.next = stop;
+        if stop == progress.end {
+            *rehash = None;
+        }
+    }
+
+    /// Completes an incremental rehash in progress.
+    pub(crate) fn finish_rehash(&mut self) {
+        self.continue_rehash(usize::MAX);
+    }
+
+    /// Switches incremental growth of the index on or off. Switching it off
+    /// completes a rehash in progress.
+    pub(crate) fn set_incremental(&mut self, incremental: bool) {
+        if !incremental {
+            self.finish_rehash();
+        }
+        self.incremental = incremental;
+    }
+
+    /// In incremental mode, makes room for `additional` more positions in
+    /// the index by starting a rehash into a larger table, if needed.
+    ///
+    /// Returns `false` if the index should grow the usual way instead.
+    fn start_rehash(
+        &mut self,
+        additional: usize,
+        allocate: impl FnOnce(&Index, usize) -> Result<Index, InternerError>,
+    ) -> Result<bool, InternerError> {
+        if !self.incremental || self.table.is_empty() {
+            return Ok(false);
+        }
+        if self.table.capacity() - self.table.len() >= additional {
+            return Ok(true);
+        }
+        // A rehash can only still run if the caller reserves more than
+        // insertions do; finish it rather than chaining tables.
+        self.finish_rehash();
+        let capacity = self
+            .table
+            .capacity()
+            .saturating_mul(2)
+            .max(self.table.len().saturating_add(additional));
+        let table = allocate(&self.table, capacity)?;
+        let old = core::mem::replace(&mut self.table, table);
+        self.rehash = Some(Rehash {
+            next: 0,
+            end: old.len(),
+            old,
+        });
+        Ok(true)
+    }
+
+    /// Reserves room for `additional` more positions in the index.
+    fn reserve_table(&mut self, additional: usize) {
+        let allocate = |table: &Index, capacity| Ok(table.with_capacity_like(capacity));
+        if let Ok(true) = self.start_rehash(additional, allocate) {
+            return;
+        }
+        let Self {
+            values,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.reserve(additional, |slot| hasher.hash_one(&values[slot]));
+    }
+
+    /// Like [`reserve_table`](Self::reserve_table), but reports allocation
+    /// failures instead of aborting.
+    fn try_reserve_table(&mut self, additional: usize) -> Result<(), InternerError> {
+        if self.start_rehash(additional, Index::try_with_capacity_like)? {
+            return Ok(());
+        }
+        let Self {
+            values,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.try_reserve(additional, |slot| hasher.hash_one(&values[slot]))
+    }
+
+    /// Reserves room for at least `additional` more values.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional);
+        self.reserve_table(additional);
+    }
+
+    /// Reserves room for exactly `additional` more values.
+    ///
+    /// Only the values are sized exactly; the index still rounds up to its
+    /// own bucket sizes.
+    pub(crate) fn reserve_exact(&mut self, additional: usize) {
+        self.values.reserve_exact(additional);
+        self.reserve_table(additional);
+    }
+
+    /// Like [`reserve`](Self::reserve), but reports allocation failures
+    /// instead of aborting.
+    pub(crate) fn try_reserve(&mut self, additional: usize) -> Result<(), InternerError> {
+        self.values.try_reserve(additional)?;
+        self.try_reserve_table(additional)
+    }
+
+    /// Like [`reserve_exact`](Self::reserve_exact), but reports allocation
+    /// failures instead of aborting.
+    pub(crate) fn try_reserve_exact(&mut self, additional: usize) -> Result<(), InternerError> {
+        self.values.try_reserve_exact(additional)?;
+        self.try_reserve_table(additional)
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.finish_rehash();
+        self.values.shrink_to_fit();
+        let Self {
+            values,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.shrink_to_fit(|slot| hasher.hash_one(&values[slot]));
+    }
+
+    /// Removes the value equal to `item`, shifting all later values down by
+    /// one position.
+    pub(crate) fn shift_remove_full<Q>(&mut self, item: &Q) -> Option<(usize, T)>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.get_index_of(item)?;
+        self.shift_remove_index(idx).map(|value| (idx, value))
+    }
+
+    /// Removes the value at `idx`, shifting all later values down by one
+    /// position. This is O(n).
+    pub(crate) fn shift_remove_index(&mut self, idx: usize) -> Option<T> {
+        self.finish_rehash();
+        let hash = self.hasher.hash_one(self.values.get(idx)?);
+        self.table.shift_remove(hash, idx);
+        let value = self.values.remove(idx);
+        self.rebuild_filter();
+        Some(value)
+    }
+
+    /// Removes the values from `len` onwards, keeping the positions of the
+    /// rest. Does nothing if `len` is not less than the length.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        if len >= self.values.len() {
+            return;
+        }
+        self.finish_rehash();
+        self.values.truncate(len);
+        self.table.truncate(len);
+        self.rebuild_filter();
+    }
+
+    /// Splits off the values from `at` onwards into a new store, in which
+    /// they start at position zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than the length.
+    pub(crate) fn split_off(&mut self, at: usize) -> Self
+    where
+        S: Clone,
+    {
+        self.finish_rehash();
+        let tail = self.values.split_off(at);
+        self.table.truncate(at);
+        self.rebuild_filter();
+
+        let mut rest = Self::with_capacity_and_hasher(0, self.hasher.clone());
+        rest.reserve_exact(tail.len());
+        rest.incremental = self.incremental;
+        if let Some(bits_per_entry) = self.negative_filter_bits() {
+            rest.set_negative_filter(bits_per_entry);
+        }
+        for value in tail {
+            rest.push_new(value);
+        }
+        rest
+    }
+
+    /// Switches the index to `usize` positions, as the first position past
+    /// `u32::MAX` does.
+    #[cfg(test)]
+    fn widen(&mut self) {
+        let Self {
+            values,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.widen(|slot| hasher.hash_one(&values[slot]));
+    }
+}
+
+impl<T, S> IntoIterator for Store<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.values)
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a Store<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(&self.values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ahash::RandomState;
+    use indexmap::IndexSet;
+
+    use super::Store;
+
+    /// A small deterministic generator for operation sequences.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (self.0 >> 33) % bound
+        }
+    }
+
+    #[test]
+    fn test_matches_index_set() {
+        for seed in 0..20 {
+            let mut rng = Lcg(seed);
+            let mut store = Store::with_hasher(RandomState::new());
+            store.set_incremental(seed % 2 == 1);
+            let mut reference = IndexSet::with_hasher(RandomState::new());
+
+            for step in 0..2_000 {
+                // Half of the runs switch to `usize` positions midway.
+                if seed % 4 >= 2 && step == 1_000 {
+                    store.widen();
+                }
+                let value = rng.next(500);
+                match rng.next(10) {
+                    0 => {
+                        let idx = usize::try_from(rng.next(600)).unwrap();
+                        assert_eq!(
+                            store.shift_remove_index(idx),
+                            reference.shift_remove_index(idx)
+                        );
+                    }
+                    1 => assert_eq!(
+                        store.shift_remove_full(&value),
+                        reference.shift_remove_full(&value)
+                    ),
+                    2 => assert_eq!(store.get_index_of(&value), reference.get_index_of(&value)),
+                    _ => assert_eq!(store.insert_full(value), reference.insert_full(value)),
+                }
+            }
+
+            assert_eq!(store.len(), reference.len());
+            assert!(store.iter().eq(reference.iter()));
+            for (idx, value) in reference.iter().enumerate() {
+                assert_eq!(store.get_index_of(value), Some(idx));
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_off_and_shrink() {
+        let mut store = Store::with_hasher(RandomState::new());
+        for value in 0..100u32 {
+            store.push_new(value);
+        }
+
+        let tail = store.split_off(60);
+        assert_eq!(store.len(), 60);
+        assert_eq!(tail.len(), 40);
+        assert_eq!(store.get_index_of(&70), None);
+        assert_eq!(tail.get_index_of(&70), Some(10));
+        assert_eq!(store.get_index_of(&59), Some(59));
+
+        store.shrink_to_fit();
+        assert!(store.capacity() >= 60);
+        assert!((0..60).all(|v| store.get_index_of(&v) == Some(usize::try_from(v).unwrap())));
+        assert_eq!(tail.into_vec(), (60..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_narrow_positions_are_four_bytes() {
+        let mut store = Store::with_capacity_and_hasher(1_000, RandomState::new());
+        for value in 0..1_000u64 {
+            store.push_new(value);
+        }
+        let narrow = store.index_allocation_size();
+        store.widen();
+        let wide = store.index_allocation_size();
+        assert_eq!(
+            wide - narrow,
+            store.table.capacity().next_power_of_two() * 4
+        );
+        assert!((0..1_000).all(|v| store.get_index_of(&v) == Some(usize::try_from(v).unwrap())));
+    }
+}
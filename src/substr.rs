@@ -0,0 +1,398 @@
+extern crate alloc;
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt, hash::BuildHasher, marker::PhantomData, ops::Range};
+
+use hashbrown::HashTable;
+
+use crate::InternerError;
+
+/// Storage for a single interned string.
+#[derive(Clone)]
+enum Entry {
+    /// A string whose bytes are owned by the interner.
+    Owned(Box<str>),
+    /// A string stored as a byte range of an `Owned` entry.
+    ///
+    /// Substrings of substrings are flattened, so `root` always refers to an
+    /// `Owned` entry and resolving never needs more than one hop.
+    Slice {
+        /// Index of the `Owned` entry containing the bytes.
+        root: usize,
+        /// Start byte offset within the root string.
+        start: usize,
+        /// End byte offset within the root string.
+        end: usize,
+    },
+}
+
+/// A string interner that can store substrings as references into already
+/// interned parent strings.
+///
+/// When many interned strings are substrings of other interned strings (e.g.
+/// header names and values cut out of full header lines), storing each one
+/// separately duplicates their bytes. [`intern_substr`](Self::intern_substr)
+/// instead records a `(parent, range)` reference, and [`resolve`](Self::resolve)
+/// reconstructs the `&str` by slicing the parent.
+///
+/// Deduplication is always by content: a substring equal to an existing
+/// standalone string shares its handle, and vice versa.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::RandomState;
+///
+/// use xgx_intern::SubstrInterner;
+///
+/// let mut interner = SubstrInterner::<RandomState>::new(RandomState::new());
+///
+/// let line = interner.intern("Content-Type: text/html").unwrap();
+/// let name = interner.intern_substr(line, 0..12).unwrap();
+/// let value = interner.intern_substr(line, 14..23).unwrap();
+///
+/// assert_eq!(interner.resolve(name), Some("Content-Type"));
+/// assert_eq!(interner.resolve(value), Some("text/html"));
+///
+/// // Interning the same content standalone returns the substring's handle.
+/// assert_eq!(interner.intern("text/html").unwrap(), value);
+/// ```
+#[derive(Clone)]
+pub struct SubstrInterner<S, H = u32>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    entries: Vec<Entry>,
+    table: HashTable<usize>,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<S, H> Default for SubstrInterner<S, H>
+where
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S, H> fmt::Debug for SubstrInterner<S, H>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubstrInterner")
+            .field("len", &self.len())
+            .field("owned_bytes", &self.owned_bytes())
+            .finish()
+    }
+}
+
+impl<S, H> SubstrInterner<S, H>
+where
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner with the given `BuildHasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            entries: Vec::new(),
+            table: HashTable::new(),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Interns a string, copying its bytes if it is not already present.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern(&mut self, s: &str) -> Result<H, InternerError> {
+        let hash = self.hasher.hash_one(s);
+        if let Some(idx) = self.find(hash, s) {
+            return Self::idx_to_handle(idx);
+        }
+        self.insert(hash, Entry::Owned(Box::from(s)))
+    }
+
+    /// Interns the substring `range` of the string behind `parent`.
+    ///
+    /// If an equal string is already present, its handle is returned.
+    /// Otherwise, the substring is stored as a reference into the parent's
+    /// bytes rather than as a copy.
+    ///
+    /// # Errors
+    ///
+    /// - `InternerError::InvalidHandle` if `parent` does not resolve.
+    /// - `InternerError::InvalidRange` if `range` is out of bounds, reversed,
+    ///   or does not fall on UTF-8 character boundaries.
+    /// - `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern_substr(&mut self, parent: H, range: Range<usize>) -> Result<H, InternerError> {
+        let parent_idx = usize::try_from(parent)
+            .ok()
+            .filter(|idx| *idx < self.entries.len())
+            .ok_or(InternerError::InvalidHandle)?;
+
+        let sub = self
+            .get(parent_idx)
+            .get(range.clone())
+            .ok_or(InternerError::InvalidRange)?;
+        let hash = self.hasher.hash_one(sub);
+        if let Some(idx) = self.find(hash, sub) {
+            return Self::idx_to_handle(idx);
+        }
+
+        // Flatten onto the owning root so resolution is a single slice.
+        let entry = match self.entries[parent_idx] {
+            Entry::Owned(_) => Entry::Slice {
+                root: parent_idx,
+                start: range.start,
+                end: range.end,
+            },
+            Entry::Slice { root, start, .. } => Entry::Slice {
+                root,
+                start: start + range.start,
+                end: start + range.end,
+            },
+        };
+        self.insert(hash, entry)
+    }
+
+    /// Returns the handle for `s` if present, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub fn lookup_handle(&self, s: &str) -> Result<Option<H>, InternerError> {
+        let hash = self.hasher.hash_one(s);
+        self.find(hash, s)
+            .map_or(Ok(None), |idx| Ok(Some(Self::idx_to_handle(idx)?)))
+    }
+
+    /// Returns true if an equal string is present.
+    #[must_use]
+    pub fn contains(&self, s: &str) -> bool {
+        self.find(self.hasher.hash_one(s), s).is_some()
+    }
+
+    /// Resolves a handle back to the interned string.
+    ///
+    /// Returns `None` if the handle is invalid (e.g., out of bounds).
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<&str> {
+        let idx = usize::try_from(handle).ok()?;
+        (idx < self.entries.len()).then(|| self.get(idx))
+    }
+
+    /// Returns `true` if the string behind `handle` is stored as a reference
+    /// into another entry rather than owning its bytes.
+    #[must_use]
+    pub fn is_substr(&self, handle: H) -> bool {
+        usize::try_from(handle)
+            .ok()
+            .and_then(|idx| self.entries.get(idx))
+            .is_some_and(|entry| matches!(entry, Entry::Slice { .. }))
+    }
+
+    /// Returns the number of unique strings in the interner.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the interner contains no strings.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of string bytes actually owned by the interner.
+    ///
+    /// Substrings stored as references do not contribute.
+    #[must_use]
+    pub fn owned_bytes(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Owned(s) => s.len(),
+                Entry::Slice { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// Iterates over all unique strings in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        (0..self.entries.len()).map(|idx| self.get(idx))
+    }
+
+    /// Flattens all strings into a single contiguous arena.
+    ///
+    /// Substrings are materialized, so the arena holds real bytes for every
+    /// handle. The layout matches [`Interner::export_arena`](crate::Interner::export_arena):
+    /// the string for handle `h` is `arena[offsets[h]..offsets[h + 1]]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the total byte length of the arena
+    /// exceeds the maximum value representable by the handle type `H`.
+    pub fn export_arena(&self) -> Result<(String, Vec<H>), InternerError> {
+        let total_bytes: usize = self.iter().map(str::len).sum();
+        let mut arena = String::with_capacity(total_bytes);
+        let mut offsets = Vec::with_capacity(self.entries.len() + 1);
+
+        offsets.push(Self::idx_to_handle(0)?);
+        for s in self.iter() {
+            arena.push_str(s);
+            offsets.push(Self::idx_to_handle(arena.len())?);
+        }
+
+        Ok((arena, offsets))
+    }
+
+    /// Returns the string stored at `idx`, which must be in bounds.
+    fn get(&self, idx: usize) -> &str {
+        resolve_in(&self.entries, idx)
+    }
+
+    /// Finds the index of the entry equal to `s`, given its precomputed hash.
+    fn find(&self, hash: u64, s: &str) -> Option<usize> {
+        self.table.find(hash, |&idx| self.get(idx) == s).copied()
+    }
+
+    /// Appends a new entry with the given content hash.
+    fn insert(&mut self, hash: u64, entry: Entry) -> Result<H, InternerError> {
+        let idx = self.entries.len();
+        let handle = Self::idx_to_handle(idx)?;
+        self.entries.push(entry);
+
+        let Self {
+            entries,
+            table,
+            hasher,
+            ..
+        } = self;
+        table.insert_unique(hash, idx, |&i| hasher.hash_one(resolve_in(entries, i)));
+        Ok(handle)
+    }
+
+    /// Internal helper to safely convert a `usize` index to a handle `H`.
+    #[inline]
+    fn idx_to_handle(idx: usize) -> Result<H, InternerError> {
+        H::try_from(idx).map_err(|_| InternerError::Overflow)
+    }
+}
+
+/// Resolves entry `idx`, which must be in bounds.
+///
+/// Takes the entries rather than the interner so it can be used while the
+/// hash table is borrowed mutably, e.g. for rehashing.
+fn resolve_in(entries: &[Entry], idx: usize) -> &str {
+    match &entries[idx] {
+        Entry::Owned(s) => s,
+        Entry::Slice { root, start, end } => match &entries[*root] {
+            Entry::Owned(s) => &s[*start..*end],
+            Entry::Slice { .. } => unreachable!("slice roots are always owned entries"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::RandomState;
+
+    use super::SubstrInterner;
+    use crate::InternerError;
+
+    fn create_interner() -> SubstrInterner<RandomState> {
+        SubstrInterner::new(RandomState::new())
+    }
+
+    #[test]
+    fn test_overlapping_substrings_share_parent_bytes() {
+        let mut interner = create_interner();
+        let line = interner.intern("abcdef").unwrap();
+
+        let abcd = interner.intern_substr(line, 0..4).unwrap();
+        let cdef = interner.intern_substr(line, 2..6).unwrap();
+        // A substring of a substring is flattened onto the original parent.
+        let cd = interner.intern_substr(cdef, 0..2).unwrap();
+
+        assert_eq!(interner.resolve(abcd), Some("abcd"));
+        assert_eq!(interner.resolve(cdef), Some("cdef"));
+        assert_eq!(interner.resolve(cd), Some("cd"));
+        assert!(interner.is_substr(cd));
+        assert!(!interner.is_substr(line));
+
+        assert_eq!(interner.len(), 4);
+        assert_eq!(interner.owned_bytes(), 6);
+    }
+
+    #[test]
+    fn test_substring_dedups_with_standalone_string() {
+        let mut interner = create_interner();
+        let value = interner.intern("html").unwrap();
+        let line = interner.intern("text/html").unwrap();
+
+        // Equal content to an existing standalone string: same handle.
+        assert_eq!(interner.intern_substr(line, 5..9).unwrap(), value);
+
+        // And the other way around: a standalone string equal to a substring.
+        let text = interner.intern_substr(line, 0..4).unwrap();
+        assert_eq!(interner.intern("text").unwrap(), text);
+        assert_eq!(interner.lookup_handle("text").unwrap(), Some(text));
+        assert!(interner.contains("text"));
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_ranges_and_handles() {
+        let mut interner = create_interner();
+        let h = interner.intern("héllo").unwrap();
+
+        // 'é' occupies bytes 1..3, so 0..2 splits it.
+        assert!(matches!(
+            interner.intern_substr(h, 0..2),
+            Err(InternerError::InvalidRange)
+        ));
+        assert!(matches!(
+            interner.intern_substr(h, 3..10),
+            Err(InternerError::InvalidRange)
+        ));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 4..3;
+        assert!(matches!(
+            interner.intern_substr(h, reversed),
+            Err(InternerError::InvalidRange)
+        ));
+        assert!(matches!(
+            interner.intern_substr(7, 0..1),
+            Err(InternerError::InvalidHandle)
+        ));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_export_arena_materializes_substrings() {
+        let mut interner = create_interner();
+        let line = interner.intern("key=value").unwrap();
+        interner.intern_substr(line, 0..3).unwrap();
+        interner.intern_substr(line, 4..9).unwrap();
+
+        let (arena, offsets) = interner.export_arena().unwrap();
+        assert_eq!(arena, "key=valuekeyvalue");
+        assert_eq!(offsets, alloc::vec![0, 9, 12, 17]);
+    }
+}
@@ -0,0 +1,388 @@
+extern crate std;
+
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+#[cfg(not(feature = "parking_lot"))]
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(feature = "parking_lot")]
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{FromRef, FrozenInterner, Interner, InternerError};
+
+/// A thread-safe interner guarded by an internal reader-writer lock.
+///
+/// All methods take `&self`, so a single `ThreadedInterner` can be shared
+/// across threads (e.g. a rayon pool) without an external `Mutex`. Lookups and
+/// resolves only take the read lock and run concurrently; the write lock is
+/// only taken when a new item actually needs to be inserted.
+///
+/// To avoid handing out lock guards, resolving is callback based: see
+/// [`resolve_with`](Self::resolve_with).
+///
+/// The lock is `std::sync::RwLock` by default, or `parking_lot::RwLock` when
+/// the `parking_lot` feature is enabled. Handle overflow behaves exactly as
+/// for [`Interner`].
+///
+/// # Examples
+///
+/// ```
+/// use std::{collections::hash_map::RandomState, sync::Arc, thread};
+///
+/// use xgx_intern::ThreadedInterner;
+///
+/// let interner = Arc::new(ThreadedInterner::<String, RandomState>::new(RandomState::new()));
+///
+/// let shared = Arc::clone(&interner);
+/// let h = thread::spawn(move || shared.intern_ref("hello").unwrap())
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(interner.intern_ref("hello").unwrap(), h);
+/// assert_eq!(interner.resolve_with(h, |s| s.len()), Some(5));
+/// ```
+pub struct ThreadedInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    inner: RwLock<Interner<T, S, H>>,
+}
+
+impl<T, S, H> Default for ThreadedInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for ThreadedInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadedInterner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> From<Interner<T, S, H>> for ThreadedInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn from(interner: Interner<T, S, H>) -> Self {
+        Self {
+            inner: RwLock::new(interner),
+        }
+    }
+}
+
+impl<T, S, H> ThreadedInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty thread-safe interner with the given `BuildHasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            inner: RwLock::new(Interner::new(hasher)),
+        }
+    }
+
+    /// Consumes the wrapper and returns the inner [`Interner`].
+    #[must_use]
+    pub fn into_inner(self) -> Interner<T, S, H> {
+        #[cfg(feature = "parking_lot")]
+        {
+            self.inner.into_inner()
+        }
+        #[cfg(not(feature = "parking_lot"))]
+        {
+            self.inner
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    /// Interns an owned value.
+    ///
+    /// Takes the read lock to look the value up, and the write lock only if it
+    /// must be inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern(&self, item: T) -> Result<H, InternerError> {
+        if let Some(handle) = self.read().lookup_handle(&item)? {
+            return Ok(handle);
+        }
+        // Another thread may have inserted the item in the meantime;
+        // `intern_owned` checks again under the write lock.
+        self.write().intern_owned(item)
+    }
+
+    /// Interns a borrowed value, cloning it only if it must be inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern_ref<Q>(&self, item: &Q) -> Result<H, InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(handle) = self.read().lookup_handle(item)? {
+            return Ok(handle);
+        }
+        self.write().intern_ref(item)
+    }
+
+    /// Interns a batch of owned values under a single write lock, returning
+    /// their handles in input order.
+    ///
+    /// Prefer this over calling [`intern`](Self::intern) in a loop when
+    /// interning many values at once: the lock is acquired once instead of
+    /// once or twice per value. Other threads are blocked for the whole batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is
+    /// exhausted. Values before the failing one stay interned.
+    pub fn intern_batch<I>(&self, items: I) -> Result<Vec<H>, InternerError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut interner = self.write();
+        items
+            .into_iter()
+            .map(|item| interner.intern_owned(item))
+            .collect()
+    }
+
+    /// Returns the handle for `item` if present, without inserting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the handle cannot be represented by `H`.
+    pub fn get<Q>(&self, item: &Q) -> Result<Option<H>, InternerError>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.read().lookup_handle(item)
+    }
+
+    /// Resolves `handle` and passes the value to `f` while the read lock is held.
+    ///
+    /// Returns `None` if the handle is invalid. Avoid interning into the same
+    /// interner from within `f`, as that would deadlock.
+    pub fn resolve_with<R, F>(&self, handle: H, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.read().resolve(handle).map(f)
+    }
+
+    /// Resolves `handle` and returns a clone of the value.
+    #[must_use]
+    pub fn resolve_cloned(&self, handle: H) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.resolve_with(handle, T::clone)
+    }
+
+    /// Returns a consistent point-in-time copy of the interner.
+    ///
+    /// The items are cloned while the read lock is held, so the snapshot never
+    /// observes a partially applied batch, and writers resume as soon as the
+    /// copy is made. Shrinking the copy happens after the lock is released.
+    ///
+    /// # Memory Cost
+    ///
+    /// Every snapshot is a full, independent copy: it clones every item and
+    /// the hash index, temporarily needing memory for both the copy and its
+    /// shrunk form. For large interners or frequent snapshots, prefer cheaply
+    /// clonable items such as `Arc<str>`, or a
+    /// [`SnapshotInterner`](crate::SnapshotInterner) if available.
+    #[must_use]
+    pub fn snapshot(&self) -> FrozenInterner<T, S, H>
+    where
+        T: Clone,
+        S: Clone,
+    {
+        let copy = self.read().clone();
+        copy.freeze()
+    }
+
+    /// Returns the number of unique items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns `true` if the interner contains no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Acquires the read lock, ignoring poisoning.
+    ///
+    /// The interner checks for overflow before inserting, so a panic while the
+    /// lock was held cannot leave it in a state where handles disagree with
+    /// the stored items.
+    fn read(&self) -> RwLockReadGuard<'_, Interner<T, S, H>> {
+        #[cfg(feature = "parking_lot")]
+        {
+            self.inner.read()
+        }
+        #[cfg(not(feature = "parking_lot"))]
+        {
+            self.inner.read().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    /// Acquires the write lock, ignoring poisoning.
+    fn write(&self) -> RwLockWriteGuard<'_, Interner<T, S, H>> {
+        #[cfg(feature = "parking_lot")]
+        {
+            self.inner.write()
+        }
+        #[cfg(not(feature = "parking_lot"))]
+        {
+            self.inner.write().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::{sync::Arc, vec::Vec};
+    use std::thread;
+
+    use ahash::RandomState;
+
+    use super::ThreadedInterner;
+    use crate::Interner;
+
+    #[test]
+    fn test_concurrent_overlapping_interning() {
+        let interner = Arc::new(ThreadedInterner::<u64, RandomState>::new(RandomState::new()));
+
+        // Thread `t` interns `t * 100 .. t * 100 + 500`, so neighbouring threads overlap.
+        let workers: Vec<_> = (0..8u64)
+            .map(|t| {
+                let interner = Arc::clone(&interner);
+                thread::spawn(move || {
+                    (t * 100..t * 100 + 500)
+                        .map(|v| (v, interner.intern(v).unwrap()))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect();
+
+        // Distinct inputs are 0 .. 7 * 100 + 500.
+        assert_eq!(interner.len(), 1200);
+        for (value, handle) in results {
+            assert_eq!(interner.resolve_with(handle, |v| *v), Some(value));
+            assert_eq!(interner.get(&value).unwrap(), Some(handle));
+        }
+    }
+
+    #[test]
+    fn test_batches_and_snapshots_interleave() {
+        let interner = Arc::new(ThreadedInterner::<u64, RandomState>::new(RandomState::new()));
+
+        let writers: Vec<_> = (0..4u64)
+            .map(|t| {
+                let interner = Arc::clone(&interner);
+                thread::spawn(move || {
+                    for batch in 0..50u64 {
+                        let start = (t * 50 + batch) * 20;
+                        let values: Vec<u64> = (start..start + 40).collect();
+                        let handles = interner.intern_batch(values.iter().copied()).unwrap();
+                        for (h, v) in handles.into_iter().zip(values) {
+                            assert_eq!(interner.resolve_cloned(h), Some(v));
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let snapshotters: Vec<_> = (0..2)
+            .map(|_| {
+                let interner = Arc::clone(&interner);
+                thread::spawn(move || {
+                    let mut last_len = 0;
+                    for _ in 0..25 {
+                        let snapshot = interner.snapshot();
+                        assert!(snapshot.len() >= last_len);
+                        last_len = snapshot.len();
+                        for h in 0..u32::try_from(snapshot.len()).unwrap() {
+                            let value = snapshot.resolve(h).unwrap();
+                            assert_eq!(snapshot.get(value), Some(h));
+                        }
+                        // Batches are applied atomically: every value in a
+                        // snapshot belongs to a fully interned batch.
+                        let whole_batches = snapshot.iter().all(|&v| {
+                            let batch_start = v / 20 * 20;
+                            (batch_start..batch_start + 20).all(|w| snapshot.get(&w).is_some())
+                        });
+                        assert!(whole_batches);
+                    }
+                })
+            })
+            .collect();
+
+        for worker in writers.into_iter().chain(snapshotters) {
+            worker.join().unwrap();
+        }
+        assert_eq!(interner.snapshot().len(), interner.len());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let mut plain = Interner::<alloc::string::String, RandomState>::new(RandomState::new());
+        let a = plain.intern_ref("a").unwrap();
+
+        let threaded = ThreadedInterner::from(plain);
+        assert_eq!(threaded.intern_ref("a").unwrap(), a);
+        let b = threaded.intern_ref("b").unwrap();
+        assert_eq!(threaded.resolve_cloned(b).as_deref(), Some("b"));
+        assert!(!threaded.is_empty());
+
+        let plain = threaded.into_inner();
+        assert_eq!(plain.len(), 2);
+    }
+}
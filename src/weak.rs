@@ -0,0 +1,367 @@
+extern crate alloc;
+
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+use hashbrown::HashTable;
+
+use crate::{FromRef, InternerError};
+
+/// A live or not yet purged entry of a [`WeakInterner`].
+struct Slot<T> {
+    /// The value, kept alive only by the `Arc`s handed out.
+    value: Weak<T>,
+    /// The value's hash, so the table can be rehashed after the value died.
+    hash: u64,
+}
+
+/// An interner holding its values weakly, so that values nobody else uses can
+/// be reclaimed.
+///
+/// Interning and resolving hand out `Arc<T>`s, while the interner itself only
+/// keeps a `Weak<T>`. Once every `Arc` to a value is dropped, the value is
+/// dropped too, and [`purge`](Self::purge) removes its entry.
+///
+/// # Handle Stability
+///
+/// Handles are tombstoned rather than reused: the handle of a purged entry
+/// resolves to `None` forever, and every other handle keeps resolving to the
+/// same value. Interning a value equal to a dead entry allocates a new handle.
+/// Each handle ever issued costs one slot, so the handle space is consumed
+/// like in an [`Interner`](crate::Interner) that never removes items, until
+/// [`compact`](Self::compact) reclaims the slots of purged entries and
+/// renumbers the rest.
+///
+/// # Examples
+///
+/// ```
+/// use std::{collections::hash_map::RandomState, sync::Arc};
+///
+/// use xgx_intern::WeakInterner;
+///
+/// let mut interner = WeakInterner::<String, RandomState>::new(RandomState::new());
+/// let (h, config) = interner.intern_ref("config").unwrap();
+///
+/// assert!(Arc::ptr_eq(&interner.resolve(h).unwrap(), &config));
+///
+/// drop(config);
+/// assert_eq!(interner.purge(), 1);
+/// assert_eq!(interner.resolve(h), None);
+/// ```
+pub struct WeakInterner<T, S, H = u32>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    slots: Vec<Option<Slot<T>>>,
+    table: HashTable<usize>,
+    hasher: S,
+    _handle: PhantomData<H>,
+}
+
+impl<T, S, H> Default for WeakInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<T, S, H> fmt::Debug for WeakInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakInterner")
+            .field("len", &self.len())
+            .field("slots", &self.slots.len())
+            .finish()
+    }
+}
+
+impl<T, S, H> WeakInterner<T, S, H>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+    H: Copy + TryFrom<usize>,
+    usize: TryFrom<H>,
+{
+    /// Creates a new, empty interner with the given `BuildHasher`.
+    #[must_use]
+    pub const fn new(hasher: S) -> Self {
+        Self {
+            slots: Vec::new(),
+            table: HashTable::new(),
+            hasher,
+            _handle: PhantomData,
+        }
+    }
+
+    /// Interns an owned value, returning its handle and a strong reference.
+    ///
+    /// If a live equal value exists, its handle and a new `Arc` to it are
+    /// returned and `item` is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern(&mut self, item: T) -> Result<(H, Arc<T>), InternerError> {
+        let hash = self.hasher.hash_one(&item);
+        if let Some(found) = self.find(hash, &item) {
+            return Ok(found);
+        }
+        self.insert(hash, Arc::new(item))
+    }
+
+    /// Interns a borrowed value, cloning it only if no live equal value exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InternerError::Overflow` if the interner's handle capacity is exhausted.
+    pub fn intern_ref<Q>(&mut self, item: &Q) -> Result<(H, Arc<T>), InternerError>
+    where
+        T: Borrow<Q> + FromRef<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(item);
+        if let Some(found) = self.find(hash, item) {
+            return Ok(found);
+        }
+        self.insert(hash, Arc::new(T::from_ref(item)))
+    }
+
+    /// Returns the handle of a live value equal to `item`, without inserting.
+    #[must_use]
+    pub fn lookup_handle<Q>(&self, item: &Q) -> Option<H>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(self.hasher.hash_one(item), item)
+            .map(|(handle, _)| handle)
+    }
+
+    /// Resolves a handle to a strong reference to its value.
+    ///
+    /// Returns `None` if the handle is invalid or its value has been dropped.
+    #[must_use]
+    pub fn resolve(&self, handle: H) -> Option<Arc<T>> {
+        let idx = usize::try_from(handle).ok()?;
+        self.slots.get(idx)?.as_ref()?.value.upgrade()
+    }
+
+    /// Removes the entries of all values that have been dropped, returning how
+    /// many were removed.
+    ///
+    /// This releases the interner's `Weak`, and with it the value's
+    /// allocation. Purged handles stay invalid; see the type-level docs.
+    pub fn purge(&mut self) -> usize {
+        let Self { slots, table, .. } = self;
+        let before = table.len();
+        table.retain(|&mut idx| {
+            let alive = slots[idx]
+                .as_ref()
+                .is_some_and(|slot| slot.value.strong_count() > 0);
+            if !alive {
+                slots[idx] = None;
+            }
+            alive
+        });
+        before - table.len()
+    }
+
+    /// Removes the slots of purged entries, renumbering the remaining entries
+    /// contiguously in their current order.
+    ///
+    /// Returns a remap indexed by old handle: the new handle of each
+    /// remaining entry, or `None` for a purged one. Every handle held outside
+    /// the interner must be translated through it, since old handles may now
+    /// resolve to different values. Call [`purge`](Self::purge) first to
+    /// also reclaim the slots of values dropped since the last purge.
+    pub fn compact(&mut self) -> Vec<Option<H>> {
+        let mut positions = Vec::with_capacity(self.slots.len());
+        let mut remap = Vec::with_capacity(self.slots.len());
+        let mut next = 0;
+        for slot in &self.slots {
+            positions.push(next);
+            if slot.is_some() {
+                // A new position never exceeds the old, valid one.
+                remap.push(H::try_from(next).ok());
+                next += 1;
+            } else {
+                remap.push(None);
+            }
+        }
+
+        self.slots.retain(Option::is_some);
+        for idx in self.table.iter_mut() {
+            *idx = positions[*idx];
+        }
+        remap
+    }
+
+    /// Returns the number of entries that have not been purged.
+    ///
+    /// This includes dead entries until the next [`purge`](Self::purge).
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns `true` if the interner has no entries left.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Finds the live entry equal to `item`, given its precomputed hash.
+    fn find<Q>(&self, hash: u64, item: &Q) -> Option<(H, Arc<T>)>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mut found = None;
+        self.table.find(hash, |&idx| {
+            found = self.slots[idx]
+                .as_ref()
+                .and_then(|slot| slot.value.upgrade())
+                .filter(|value| (**value).borrow() == item)
+                .map(|value| (idx, value));
+            found.is_some()
+        })?;
+        // Every stored index was a valid handle when it was inserted.
+        found.and_then(|(idx, value)| Some((H::try_from(idx).ok()?, value)))
+    }
+
+    /// Appends a new entry for `value` with the given hash.
+    fn insert(&mut self, hash: u64, value: Arc<T>) -> Result<(H, Arc<T>), InternerError> {
+        let idx = self.slots.len();
+        let handle = H::try_from(idx).map_err(|_| InternerError::Overflow)?;
+        self.slots.push(Some(Slot {
+            value: Arc::downgrade(&value),
+            hash,
+        }));
+
+        let slots = &self.slots;
+        self.table.insert_unique(hash, idx, |&i| {
+            slots[i].as_ref().map_or(0, |slot| slot.hash)
+        });
+        Ok((handle, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{sync::Arc, vec::Vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use ahash::RandomState;
+
+    use super::WeakInterner;
+
+    /// Number of `Config` values dropped so far.
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    /// A value counting how many instances have been dropped.
+    #[derive(PartialEq, Eq, Hash)]
+    struct Config(u32);
+
+    impl Drop for Config {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_purge_reclaims_dropped_values() {
+        let mut interner = WeakInterner::<Config, RandomState>::new(RandomState::new());
+
+        let mut held: Vec<_> = (0..6)
+            .map(|i| interner.intern(Config(i)).unwrap())
+            .collect();
+        assert_eq!(interner.len(), 6);
+
+        // Interning an equal live value shares the existing `Arc`.
+        let (h, again) = interner.intern(Config(2)).unwrap();
+        assert_eq!(h, held[2].0);
+        assert!(Arc::ptr_eq(&again, &held[2].1));
+        assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+        drop(again);
+
+        // Drop every external reference to the odd entries.
+        let dead: Vec<_> = held
+            .iter()
+            .filter(|(h, _)| h % 2 == 1)
+            .map(|(h, _)| *h)
+            .collect();
+        held.retain(|(h, _)| h % 2 == 0);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 4);
+        for &h in &dead {
+            assert!(interner.resolve(h).is_none());
+        }
+
+        assert_eq!(interner.purge(), 3);
+        assert_eq!(interner.purge(), 0);
+        assert_eq!(interner.len(), 3);
+        for (h, value) in &held {
+            assert!(Arc::ptr_eq(&interner.resolve(*h).unwrap(), value));
+        }
+
+        // A purged value comes back under a fresh handle.
+        let (h, _revived) = interner.intern(Config(1)).unwrap();
+        assert_eq!(h, 6);
+        assert!(interner.resolve(dead[0]).is_none());
+        assert_eq!(interner.lookup_handle(&Config(1)), Some(6));
+    }
+
+    #[test]
+    fn test_intern_ref_str() {
+        let mut interner =
+            WeakInterner::<alloc::string::String, RandomState>::new(RandomState::new());
+        let (a, first) = interner.intern_ref("a").unwrap();
+        let (b, _) = interner.intern_ref("b").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(interner.intern_ref("a").unwrap().0, a);
+        assert_eq!(interner.lookup_handle("a"), Some(a));
+        assert_eq!(first.as_str(), "a");
+        assert!(!interner.is_empty());
+    }
+
+    #[test]
+    fn test_compact_after_purge() {
+        let mut interner =
+            WeakInterner::<alloc::string::String, RandomState>::new(RandomState::new());
+        let (a, _a) = interner.intern_ref("a").unwrap();
+        let (b, b_value) = interner.intern_ref("b").unwrap();
+        let (_, c_value) = interner.intern_ref("c").unwrap();
+        drop(b_value);
+        assert_eq!(interner.purge(), 1);
+
+        let remap = interner.compact();
+        assert_eq!(remap, [Some(a), None, Some(1)]);
+        assert!(Arc::ptr_eq(&interner.resolve(1).unwrap(), &c_value));
+        assert_eq!(interner.lookup_handle("c"), Some(1));
+        assert_eq!(interner.resolve(b), Some(c_value));
+        assert_eq!(interner.intern_ref("d").unwrap().0, 2);
+    }
+}